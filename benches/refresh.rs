@@ -0,0 +1,80 @@
+//! Compares a full `refresh_all` against the targeted refreshes the
+//! collector does for what the open view demands: CPU and memory alone
+//! while no view shows processes or disks, plus the process table while
+//! the Processes tab is open, plus each process's tasks while its Threads
+//! column is.
+//!
+//! The collector lives in the binary, out of a bench's reach, so each case
+//! repeats the sysinfo calls `SysinfoCollector::refresh` makes for its
+//! `RefreshDemand`.
+//!
+//! Run with `cargo bench --bench refresh`; the gap grows with the number of
+//! processes on the host.
+
+use std::time::{Duration, Instant};
+
+use sysinfo::{Disks, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+const ROUNDS: u32 = 20;
+
+fn time(label: &str, mut refresh: impl FnMut()) -> Duration {
+    refresh();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        refresh();
+    }
+    let per_round = start.elapsed() / ROUNDS;
+    println!("{label:<28} {per_round:>12.2?} per refresh");
+    per_round
+}
+
+/// What the collector loads per process, with or without the tasks the
+/// Threads column counts.
+fn process_kind(threads: bool) -> ProcessRefreshKind {
+    let kind = ProcessRefreshKind::nothing()
+        .with_memory()
+        .with_cpu()
+        .with_disk_usage()
+        .with_exe(UpdateKind::OnlyIfNotSet)
+        .with_user(UpdateKind::OnlyIfNotSet);
+    if threads {
+        kind.with_tasks()
+    } else {
+        kind.without_tasks()
+    }
+}
+
+fn main() {
+    let mut system = System::new_all();
+    let mut disks = Disks::new_with_refreshed_list();
+    println!("{} processes", system.processes().len());
+
+    let all = time("refresh_all", || {
+        system.refresh_all();
+        disks.refresh(true);
+    });
+    let report = |label: &str, duration: Duration| {
+        println!(
+            "{label} takes {:.1}% of refresh_all",
+            duration.as_secs_f64() / all.as_secs_f64() * 100.0
+        );
+    };
+
+    let targeted = time("targeted (cpu + memory)", || {
+        system.refresh_cpu_usage();
+        system.refresh_memory();
+    });
+    report("targeted refresh", targeted);
+
+    for (label, threads) in [
+        ("on demand (processes)", false),
+        ("on demand (+ threads)", true),
+    ] {
+        let on_demand = time(label, || {
+            system.refresh_cpu_usage();
+            system.refresh_memory();
+            system.refresh_processes_specifics(ProcessesToUpdate::All, true, process_kind(threads));
+        });
+        report(label, on_demand);
+    }
+}
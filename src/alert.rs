@@ -0,0 +1,317 @@
+use std::{
+    collections::VecDeque,
+    fmt::Write as _,
+    time::{Duration, Instant},
+};
+
+use crate::util::{Snapshot, format_timestamp};
+
+/// Alerts kept in the log before the oldest are dropped.
+pub const ALERT_LOG_CAPACITY: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlertKind {
+    Cpu,
+    Memory,
+}
+
+impl AlertKind {
+    pub const ALL: [AlertKind; 2] = [AlertKind::Cpu, AlertKind::Memory];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertKind::Cpu => "CPU",
+            AlertKind::Memory => "Memory",
+        }
+    }
+
+    /// Usage in percent the alert watches in `snapshot`.
+    pub fn value(self, snapshot: &Snapshot) -> f32 {
+        match self {
+            AlertKind::Cpu => snapshot.cpu.usage,
+            AlertKind::Memory => {
+                let memory = &snapshot.memory;
+                if memory.total > 0 {
+                    (memory.used as f64 / memory.total as f64 * 100.0) as f32
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Warning and alert levels of one metric, in percent.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Thresholds {
+    pub warning: Option<f32>,
+    /// Rings the bell in the dashboard; critical for `--check`.
+    pub alert: Option<f32>,
+}
+
+impl Thresholds {
+    pub fn status(&self, value: f32) -> Status {
+        let reached = |threshold: Option<f32>| threshold.is_some_and(|t| value >= t);
+        if reached(self.alert) {
+            Status::Critical
+        } else if reached(self.warning) {
+            Status::Warning
+        } else {
+            Status::Ok
+        }
+    }
+}
+
+/// How a sample stands against the thresholds, worst last so the overall
+/// status is the maximum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    Ok,
+    Warning,
+    Critical,
+    /// Nothing could be measured, e.g. because collection failed.
+    Unknown,
+}
+
+impl Status {
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Warning => "WARNING",
+            Status::Critical => "CRITICAL",
+            Status::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// Exit code in the Nagios plugin convention.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Status::Ok => 0,
+            Status::Warning => 1,
+            Status::Critical => 2,
+            Status::Unknown => 3,
+        }
+    }
+}
+
+/// Checks `snapshot` against the thresholds of every alert kind. Returns the
+/// worst status and a one-line summary with Nagios performance data, e.g.
+/// `CRITICAL - CPU 97.2% (>= 90.0%), Memory 41.0% | cpu=97.2%;80;90 memory=41.0%;;`.
+pub fn check(
+    snapshot: &Snapshot,
+    thresholds: impl Fn(AlertKind) -> Thresholds,
+) -> (Status, String) {
+    let mut worst = Status::Ok;
+    let mut readings = Vec::new();
+    let mut perf = Vec::new();
+    for kind in AlertKind::ALL {
+        let limits = thresholds(kind);
+        let value = kind.value(snapshot);
+        let status = limits.status(value);
+        worst = worst.max(status);
+        let mut reading = format!("{} {value:.1}%", kind.label());
+        let reached = match status {
+            Status::Ok | Status::Unknown => None,
+            Status::Warning => limits.warning,
+            Status::Critical => limits.alert,
+        };
+        if let Some(threshold) = reached {
+            let _ = write!(reading, " (>= {threshold:.1}%)");
+        }
+        readings.push(reading);
+        let level = |threshold: Option<f32>| threshold.map(|t| t.to_string()).unwrap_or_default();
+        perf.push(format!(
+            "{}={value:.1}%;{};{}",
+            kind.label().to_lowercase(),
+            level(limits.warning),
+            level(limits.alert)
+        ));
+    }
+    let summary = format!(
+        "{} - {} | {}",
+        worst.label(),
+        readings.join(", "),
+        perf.join(" ")
+    );
+    (worst, summary)
+}
+
+/// Debounce and cooldown bookkeeping for a single alert kind.
+#[derive(Default)]
+pub struct AlertTracker {
+    breaches: u32,
+    last_fired: Option<Instant>,
+}
+
+impl AlertTracker {
+    /// Records one sample and returns whether the alert should fire now.
+    ///
+    /// The threshold has to be breached for `required` samples in a row, and
+    /// once fired the alert stays quiet for `cooldown` even if it flaps.
+    pub fn observe(
+        &mut self,
+        breached: bool,
+        required: u32,
+        cooldown: Duration,
+        now: Instant,
+    ) -> bool {
+        if !breached {
+            self.breaches = 0;
+            return false;
+        }
+
+        self.breaches = self.breaches.saturating_add(1);
+        if self.breaches < required {
+            return false;
+        }
+        let cooled_down = self
+            .last_fired
+            .is_none_or(|fired| now.duration_since(fired) >= cooldown);
+        if cooled_down {
+            self.last_fired = Some(now);
+        }
+        cooled_down
+    }
+
+    pub fn is_active(&self, required: u32) -> bool {
+        self.breaches >= required
+    }
+}
+
+/// One fired alert.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlertEvent {
+    /// Seconds since the Unix epoch of the sample that fired it.
+    pub timestamp: u64,
+    pub kind: AlertKind,
+    /// Usage in percent at that sample.
+    pub value: f32,
+    /// Threshold in percent it was compared against.
+    pub threshold: f32,
+}
+
+/// The most recent fired alerts of the session, oldest first.
+pub struct AlertLog {
+    events: VecDeque<AlertEvent>,
+    capacity: usize,
+}
+
+impl Default for AlertLog {
+    fn default() -> Self {
+        Self::with_capacity(ALERT_LOG_CAPACITY)
+    }
+}
+
+impl AlertLog {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Appends `event`, dropping the oldest one when the log is full.
+    pub fn push(&mut self, event: AlertEvent) {
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &AlertEvent> {
+        self.events.iter()
+    }
+
+    /// The log as CSV with a header row, oldest alert first.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("time_utc,metric,value_percent,threshold_percent\n");
+        for event in &self.events {
+            let _ = writeln!(
+                csv,
+                "{},{},{:.1},{:.1}",
+                format_timestamp(event.timestamp),
+                event.kind.label(),
+                event.value,
+                event.threshold
+            );
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(timestamp: u64) -> AlertEvent {
+        AlertEvent {
+            timestamp,
+            kind: AlertKind::Cpu,
+            value: 97.25,
+            threshold: 90.0,
+        }
+    }
+
+    #[test]
+    fn check_reports_the_worst_status() {
+        let mut snapshot = Snapshot::default();
+        snapshot.cpu.usage = 85.0;
+        snapshot.memory.total = 100;
+        snapshot.memory.used = 41;
+        let thresholds = |kind| match kind {
+            AlertKind::Cpu => Thresholds {
+                warning: Some(80.0),
+                alert: Some(90.0),
+            },
+            AlertKind::Memory => Thresholds::default(),
+        };
+
+        let (status, summary) = check(&snapshot, thresholds);
+        assert_eq!(status, Status::Warning);
+        assert_eq!(status.exit_code(), 1);
+        assert_eq!(
+            summary,
+            "WARNING - CPU 85.0% (>= 80.0%), Memory 41.0% | cpu=85.0%;80;90 memory=41.0%;;"
+        );
+
+        snapshot.cpu.usage = 97.25;
+        assert_eq!(check(&snapshot, thresholds).0, Status::Critical);
+        snapshot.cpu.usage = 10.0;
+        assert_eq!(check(&snapshot, thresholds).0, Status::Ok);
+        assert_eq!(Status::Unknown.exit_code(), 3);
+    }
+
+    #[test]
+    fn log_keeps_the_newest_events() {
+        let mut log = AlertLog::with_capacity(2);
+        for timestamp in 1..=3 {
+            log.push(event(timestamp));
+        }
+        let timestamps: Vec<_> = log.iter().map(|event| event.timestamp).collect();
+        assert_eq!(timestamps, [2, 3]);
+        log.clear();
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn log_exports_as_csv() {
+        let mut log = AlertLog::default();
+        log.push(event(60));
+        assert_eq!(
+            log.to_csv(),
+            "time_utc,metric,value_percent,threshold_percent\n1970-01-01 00:01:00,CPU,97.2,90.0\n"
+        );
+    }
+}
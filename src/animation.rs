@@ -0,0 +1,107 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// How long a gauge takes to reach a new value.
+pub const ANIMATION_DURATION: Duration = Duration::from_millis(300);
+
+/// Time between redraws while a gauge is moving, about 30 frames a second.
+pub const ANIMATION_FRAME: Duration = Duration::from_millis(33);
+
+/// A value easing from one number to another.
+struct Tween {
+    from: f64,
+    to: f64,
+    started: Instant,
+}
+
+impl Tween {
+    fn value(&self, now: Instant) -> f64 {
+        let t = (now.duration_since(self.started).as_secs_f64() / ANIMATION_DURATION.as_secs_f64())
+            .min(1.0);
+        // Ease out: fast at first, settling gently on the new value.
+        let eased = 1.0 - (1.0 - t).powi(3);
+        self.from + (self.to - self.from) * eased
+    }
+
+    fn is_done(&self, now: Instant) -> bool {
+        self.from == self.to || now.duration_since(self.started) >= ANIMATION_DURATION
+    }
+}
+
+/// Displayed values of the animated gauges, keyed by gauge.
+#[derive(Default)]
+pub struct GaugeAnimations {
+    tweens: HashMap<String, Tween>,
+}
+
+impl GaugeAnimations {
+    /// Value to draw the gauge `key` at, given the value it should end up
+    /// at. A gauge seen for the first time shows `target` straight away; a
+    /// changed target starts a new tween from wherever the gauge is now.
+    pub fn value(&mut self, key: &str, target: f64, now: Instant) -> f64 {
+        let Some(tween) = self.tweens.get_mut(key) else {
+            self.tweens.insert(
+                key.to_string(),
+                Tween {
+                    from: target,
+                    to: target,
+                    started: now,
+                },
+            );
+            return target;
+        };
+        if tween.to != target {
+            *tween = Tween {
+                from: tween.value(now),
+                to: target,
+                started: now,
+            };
+        }
+        tween.value(now)
+    }
+
+    /// Whether any gauge is still on its way, so frames should come faster
+    /// than samples.
+    pub fn is_animating(&self, now: Instant) -> bool {
+        self.tweens.values().any(|tween| !tween.is_done(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gauges_ease_towards_new_values() {
+        let mut animations = GaugeAnimations::default();
+        let start = Instant::now();
+        assert_eq!(animations.value("cpu", 20.0, start), 20.0);
+        assert!(!animations.is_animating(start));
+
+        let halfway = start + ANIMATION_DURATION / 2;
+        animations.value("cpu", 80.0, start);
+        let value = animations.value("cpu", 80.0, halfway);
+        assert!(
+            value > 50.0 && value < 80.0,
+            "eased past the midpoint: {value}"
+        );
+        assert!(animations.is_animating(halfway));
+
+        let end = start + ANIMATION_DURATION;
+        assert_eq!(animations.value("cpu", 80.0, end), 80.0);
+        assert!(!animations.is_animating(end));
+    }
+
+    #[test]
+    fn a_new_target_starts_from_the_shown_value() {
+        let mut animations = GaugeAnimations::default();
+        let start = Instant::now();
+        animations.value("mem", 0.0, start);
+        animations.value("mem", 100.0, start);
+        let halfway = start + ANIMATION_DURATION / 2;
+        let shown = animations.value("mem", 100.0, halfway);
+        assert_eq!(animations.value("mem", 0.0, halfway), shown);
+    }
+}
@@ -0,0 +1,70 @@
+use std::{
+    net::{SocketAddr, TcpListener as StdTcpListener},
+    sync::{Arc, Mutex},
+};
+
+use tokio::{
+    io::{self, AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    runtime::Handle,
+};
+
+use crate::util::{SystemState, lock_state};
+
+/// Serves the latest `Snapshot` as JSON on `GET /` and `GET /snapshot`.
+///
+/// Binding happens up front so a failure can be reported before the TUI
+/// takes over the terminal; the listener itself runs on `runtime`, next to
+/// the metric collection.
+pub fn spawn(
+    runtime: &Handle,
+    addr: SocketAddr,
+    system_state: Arc<Mutex<SystemState>>,
+) -> Result<(), io::Error> {
+    let listener = StdTcpListener::bind(addr)?;
+    listener.set_nonblocking(true)?;
+
+    runtime.spawn(async move {
+        let listener = match TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(_) => return,
+        };
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let state = Arc::clone(&system_state);
+            tokio::spawn(async move {
+                let _ = handle_connection(stream, state).await;
+            });
+        }
+    });
+
+    Ok(())
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    system_state: Arc<Mutex<SystemState>>,
+) -> Result<(), io::Error> {
+    let mut buf = [0u8; 1024];
+    let read = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..read]);
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+    let (status, body) = match path {
+        "/" | "/snapshot" => {
+            let body = serde_json::to_string(&lock_state(&system_state).snapshot)
+                .unwrap_or_else(|e| format!("{{\"error\":\"{e}\"}}"));
+            ("200 OK", body)
+        }
+        _ => ("404 Not Found", "{\"error\":\"not found\"}".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
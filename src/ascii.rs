@@ -0,0 +1,148 @@
+//! ASCII fallback for terminals that can't show box drawing, block or
+//! braille characters. The symbols the dashboard draws itself come from a
+//! `Glyphs` set picked up front; only what tui 0.19 draws without letting
+//! the caller choose is rewritten in the finished frame.
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    symbols::{self, bar, line},
+    widgets::Widget,
+};
+
+/// Whether the locale says the terminal expects UTF-8. The first of
+/// `LC_ALL`, `LC_CTYPE` and `LANG` that is set decides, as in libc; with
+/// none set, Unicode is assumed since nearly every terminal handles it.
+pub fn unicode_locale() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .is_none_or(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+/// Symbols the dashboard draws itself, in a Unicode and an ASCII flavour.
+pub struct Glyphs {
+    pub sort_ascending: &'static str,
+    pub sort_descending: &'static str,
+    pub marked: &'static str,
+    pub collapsed: &'static str,
+    pub expanded: &'static str,
+    /// Colored square in front of a legend entry.
+    pub swatch: &'static str,
+    /// Unit of temperatures.
+    pub celsius: &'static str,
+    /// Divider between the tab titles.
+    pub divider: &'static str,
+    pub bars: bar::Set,
+}
+
+const UNICODE: Glyphs = Glyphs {
+    sort_ascending: "▲",
+    sort_descending: "▼",
+    marked: "✓",
+    collapsed: "▸",
+    expanded: "▾",
+    swatch: "■",
+    celsius: "°C",
+    divider: line::VERTICAL,
+    bars: bar::NINE_LEVELS,
+};
+
+const ASCII: Glyphs = Glyphs {
+    sort_ascending: "^",
+    sort_descending: "v",
+    marked: "x",
+    collapsed: ">",
+    expanded: "v",
+    swatch: "#",
+    celsius: "C",
+    divider: "|",
+    bars: bar::Set {
+        full: "#",
+        seven_eighths: "#",
+        three_quarters: "#",
+        five_eighths: "|",
+        half: "|",
+        three_eighths: ":",
+        one_quarter: ":",
+        one_eighth: ".",
+        empty: " ",
+    },
+};
+
+impl Glyphs {
+    pub fn new(ascii: bool) -> &'static Glyphs {
+        if ascii { &ASCII } else { &UNICODE }
+    }
+}
+
+/// ASCII stand-in for a border or chart symbol, the parts tui 0.19 draws
+/// from fixed sets: `Block` only offers Unicode `BorderType`s, and the
+/// nearest thing to an ASCII chart marker is `Marker::Dot`.
+fn to_ascii(symbol: &str) -> Option<&'static str> {
+    if symbol == symbols::DOT {
+        return Some("*");
+    }
+    [line::NORMAL, line::ROUNDED, line::DOUBLE, line::THICK]
+        .iter()
+        .find_map(|set| {
+            if symbol == set.horizontal {
+                Some("-")
+            } else if symbol == set.vertical {
+                Some("|")
+            } else if [
+                set.top_left,
+                set.top_right,
+                set.bottom_left,
+                set.bottom_right,
+                set.vertical_left,
+                set.vertical_right,
+                set.horizontal_down,
+                set.horizontal_up,
+                set.cross,
+            ]
+            .contains(&symbol)
+            {
+                Some("+")
+            } else {
+                None
+            }
+        })
+}
+
+/// Widget that replaces border lines and chart dots in its area; render it
+/// over the whole frame after everything else.
+pub struct AsciiFallback;
+
+impl Widget for AsciiFallback {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                if let Some(ascii) = to_ascii(&cell.symbol) {
+                    cell.symbol = ascii.to_string();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbols_map_to_ascii() {
+        assert_eq!(to_ascii("┌"), Some("+"));
+        assert_eq!(to_ascii("╯"), Some("+"));
+        assert_eq!(to_ascii("━"), Some("-"));
+        assert_eq!(to_ascii("║"), Some("|"));
+        assert_eq!(to_ascii("•"), Some("*"));
+        assert_eq!(to_ascii("é"), None);
+        assert_eq!(to_ascii("q"), None);
+    }
+}
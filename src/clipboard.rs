@@ -0,0 +1,111 @@
+//! Copying text to the system clipboard through the platform's command-line
+//! tool, so no clipboard library or display connection is linked in.
+
+use std::{
+    io::{self, Write},
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Tools tried in order, with their arguments.
+#[cfg(target_os = "macos")]
+const TOOLS: &[(&str, &[&str])] = &[("pbcopy", &[])];
+
+#[cfg(windows)]
+const TOOLS: &[(&str, &[&str])] = &[("clip", &[])];
+
+#[cfg(not(any(target_os = "macos", windows)))]
+const TOOLS: &[(&str, &[&str])] = &[
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// How long a tool gets to take the text before it counts as hung, as
+/// xclip can be with no display to talk to. Copying runs on the UI thread.
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Copies `text` with the first tool that is installed and works, and
+/// returns its name. A tool that fails, like wl-copy outside Wayland, or
+/// hangs makes way for the next.
+pub fn copy(text: &str) -> Result<&'static str, io::Error> {
+    copy_with(TOOLS, text, TIMEOUT)
+}
+
+fn copy_with(
+    tools: &[(&'static str, &[&str])],
+    text: &str,
+    timeout: Duration,
+) -> Result<&'static str, io::Error> {
+    for &(tool, args) in tools {
+        let mut child = match Command::new(tool)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        // Written from a thread of its own, as a tool that never reads
+        // would block it on a full pipe. Dropping stdin closes it, which is
+        // what the tools wait for.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let text = text.to_string();
+        let writer = thread::spawn(move || stdin.write_all(text.as_bytes()));
+        match wait_timeout(&mut child, timeout)? {
+            Some(status) => {
+                let written = writer.join().is_ok_and(|written| written.is_ok());
+                if written && status.success() {
+                    return Ok(tool);
+                }
+            }
+            // Killing it also ends the writer with a broken pipe.
+            None => {
+                let _ = child.kill();
+                child.wait()?;
+            }
+        }
+    }
+    let names: Vec<_> = tools.iter().map(|(tool, _)| *tool).collect();
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no working clipboard tool ({})", names.join(", ")),
+    ))
+}
+
+/// The exit status of `child`, or `None` if it's still running after
+/// `timeout`.
+fn wait_timeout(child: &mut Child, timeout: Duration) -> Result<Option<ExitStatus>, io::Error> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_hung_tool_makes_way_for_the_next() {
+        let started = Instant::now();
+        let tools: &[(&str, &[&str])] = &[("sleep", &["10"]), ("cat", &[])];
+        let tool = copy_with(tools, "text", Duration::from_millis(200)).unwrap();
+        assert_eq!(tool, "cat");
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let tools: &[(&str, &[&str])] = &[("sleep", &["10"])];
+        let error = copy_with(tools, "text", Duration::from_millis(200)).unwrap_err();
+        assert_eq!(error.to_string(), "no working clipboard tool (sleep)");
+    }
+}
@@ -0,0 +1,140 @@
+//! Memory and CPU limits of the cgroup dashoxide runs in. Inside a
+//! container these matter more than the host totals sysinfo reports.
+
+use crate::util::CgroupSnapshot;
+
+/// cgroup v1 reports "no memory limit" as the largest page-aligned `i64`
+/// rather than a marker value; anything this large is no real limit.
+#[cfg(any(target_os = "linux", test))]
+const V1_UNLIMITED: u64 = 1 << 62;
+
+/// Reads the limits of the current process's cgroup. Returns `None` when
+/// cgroups aren't available or neither memory nor CPU is limited, so callers
+/// fall back to host totals.
+#[cfg(target_os = "linux")]
+pub fn read() -> Option<CgroupSnapshot> {
+    use std::{fs, path::Path};
+
+    let root = Path::new("/sys/fs/cgroup");
+    let membership = fs::read_to_string("/proc/self/cgroup").ok()?;
+    // Inside a cgroup namespace the listed path may not exist under our
+    // mount, while the controller root is the container's own cgroup.
+    let dir = |controller: &str| {
+        let base = root.join(controller);
+        cgroup_path(&membership, controller)
+            .map(|path| base.join(path.trim_start_matches('/')))
+            .filter(|dir| dir.is_dir())
+            .unwrap_or(base)
+    };
+    let read = |path: std::path::PathBuf| fs::read_to_string(path).ok();
+
+    let snapshot = if root.join("cgroup.controllers").exists() {
+        let dir = dir("");
+        CgroupSnapshot {
+            version: 2,
+            memory_limit: read(dir.join("memory.max")).and_then(|s| parse_v2_limit(&s)),
+            memory_used: read(dir.join("memory.current")).and_then(|s| s.trim().parse().ok()),
+            cpu_limit: read(dir.join("cpu.max")).and_then(|s| parse_v2_cpu(&s)),
+        }
+    } else {
+        let memory = dir("memory");
+        let cpu = dir("cpu");
+        CgroupSnapshot {
+            version: 1,
+            memory_limit: read(memory.join("memory.limit_in_bytes"))
+                .and_then(|s| parse_v1_limit(&s)),
+            memory_used: read(memory.join("memory.usage_in_bytes"))
+                .and_then(|s| s.trim().parse().ok()),
+            cpu_limit: read(cpu.join("cpu.cfs_quota_us")).and_then(|quota| {
+                let period = read(cpu.join("cpu.cfs_period_us"))?;
+                parse_v1_cpu(&quota, &period)
+            }),
+        }
+    };
+
+    (snapshot.memory_limit.is_some() || snapshot.cpu_limit.is_some()).then_some(snapshot)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read() -> Option<CgroupSnapshot> {
+    None
+}
+
+/// Finds the cgroup path for `controller` in `/proc/self/cgroup`. An empty
+/// controller selects the v2 unified hierarchy (`0::/path`).
+#[cfg(any(target_os = "linux", test))]
+fn cgroup_path<'a>(membership: &'a str, controller: &str) -> Option<&'a str> {
+    membership.lines().find_map(|line| {
+        let mut fields = line.splitn(3, ':');
+        let (_, controllers, path) = (fields.next()?, fields.next()?, fields.next()?);
+        let matches = if controller.is_empty() {
+            controllers.is_empty()
+        } else {
+            controllers.split(',').any(|c| c == controller)
+        };
+        matches.then_some(path)
+    })
+}
+
+/// `memory.max` holds a byte count or "max".
+#[cfg(any(target_os = "linux", test))]
+fn parse_v2_limit(contents: &str) -> Option<u64> {
+    contents.trim().parse().ok()
+}
+
+/// `cpu.max` holds "<quota> <period>", with "max" as the quota when
+/// unlimited.
+#[cfg(any(target_os = "linux", test))]
+fn parse_v2_cpu(contents: &str) -> Option<f32> {
+    let mut fields = contents.split_whitespace();
+    let quota: f32 = fields.next()?.parse().ok()?;
+    let period: f32 = fields.next()?.parse().ok()?;
+    (period > 0.0).then(|| quota / period)
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_v1_limit(contents: &str) -> Option<u64> {
+    contents
+        .trim()
+        .parse()
+        .ok()
+        .filter(|&limit| limit < V1_UNLIMITED)
+}
+
+/// A quota of -1 means unlimited.
+#[cfg(any(target_os = "linux", test))]
+fn parse_v1_cpu(quota: &str, period: &str) -> Option<f32> {
+    let quota: i64 = quota.trim().parse().ok()?;
+    let period: i64 = period.trim().parse().ok()?;
+    (quota > 0 && period > 0).then(|| quota as f32 / period as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_paths_in_either_hierarchy() {
+        let membership = "4:memory:/docker/abc\n2:cpu,cpuacct:/docker/abc\n0::/user.slice\n";
+        assert_eq!(cgroup_path(membership, "memory"), Some("/docker/abc"));
+        assert_eq!(cgroup_path(membership, "cpu"), Some("/docker/abc"));
+        assert_eq!(cgroup_path(membership, ""), Some("/user.slice"));
+        assert_eq!(cgroup_path(membership, "pids"), None);
+    }
+
+    #[test]
+    fn v2_max_means_no_limit() {
+        assert_eq!(parse_v2_limit("536870912\n"), Some(536_870_912));
+        assert_eq!(parse_v2_limit("max\n"), None);
+        assert_eq!(parse_v2_cpu("150000 100000\n"), Some(1.5));
+        assert_eq!(parse_v2_cpu("max 100000\n"), None);
+    }
+
+    #[test]
+    fn v1_sentinels_mean_no_limit() {
+        assert_eq!(parse_v1_limit("1073741824\n"), Some(1_073_741_824));
+        assert_eq!(parse_v1_limit("9223372036854771712\n"), None);
+        assert_eq!(parse_v1_cpu("200000\n", "100000\n"), Some(2.0));
+        assert_eq!(parse_v1_cpu("-1\n", "100000\n"), None);
+    }
+}
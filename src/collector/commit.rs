@@ -0,0 +1,35 @@
+//! The Windows commit charge: memory promised to processes, backed by RAM
+//! and the page file together. Windows has no swap as such; this is what
+//! Task Manager shows as "Committed" and what runs out when it's full.
+//! sysinfo only reports the part beyond physical memory, so a machine
+//! committing less than its RAM would show nothing.
+
+/// Committed bytes and the commit limit.
+#[cfg(windows)]
+pub fn read() -> Option<(u64, u64)> {
+    use windows_sys::Win32::System::ProcessStatus::{
+        K32GetPerformanceInfo, PERFORMANCE_INFORMATION,
+    };
+
+    let size = std::mem::size_of::<PERFORMANCE_INFORMATION>() as u32;
+    // SAFETY: PERFORMANCE_INFORMATION is plain data, and the call writes at
+    // most `size` bytes into it.
+    let info = unsafe {
+        let mut info: PERFORMANCE_INFORMATION = std::mem::zeroed();
+        info.cb = size;
+        if K32GetPerformanceInfo(&mut info, size) == 0 {
+            return None;
+        }
+        info
+    };
+    let page_size = info.PageSize as u64;
+    Some((
+        info.CommitTotal as u64 * page_size,
+        info.CommitLimit as u64 * page_size,
+    ))
+}
+
+#[cfg(not(windows))]
+pub fn read() -> Option<(u64, u64)> {
+    None
+}
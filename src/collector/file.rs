@@ -0,0 +1,31 @@
+use std::{fs, io, path::PathBuf};
+
+use super::Collector;
+use crate::util::Snapshot;
+
+/// Reads a snapshot in `--report` format from a file on every sample.
+///
+/// Whatever writes the file decides what the dashboard shows, which makes
+/// this handy for demos, for reproducing a layout bug from someone else's
+/// report, and for feeding in data from a source dashoxide doesn't know.
+pub struct FileCollector {
+    path: PathBuf,
+}
+
+impl FileCollector {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Collector for FileCollector {
+    fn collect(&mut self) -> Result<Snapshot, io::Error> {
+        let contents = fs::read(&self.path)?;
+        serde_json::from_slice(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid snapshot in {}: {e}", self.path.display()),
+            )
+        })
+    }
+}
@@ -0,0 +1,515 @@
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    io,
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use sysinfo::{
+    Components, Disks, MINIMUM_CPU_UPDATE_INTERVAL, Networks, Pid, Process, ProcessRefreshKind,
+    ProcessStatus, ProcessesToUpdate, System, ThreadKind, UpdateKind, Users,
+};
+
+use super::{
+    Collector, RefreshDemand, cgroup, commit, macos, meminfo, numa, priority, procstat, smaps,
+};
+use crate::util::{
+    CpuSnapshot, DiskSnapshot, InterfaceSnapshot, MemorySnapshot, ProcessSnapshot, ProcessState,
+    SensorSnapshot, Snapshot, os_str_display,
+};
+
+/// Collects from the local machine through sysinfo.
+pub struct SysinfoCollector {
+    system: System,
+    disks: Disks,
+    networks: Networks,
+    components: Components,
+    /// Read once at startup for resolving process owners.
+    users: Users,
+    battery: Option<bool>,
+    /// How often processes and disks are refreshed while nobody is looking
+    /// at them.
+    process_interval: Duration,
+    demand: RefreshDemand,
+    /// Refresh every source on every tick regardless of `demand`.
+    refresh_everything: bool,
+    last_process_refresh: Instant,
+    /// The last `refresh` left processes as they were.
+    processes_stale: bool,
+    disks_stale: bool,
+    smc: macos::SmcSensors,
+}
+
+impl SysinfoCollector {
+    pub fn new(process_interval: Duration, refresh_everything: bool) -> Self {
+        // Usage is the difference between two readings at least
+        // `MINIMUM_CPU_UPDATE_INTERVAL` apart, so refreshing straight away
+        // would make the first sample show 0% or a bogus figure.
+        let mut system = System::new_all();
+        std::thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+        system.refresh_all();
+
+        Self {
+            system,
+            disks: Disks::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+            components: Components::new_with_refreshed_list(),
+            users: Users::new_with_refreshed_list(),
+            battery: detect_battery(),
+            process_interval,
+            demand: RefreshDemand::default(),
+            refresh_everything,
+            last_process_refresh: Instant::now(),
+            processes_stale: false,
+            disks_stale: false,
+            smc: macos::SmcSensors::default(),
+        }
+    }
+
+    /// Per-process data is the expensive part of a refresh, so it runs on
+    /// its own cadence unless demanded.
+    fn refresh_processes(&mut self) {
+        // What `refresh_processes` loads, plus the owner of new processes.
+        let kind = ProcessRefreshKind::nothing()
+            .with_memory()
+            .with_cpu()
+            .with_disk_usage()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_user(UpdateKind::OnlyIfNotSet);
+        let kind = if self.demand.threads {
+            kind.with_tasks()
+        } else {
+            kind.without_tasks()
+        };
+        self.system
+            .refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+        self.last_process_refresh = Instant::now();
+    }
+
+    /// Refreshing with `true` re-reads the mount and interface lists too, so
+    /// hot-plugged disks and interfaces that come up (or go away) mid-session
+    /// show up in the next snapshot that covers them.
+    fn refresh(&mut self) {
+        if self.refresh_everything {
+            self.system.refresh_all();
+            self.disks.refresh(true);
+            self.components.refresh(true);
+            self.last_process_refresh = Instant::now();
+        } else {
+            self.system.refresh_cpu_usage();
+            self.system.refresh_memory();
+
+            let background_due = self.last_process_refresh.elapsed() >= self.process_interval;
+            self.processes_stale = !self.demand.processes && !background_due;
+            if !self.processes_stale {
+                self.refresh_processes();
+            }
+            self.disks_stale = !self.demand.disks && !background_due;
+            if !self.disks_stale {
+                self.disks.refresh(true);
+            }
+        }
+        self.networks.refresh(true);
+    }
+
+    fn snapshot(&mut self) -> Snapshot {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let disks = self
+            .disks
+            .list()
+            .iter()
+            .map(|disk| {
+                let usage = disk.usage();
+                DiskSnapshot {
+                    mount_point: os_str_display(disk.mount_point().as_os_str()),
+                    device: disk_device(disk),
+                    file_system: os_str_display(disk.file_system()),
+                    total: disk.total_space(),
+                    available: disk.available_space(),
+                    removable: disk.is_removable(),
+                    read_only: disk.is_read_only(),
+                    total_read: usage.total_read_bytes,
+                    total_written: usage.total_written_bytes,
+                    read_rate: 0,
+                    write_rate: 0,
+                }
+            })
+            .collect();
+
+        let networks = self
+            .networks
+            .list()
+            .iter()
+            .map(|(name, data)| InterfaceSnapshot {
+                name: name.clone(),
+                rx_rate: 0,
+                tx_rate: 0,
+                total_received: data.total_received(),
+                total_transmitted: data.total_transmitted(),
+                up: link_up(name),
+                mac_address: Some(data.mac_address())
+                    .filter(|mac| !mac.is_unspecified())
+                    .map(|mac| mac.to_string()),
+                mtu: Some(data.mtu()).filter(|&mtu| mtu > 0),
+                addresses: data
+                    .ip_networks()
+                    .iter()
+                    .map(|network| format!("{}/{}", network.addr, network.prefix))
+                    .collect(),
+            })
+            .collect();
+
+        let swap_pids: HashSet<u32> = self.demand.swap_pids.iter().copied().collect();
+        let processes = self
+            .system
+            .processes()
+            .iter()
+            // Walking tasks lists each thread as a process of its own too;
+            // leaving them out keeps the table the same either way.
+            .filter(|(_, process)| process.thread_kind() != Some(ThreadKind::Userland))
+            .map(|(pid, process)| {
+                let disk_usage = process.disk_usage();
+                // sysinfo reads these from /proc on every call.
+                let (open_files, open_files_limit) =
+                    if self.demand.open_files || self.refresh_everything {
+                        (process.open_files(), process.open_files_limit())
+                    } else {
+                        (None, None)
+                    };
+                ProcessSnapshot {
+                    pid: pid.as_u32(),
+                    name: os_str_display(process.name()),
+                    cpu_usage: process.cpu_usage(),
+                    memory: process.memory(),
+                    status: process_state(process.status()),
+                    disk_read: disk_usage.read_bytes,
+                    disk_written: disk_usage.written_bytes,
+                    disk_total_read: disk_usage.total_read_bytes,
+                    disk_total_written: disk_usage.total_written_bytes,
+                    disk_read_rate: 0,
+                    disk_write_rate: 0,
+                    threads: process.tasks().map(|tasks| tasks.len()),
+                    user: process
+                        .user_id()
+                        .and_then(|uid| self.users.get_user_by_id(uid))
+                        .map(|user| user.name().to_string()),
+                    start_time: process.start_time(),
+                    open_files,
+                    open_files_limit,
+                    swap: if self.refresh_everything
+                        || self.demand.swap_all
+                        || swap_pids.contains(&pid.as_u32())
+                    {
+                        smaps::swap(pid.as_u32())
+                    } else {
+                        None
+                    },
+                    nice: priority::nice(pid.as_u32()),
+                }
+            })
+            .collect();
+
+        let commit = commit::read();
+        let swap_is_commit = commit.is_some();
+        let (swap_used, swap_total) =
+            commit.unwrap_or_else(|| (self.system.used_swap(), self.system.total_swap()));
+
+        let (smc_sensors, fans) = self.smc.read();
+        let sensors = self
+            .components
+            .list()
+            .iter()
+            .map(|component| SensorSnapshot {
+                label: component.label().to_string(),
+                temperature: component.temperature(),
+            })
+            .chain(smc_sensors)
+            .collect();
+
+        Snapshot {
+            timestamp,
+            cpu: CpuSnapshot {
+                usage: self.system.global_cpu_usage(),
+                cores: self
+                    .system
+                    .cpus()
+                    .iter()
+                    .map(|cpu| cpu.cpu_usage())
+                    .collect(),
+            },
+            memory: MemorySnapshot {
+                used: self.system.used_memory(),
+                total: self.system.total_memory(),
+                swap_used,
+                swap_total,
+                swap_is_commit,
+            },
+            disks,
+            networks,
+            processes,
+            sensors,
+            fans,
+            thermal_pressure: macos::thermal_pressure(),
+            battery: self.battery,
+            cgroup: cgroup::read(),
+            numa_nodes: numa::read(),
+            kernel_memory: meminfo::read(),
+            kernel_counters: procstat::read(),
+            processes_stale: self.processes_stale,
+            disks_stale: self.disks_stale,
+        }
+    }
+}
+
+impl Collector for SysinfoCollector {
+    fn collect(&mut self) -> Result<Snapshot, io::Error> {
+        self.refresh();
+        Ok(self.snapshot())
+    }
+
+    /// Immediately refreshes any source that just became visible so it
+    /// doesn't show background-cadence data.
+    fn set_demand(&mut self, demand: RefreshDemand) {
+        if demand.processes && !self.demand.processes {
+            self.refresh_processes();
+        }
+        if demand.disks && !self.demand.disks {
+            self.disks.refresh(true);
+        }
+        self.demand = demand;
+    }
+
+    fn own_pid(&self) -> Option<u32> {
+        sysinfo::get_current_pid().ok().map(Pid::as_u32)
+    }
+
+    /// Sends SIGTERM where the platform has it, and the platform's default
+    /// kill otherwise.
+    fn kill(&mut self, pid: u32) -> Result<(), io::Error> {
+        let Some(process) = self.system.process(Pid::from_u32(pid)) else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "no such process"));
+        };
+        terminate(process)
+    }
+
+    fn renice(&mut self, pid: u32, nice: i32) -> Result<(), io::Error> {
+        priority::set_nice(pid, nice)
+    }
+
+    /// Read on demand rather than with every refresh, as environments are
+    /// large and rarely looked at.
+    fn environment(&mut self, pid: u32) -> Result<Vec<String>, io::Error> {
+        let pid = Pid::from_u32(pid);
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            false,
+            ProcessRefreshKind::nothing().with_environ(UpdateKind::Always),
+        );
+        let Some(process) = self.system.process(pid) else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "no such process"));
+        };
+        let environment: Vec<_> = process
+            .environ()
+            .iter()
+            .map(|entry| entry.to_string_lossy().into_owned())
+            .collect();
+        match environment_error(pid.as_u32()) {
+            Some(error) if environment.is_empty() => Err(error),
+            _ => Ok(environment),
+        }
+    }
+}
+
+/// Sends `process` SIGTERM. sysinfo only says whether that worked, so the
+/// signal goes out through libc, whose errno tells a process that's gone
+/// from one the user may not signal.
+#[cfg(unix)]
+fn terminate(process: &Process) -> Result<(), io::Error> {
+    let pid = libc::pid_t::try_from(process.pid().as_u32())
+        .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "no such process"))?;
+    // SAFETY: kill takes no pointers.
+    if unsafe { libc::kill(pid, libc::SIGTERM) } == 0 {
+        return Ok(());
+    }
+    Err(kill_error(io::Error::last_os_error()))
+}
+
+#[cfg(unix)]
+fn kill_error(error: io::Error) -> io::Error {
+    match error.raw_os_error() {
+        Some(libc::EPERM | libc::EACCES) => io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "permission denied (another user's process needs root)",
+        ),
+        Some(libc::ESRCH) => io::Error::new(io::ErrorKind::NotFound, "no such process"),
+        _ => error,
+    }
+}
+
+/// Ends `process`, politely where the platform has a way to ask.
+#[cfg(not(unix))]
+fn terminate(process: &Process) -> Result<(), io::Error> {
+    if process
+        .kill_with(sysinfo::Signal::Term)
+        .unwrap_or_else(|| process.kill())
+    {
+        Ok(())
+    } else {
+        Err(io::Error::other("the process could not be ended"))
+    }
+}
+
+/// Why the environment of `pid` can't be read, if it can't. sysinfo leaves
+/// it empty either way, so only the file itself tells.
+#[cfg(target_os = "linux")]
+fn environment_error(pid: u32) -> Option<io::Error> {
+    let error = std::fs::read(format!("/proc/{pid}/environ")).err()?;
+    Some(if error.kind() == io::ErrorKind::PermissionDenied {
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "permission denied (another user's environment needs root)",
+        )
+    } else {
+        error
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn environment_error(_pid: u32) -> Option<io::Error> {
+    None
+}
+
+fn process_state(status: ProcessStatus) -> ProcessState {
+    map_process_state(status, cfg!(windows))
+}
+
+/// sysinfo can't tell Windows process states apart and calls every process
+/// runnable, which would make the whole list look busy.
+fn map_process_state(status: ProcessStatus, windows: bool) -> ProcessState {
+    if windows {
+        return ProcessState::Unknown;
+    }
+    match status {
+        ProcessStatus::Run => ProcessState::Running,
+        ProcessStatus::Waking => ProcessState::Waking,
+        ProcessStatus::Sleep => ProcessState::Sleeping,
+        ProcessStatus::Parked => ProcessState::Parked,
+        ProcessStatus::Idle => ProcessState::Idle,
+        ProcessStatus::Stop => ProcessState::Stopped,
+        ProcessStatus::Tracing => ProcessState::Traced,
+        ProcessStatus::Wakekill => ProcessState::Wakekill,
+        ProcessStatus::UninterruptibleDiskSleep => ProcessState::DiskWait,
+        ProcessStatus::LockBlocked => ProcessState::LockWait,
+        ProcessStatus::Zombie => ProcessState::Zombie,
+        ProcessStatus::Dead => ProcessState::Dead,
+        ProcessStatus::Unknown(_) => ProcessState::Unknown,
+    }
+}
+
+fn disk_device(disk: &sysinfo::Disk) -> String {
+    device_name(disk.name(), disk.mount_point(), cfg!(windows))
+}
+
+/// What a disk is mounted from, so disk totals count it once. Windows
+/// reports the volume label instead, which is often empty or shared ("New
+/// Volume"), so there the drive letter stands in.
+fn device_name(name: &OsStr, mount_point: &Path, windows: bool) -> String {
+    if windows {
+        os_str_display(mount_point.as_os_str())
+            .trim_end_matches('\\')
+            .to_string()
+    } else {
+        os_str_display(name)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn detect_battery() -> Option<bool> {
+    let entries = std::fs::read_dir("/sys/class/power_supply").ok()?;
+    Some(entries.flatten().any(|entry| {
+        std::fs::read_to_string(entry.path().join("type"))
+            .map(|kind| kind.trim() == "Battery")
+            .unwrap_or(false)
+    }))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn detect_battery() -> Option<bool> {
+    None
+}
+
+/// Operational state of an interface. Virtual interfaces like `lo` report
+/// "unknown", in which case the administrative up flag decides.
+#[cfg(target_os = "linux")]
+fn link_up(name: &str) -> Option<bool> {
+    const IFF_UP: u32 = 0x1;
+    let dir = std::path::Path::new("/sys/class/net").join(name);
+    match std::fs::read_to_string(dir.join("operstate")).ok()?.trim() {
+        "up" => Some(true),
+        "unknown" => {
+            let flags = std::fs::read_to_string(dir.join("flags")).ok()?;
+            let flags = u32::from_str_radix(flags.trim().trim_start_matches("0x"), 16).ok()?;
+            Some(flags & IFF_UP != 0)
+        }
+        _ => Some(false),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn link_up(_name: &str) -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_processes_are_in_an_unknown_state() {
+        assert_eq!(
+            map_process_state(ProcessStatus::Run, false),
+            ProcessState::Running
+        );
+        assert_eq!(
+            map_process_state(ProcessStatus::UninterruptibleDiskSleep, false),
+            ProcessState::DiskWait
+        );
+        assert_eq!(
+            map_process_state(ProcessStatus::Run, true),
+            ProcessState::Unknown
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kill_errors_tell_a_gone_process_from_a_forbidden_one() {
+        let kind = |errno| kill_error(io::Error::from_raw_os_error(errno)).kind();
+        assert_eq!(kind(libc::ESRCH), io::ErrorKind::NotFound);
+        assert_eq!(kind(libc::EPERM), io::ErrorKind::PermissionDenied);
+        assert_eq!(kind(libc::EACCES), io::ErrorKind::PermissionDenied);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn device_names_show_control_characters_as_placeholders() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"/dev/sd\x1b[2Ja");
+        assert_eq!(device_name(name, Path::new("/"), false), "/dev/sd?[2Ja");
+    }
+
+    #[test]
+    fn windows_disks_are_named_by_drive_letter() {
+        let name = OsStr::new("New Volume");
+        assert_eq!(device_name(name, Path::new("D:\\"), true), "D:");
+        assert_eq!(
+            device_name(OsStr::new("/dev/sda1"), Path::new("/data"), false),
+            "/dev/sda1"
+        );
+    }
+}
@@ -0,0 +1,299 @@
+//! Sensors sysinfo misses on macOS: CPU and GPU temperatures and fan
+//! speeds read from the System Management Controller, and the thermal
+//! pressure level. sysinfo's components come up empty on most Macs, Apple
+//! Silicon ones especially.
+//!
+//! SMC keys differ between models, so a handful of common ones are tried
+//! and whichever the machine has are reported.
+
+use crate::util::{FanSnapshot, SensorSnapshot, ThermalPressure};
+
+/// Temperature keys and the labels they're reported under. "CPU" in a label
+/// lets `Snapshot::cpu_temperature` find it.
+#[cfg(target_os = "macos")]
+const TEMPERATURE_KEYS: [(&[u8; 4], &str); 6] = [
+    (b"TC0P", "SMC CPU proximity"),
+    (b"TC0D", "SMC CPU die"),
+    (b"Tp09", "SMC CPU efficiency core"),
+    (b"Tp01", "SMC CPU performance core"),
+    (b"TG0P", "SMC GPU proximity"),
+    (b"Tg05", "SMC GPU"),
+];
+
+/// The SMC, opened on the first read and kept open between samples.
+#[derive(Default)]
+pub struct SmcSensors {
+    #[cfg(target_os = "macos")]
+    connection: Option<smc::Smc>,
+}
+
+impl SmcSensors {
+    /// Temperatures and fan speeds; both empty off macOS or when the SMC
+    /// can't be opened. A connection that stops answering is closed and
+    /// opened again on the next read.
+    #[cfg(target_os = "macos")]
+    pub fn read(&mut self) -> (Vec<SensorSnapshot>, Vec<FanSnapshot>) {
+        if self.connection.is_none() {
+            self.connection = smc::Smc::open();
+        }
+        let Some(connection) = &self.connection else {
+            return (Vec::new(), Vec::new());
+        };
+        match read(connection) {
+            Ok(readings) => readings,
+            Err(_) => {
+                self.connection = None;
+                (Vec::new(), Vec::new())
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn read(&mut self) -> (Vec<SensorSnapshot>, Vec<FanSnapshot>) {
+        (Vec::new(), Vec::new())
+    }
+}
+
+/// Whichever of the known temperature keys and fans `smc` has.
+#[cfg(target_os = "macos")]
+fn read(smc: &smc::Smc) -> Result<(Vec<SensorSnapshot>, Vec<FanSnapshot>), smc::KernReturn> {
+    let mut sensors = Vec::new();
+    for &(key, label) in &TEMPERATURE_KEYS {
+        // Missing sensors read as 0 on some models.
+        if let Some(temperature) = smc.read(key)?.filter(|&value| value > 0.0) {
+            sensors.push(SensorSnapshot {
+                label: label.to_string(),
+                temperature: Some(temperature),
+            });
+        }
+    }
+    let count = smc.read(b"FNum")?.map_or(0, |count| count as u8);
+    let mut fans = Vec::new();
+    for fan in 0..count {
+        let key = [b'F', b'0' + fan, b'A', b'c'];
+        if let Some(rpm) = smc.read(&key)? {
+            fans.push(FanSnapshot {
+                label: format!("Fan {fan}"),
+                rpm,
+            });
+        }
+    }
+    Ok((sensors, fans))
+}
+
+/// The level macOS posts to "com.apple.system.thermalpressurelevel".
+#[cfg(target_os = "macos")]
+pub fn thermal_pressure() -> Option<ThermalPressure> {
+    use std::ffi::{c_char, c_int};
+
+    unsafe extern "C" {
+        fn notify_register_check(name: *const c_char, token: *mut c_int) -> u32;
+        fn notify_get_state(token: c_int, state: *mut u64) -> u32;
+        fn notify_cancel(token: c_int) -> u32;
+    }
+
+    let mut token = 0;
+    let mut state = 0;
+    // SAFETY: the name is NUL-terminated and both out-pointers are valid;
+    // the token is cancelled once read.
+    unsafe {
+        if notify_register_check(
+            c"com.apple.system.thermalpressurelevel".as_ptr(),
+            &mut token,
+        ) != 0
+        {
+            return None;
+        }
+        let status = notify_get_state(token, &mut state);
+        notify_cancel(token);
+        if status != 0 {
+            return None;
+        }
+    }
+    Some(match state {
+        0 => ThermalPressure::Nominal,
+        1 => ThermalPressure::Moderate,
+        2 => ThermalPressure::Heavy,
+        3 => ThermalPressure::Trapping,
+        _ => ThermalPressure::Sleeping,
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn thermal_pressure() -> Option<ThermalPressure> {
+    None
+}
+
+/// A connection to the AppleSMC driver, through the struct call the
+/// driver's user client takes.
+#[cfg(target_os = "macos")]
+mod smc {
+    use std::ffi::{c_char, c_void};
+
+    pub type KernReturn = i32;
+    type MachPort = u32;
+
+    /// Selector of the user client's one struct method.
+    const KERNEL_INDEX_SMC: u32 = 2;
+    const CMD_READ_BYTES: u8 = 5;
+    const CMD_READ_KEY_INFO: u8 = 9;
+
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct Version {
+        major: u8,
+        minor: u8,
+        build: u8,
+        reserved: u8,
+        release: u16,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct PowerLimits {
+        version: u16,
+        length: u16,
+        cpu: u32,
+        gpu: u32,
+        memory: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct KeyInfo {
+        data_size: u32,
+        data_type: u32,
+        data_attributes: u8,
+    }
+
+    /// `SMCKeyData_t`, the driver's in and out parameter. Only some fields
+    /// are read, but all of them make up its layout.
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct KeyData {
+        key: u32,
+        version: Version,
+        limits: PowerLimits,
+        key_info: KeyInfo,
+        result: u8,
+        status: u8,
+        command: u8,
+        data32: u32,
+        bytes: [u8; 32],
+    }
+
+    #[link(name = "IOKit", kind = "framework")]
+    unsafe extern "C" {
+        fn IOServiceMatching(name: *const c_char) -> *mut c_void;
+        fn IOServiceGetMatchingService(main_port: MachPort, matching: *mut c_void) -> MachPort;
+        fn IOServiceOpen(
+            service: MachPort,
+            owning_task: MachPort,
+            kind: u32,
+            connect: *mut MachPort,
+        ) -> KernReturn;
+        fn IOServiceClose(connect: MachPort) -> KernReturn;
+        fn IOObjectRelease(object: MachPort) -> KernReturn;
+        fn IOConnectCallStructMethod(
+            connection: MachPort,
+            selector: u32,
+            input: *const c_void,
+            input_size: usize,
+            output: *mut c_void,
+            output_size: *mut usize,
+        ) -> KernReturn;
+        static mach_task_self_: MachPort;
+    }
+
+    pub struct Smc(MachPort);
+
+    impl Smc {
+        pub fn open() -> Option<Self> {
+            let mut connection = 0;
+            // SAFETY: the matching dictionary is consumed by the lookup, and
+            // the service is released once the connection is open.
+            unsafe {
+                let service =
+                    IOServiceGetMatchingService(0, IOServiceMatching(c"AppleSMC".as_ptr()));
+                if service == 0 {
+                    return None;
+                }
+                let result = IOServiceOpen(service, mach_task_self_, 0, &mut connection);
+                IOObjectRelease(service);
+                (result == 0).then_some(Self(connection))
+            }
+        }
+
+        /// The value of `key` as a number, decoded from whichever of the
+        /// SMC's usual types it has. `None` if the key doesn't exist, an
+        /// error if the driver call itself failed.
+        pub fn read(&self, key: &[u8; 4]) -> Result<Option<f32>, KernReturn> {
+            let Some(info) = self.call(KeyData {
+                key: u32::from_be_bytes(*key),
+                command: CMD_READ_KEY_INFO,
+                ..KeyData::default()
+            })?
+            else {
+                return Ok(None);
+            };
+            let size = info.key_info.data_size;
+            let Some(data) = self.call(KeyData {
+                key: u32::from_be_bytes(*key),
+                key_info: KeyInfo {
+                    data_size: size,
+                    ..KeyInfo::default()
+                },
+                command: CMD_READ_BYTES,
+                ..KeyData::default()
+            })?
+            else {
+                return Ok(None);
+            };
+            let bytes = &data.bytes;
+            Ok(match &info.key_info.data_type.to_be_bytes() {
+                b"sp78" => Some(f32::from(i16::from_be_bytes([bytes[0], bytes[1]])) / 256.0),
+                b"fpe2" => Some(f32::from(u16::from_be_bytes([bytes[0], bytes[1]])) / 4.0),
+                b"flt " => Some(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+                b"ui8 " => Some(f32::from(bytes[0])),
+                b"ui16" => Some(f32::from(u16::from_be_bytes([bytes[0], bytes[1]]))),
+                _ => None,
+            })
+        }
+
+        /// The driver's answer to `input`; `None` if it reports an error
+        /// for the key, such as it not existing.
+        fn call(&self, input: KeyData) -> Result<Option<KeyData>, KernReturn> {
+            let mut output = KeyData::default();
+            let mut output_size = size_of::<KeyData>();
+            // SAFETY: both buffers are `KeyData`, the layout the driver
+            // expects, and the sizes passed are theirs.
+            let result = unsafe {
+                IOConnectCallStructMethod(
+                    self.0,
+                    KERNEL_INDEX_SMC,
+                    (&raw const input).cast(),
+                    size_of::<KeyData>(),
+                    (&raw mut output).cast(),
+                    &mut output_size,
+                )
+            };
+            if result != 0 {
+                return Err(result);
+            }
+            Ok((output.result == 0).then_some(output))
+        }
+    }
+
+    impl Drop for Smc {
+        fn drop(&mut self) {
+            // SAFETY: the connection was opened by `open` and is closed once.
+            unsafe {
+                IOServiceClose(self.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! The kernel's own share of memory from `/proc/meminfo`, which sysinfo
+//! doesn't report. Runaway slab or a pile of dirty pages only show up here.
+
+use crate::util::KernelMemorySnapshot;
+
+#[cfg(target_os = "linux")]
+pub fn read() -> Option<KernelMemorySnapshot> {
+    parse(&std::fs::read_to_string("/proc/meminfo").ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read() -> Option<KernelMemorySnapshot> {
+    None
+}
+
+/// Picks the kernel fields out of `/proc/meminfo`, whose lines read like
+/// "Slab:             812344 kB". `None` if any of them is missing.
+#[cfg(any(target_os = "linux", test))]
+fn parse(contents: &str) -> Option<KernelMemorySnapshot> {
+    let field = |name: &str| {
+        contents.lines().find_map(|line| {
+            let mut words = line.split_whitespace();
+            (words.next()?.strip_suffix(':')? == name)
+                .then(|| words.next()?.parse::<u64>().ok())
+                .flatten()
+                .map(|kb| kb * 1024)
+        })
+    };
+    Some(KernelMemorySnapshot {
+        slab_reclaimable: field("SReclaimable")?,
+        slab_unreclaimable: field("SUnreclaim")?,
+        page_tables: field("PageTables")?,
+        kernel_stack: field("KernelStack")?,
+        dirty: field("Dirty")?,
+        writeback: field("Writeback")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_kernel_fields_in_bytes() {
+        let meminfo = "MemTotal:       16314264 kB\n\
+                       Dirty:               412 kB\n\
+                       Writeback:             0 kB\n\
+                       WritebackTmp:          8 kB\n\
+                       Slab:             812344 kB\n\
+                       SReclaimable:     600000 kB\n\
+                       SUnreclaim:       212344 kB\n\
+                       KernelStack:       18800 kB\n\
+                       PageTables:        51200 kB\n";
+        let kernel = parse(meminfo).unwrap();
+        assert_eq!(kernel.slab(), 812_344 * 1024);
+        assert_eq!(kernel.dirty, 412 * 1024);
+        assert_eq!(kernel.writeback, 0, "not confused with WritebackTmp");
+        assert_eq!(kernel.page_tables, 51_200 * 1024);
+        assert!(parse("MemTotal: 10 kB\n").is_none());
+    }
+}
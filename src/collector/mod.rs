@@ -0,0 +1,113 @@
+mod cgroup;
+mod commit;
+mod file;
+mod local;
+mod macos;
+mod meminfo;
+mod numa;
+mod priority;
+mod procstat;
+mod remote;
+mod replay;
+mod smaps;
+
+use std::io;
+
+use crate::util::Snapshot;
+
+pub use self::file::FileCollector;
+pub use self::local::SysinfoCollector;
+pub use self::priority::NICE_RANGE;
+pub use self::remote::SshCollector;
+pub use self::replay::ReplayCollector;
+
+/// The expensive data sources the visible view needs refreshed every tick.
+/// Collectors may refresh anything not demanded on a slower cadence.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct RefreshDemand {
+    pub processes: bool,
+    pub disks: bool,
+    /// Per-process open file descriptor counts, which cost a directory
+    /// listing per process and are only gathered while shown.
+    pub open_files: bool,
+    /// Per-process thread counts, which cost a walk of each process's task
+    /// directory and are likewise only gathered while shown.
+    pub threads: bool,
+    /// Processes to read swap usage for, which costs a walk of each one's
+    /// page tables: those in the rows on screen.
+    pub swap_pids: Vec<u32>,
+    /// Read swap usage for every process, as sorting by it needs.
+    pub swap_all: bool,
+}
+
+/// A source of metrics snapshots. `SystemState` drives one of these from the
+/// collection thread and never looks at where the data came from.
+pub trait Collector: Send {
+    fn collect(&mut self) -> Result<Snapshot, io::Error>;
+
+    /// Tells the collector which sources the open view shows. Collectors
+    /// that always gather everything can ignore it.
+    fn set_demand(&mut self, _demand: RefreshDemand) {}
+
+    /// Whether snapshots arrive with network and disk I/O rates already
+    /// filled in, as recorded ones do. Otherwise `SystemState` derives them.
+    fn provides_rates(&self) -> bool {
+        false
+    }
+
+    /// PID of this dashoxide process among the snapshot's processes, for
+    /// collectors watching the machine it runs on.
+    fn own_pid(&self) -> Option<u32> {
+        None
+    }
+
+    /// Where playback stands, for collectors that play back recorded data.
+    fn replay_status(&self) -> Option<ReplayStatus> {
+        None
+    }
+
+    fn control_replay(&mut self, _command: ReplayCommand) {}
+
+    /// Asks process `pid` to terminate. Sources that only observe, such as
+    /// remote hosts and recordings, refuse.
+    fn kill(&mut self, _pid: u32) -> Result<(), io::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "not supported for this source",
+        ))
+    }
+
+    /// Sets the nice value of process `pid`, refused like `kill`.
+    fn renice(&mut self, _pid: u32, _nice: i32) -> Result<(), io::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "not supported for this source",
+        ))
+    }
+
+    /// Environment of process `pid` as "KEY=value" entries. Snapshots don't
+    /// carry it, so only the local machine can answer.
+    fn environment(&mut self, _pid: u32) -> Result<Vec<String>, io::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "not supported for this source",
+        ))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ReplayStatus {
+    /// Index of the frame currently shown.
+    pub position: usize,
+    pub len: usize,
+    pub paused: bool,
+    /// Frames advanced per collection tick while playing.
+    pub speed: usize,
+}
+
+#[derive(Clone, Copy)]
+pub enum ReplayCommand {
+    TogglePause,
+    /// Moves this many frames forward (or back, if negative).
+    Step(isize),
+}
@@ -0,0 +1,101 @@
+//! Memory and CPUs of each NUMA node. Machine-wide totals hide one node
+//! running out while the others have plenty, which is what makes memory
+//! access slow on NUMA servers.
+
+use crate::util::NumaNodeSnapshot;
+
+/// Reads every node under `/sys/devices/system/node`, ordered by id. A
+/// machine without NUMA support lists nothing; one with a single node
+/// lists just that node.
+#[cfg(target_os = "linux")]
+pub fn read() -> Vec<NumaNodeSnapshot> {
+    use std::fs;
+
+    let Ok(entries) = fs::read_dir("/sys/devices/system/node") else {
+        return Vec::new();
+    };
+    let mut nodes: Vec<_> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("node")?
+                .parse()
+                .ok()?;
+            let meminfo = fs::read_to_string(entry.path().join("meminfo")).ok()?;
+            let (total, free) = parse_meminfo(&meminfo)?;
+            let cpus = fs::read_to_string(entry.path().join("cpulist"))
+                .map(|list| parse_cpu_list(&list))
+                .unwrap_or_default();
+            Some(NumaNodeSnapshot {
+                id,
+                total,
+                free,
+                cpus,
+            })
+        })
+        .collect();
+    nodes.sort_by_key(|node| node.id);
+    nodes
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read() -> Vec<NumaNodeSnapshot> {
+    Vec::new()
+}
+
+/// Total and free bytes from a node's `meminfo`, whose lines read like
+/// "Node 0 MemTotal:       65842608 kB".
+#[cfg(any(target_os = "linux", test))]
+fn parse_meminfo(contents: &str) -> Option<(u64, u64)> {
+    let field = |name: &str| {
+        contents.lines().find_map(|line| {
+            let mut words = line.split_whitespace().skip(2);
+            (words.next()? == name)
+                .then(|| words.next()?.parse::<u64>().ok())
+                .flatten()
+                .map(|kb| kb * 1024)
+        })
+    };
+    Some((field("MemTotal:")?, field("MemFree:")?))
+}
+
+/// Expands a kernel CPU list such as "0-3,8,10-11".
+#[cfg(any(target_os = "linux", test))]
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.trim()
+        .split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((first, last)) => Some(first.parse().ok()?..=last.parse().ok()?),
+            None => {
+                let cpu = range.parse().ok()?;
+                Some(cpu..=cpu)
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_node_memory_in_bytes() {
+        let meminfo = "Node 1 MemTotal:       65842608 kB\n\
+                       Node 1 MemFree:        1024 kB\n\
+                       Node 1 MemUsed:        65841584 kB\n";
+        assert_eq!(
+            parse_meminfo(meminfo),
+            Some((65_842_608 * 1024, 1024 * 1024))
+        );
+        assert_eq!(parse_meminfo("Node 0 MemTotal: 10 kB\n"), None);
+    }
+
+    #[test]
+    fn expands_cpu_lists() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), [0, 1, 2, 3, 8, 10, 11]);
+        assert!(parse_cpu_list("\n").is_empty());
+    }
+}
@@ -0,0 +1,51 @@
+//! Nice values of processes: read for the Nice column and changed by the
+//! renice actions. Only Unix has them.
+
+use std::io;
+
+/// Lowest and highest nice values; lower means a bigger share of the CPU.
+pub const NICE_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
+/// Nice value of `pid`, or `None` if the process is gone.
+#[cfg(target_os = "linux")]
+pub fn nice(pid: u32) -> Option<i32> {
+    // -1 is a valid nice value, so only errno tells a failure apart.
+    // SAFETY: errno is thread-local and getpriority takes no pointers.
+    unsafe {
+        *libc::__errno_location() = 0;
+        let nice = libc::getpriority(libc::PRIO_PROCESS, pid);
+        (*libc::__errno_location() == 0).then_some(nice)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn nice(_pid: u32) -> Option<i32> {
+    None
+}
+
+/// Sets the nice value of `pid`. Anyone may raise their own processes'
+/// values; lowering one, or touching another user's process, needs root.
+#[cfg(unix)]
+pub fn set_nice(pid: u32, nice: i32) -> Result<(), io::Error> {
+    // SAFETY: setpriority takes no pointers.
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, pid, nice) } == 0 {
+        return Ok(());
+    }
+    let error = io::Error::last_os_error();
+    Err(match error.raw_os_error() {
+        Some(libc::EPERM | libc::EACCES) => io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "permission denied (lowering a nice value needs root)",
+        ),
+        Some(libc::ESRCH) => io::Error::new(io::ErrorKind::NotFound, "no such process"),
+        _ => error,
+    })
+}
+
+#[cfg(not(unix))]
+pub fn set_nice(_pid: u32, _nice: i32) -> Result<(), io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "nice values are only supported on Unix",
+    ))
+}
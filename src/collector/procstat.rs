@@ -0,0 +1,51 @@
+//! Context switch and interrupt counters from `/proc/stat`. Thrashing and
+//! lock contention show up here long before they do in CPU usage.
+
+use crate::util::KernelCountersSnapshot;
+
+#[cfg(target_os = "linux")]
+pub fn read() -> Option<KernelCountersSnapshot> {
+    parse(&std::fs::read_to_string("/proc/stat").ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read() -> Option<KernelCountersSnapshot> {
+    None
+}
+
+/// Picks the "ctxt" line and the total at the start of the "intr" line,
+/// which goes on with a count per interrupt. `None` if either is missing.
+#[cfg(any(target_os = "linux", test))]
+fn parse(contents: &str) -> Option<KernelCountersSnapshot> {
+    let field = |name: &str| {
+        contents.lines().find_map(|line| {
+            let mut words = line.split_whitespace();
+            (words.next()? == name)
+                .then(|| words.next()?.parse::<u64>().ok())
+                .flatten()
+        })
+    };
+    Some(KernelCountersSnapshot {
+        context_switches: field("ctxt")?,
+        interrupts: field("intr")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_totals() {
+        let stat = "cpu  4705 356 584 3699 23 23 0 0 0 0\n\
+                    cpu0 1393 280 290 1210 5 13 0 0 0 0\n\
+                    intr 1462898 0 9 0 0 0 0 3 0 1 0\n\
+                    ctxt 2957401\n\
+                    btime 1714000000\n\
+                    processes 20394\n";
+        let counters = parse(stat).unwrap();
+        assert_eq!(counters.context_switches, 2_957_401);
+        assert_eq!(counters.interrupts, 1_462_898);
+        assert!(parse("cpu  1 2 3\nctxt 5\n").is_none());
+    }
+}
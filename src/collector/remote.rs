@@ -0,0 +1,111 @@
+use std::{io, process::Command};
+
+use super::Collector;
+use crate::util::Snapshot;
+
+/// Collects from another machine by running `dashoxide --report` there over
+/// SSH and parsing the JSON it prints.
+///
+/// Each sample is a fresh `ssh` invocation, so key-based authentication (or
+/// a multiplexed control connection) is needed for this to be usable.
+pub struct SshCollector {
+    destination: String,
+    remote_command: String,
+}
+
+impl SshCollector {
+    pub fn new(destination: String, remote_command: String) -> Self {
+        Self {
+            destination,
+            remote_command,
+        }
+    }
+}
+
+impl Collector for SshCollector {
+    fn collect(&mut self) -> Result<Snapshot, io::Error> {
+        let output = Command::new("ssh")
+            .args(["-o", "BatchMode=yes", "--"])
+            .arg(&self.destination)
+            .arg(&self.remote_command)
+            .output()?;
+        parse_report(
+            &self.destination,
+            output.status.success(),
+            &output.stdout,
+            &output.stderr,
+        )
+    }
+}
+
+/// The snapshot in what `ssh` printed, or why there isn't one.
+fn parse_report(
+    destination: &str,
+    succeeded: bool,
+    stdout: &[u8],
+    stderr: &[u8],
+) -> Result<Snapshot, io::Error> {
+    if !succeeded {
+        let stderr = String::from_utf8_lossy(stderr);
+        return Err(io::Error::other(format!(
+            "ssh {destination} failed: {}",
+            stderr.trim()
+        )));
+    }
+
+    serde_json::from_slice(stdout).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid report from {destination}: {e}"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_report() {
+        let report = br#"{
+            "timestamp": 1700000000,
+            "cpu": {"usage": 12.5, "cores": [20.0, 5.0]},
+            "memory": {"used": 1024, "total": 4096, "swap_used": 0, "swap_total": 2048},
+            "disks": [{"mount_point": "/", "file_system": "ext4", "total": 100, "available": 40}],
+            "networks": [{"name": "eth0", "total_received": 10, "total_transmitted": 20}],
+            "processes": [{"pid": 1, "name": "init", "cpu_usage": 0.5, "memory": 512, "status": "sleeping"}]
+        }"#;
+        let snapshot = parse_report("host", true, report, b"").unwrap();
+        assert_eq!(snapshot.timestamp, 1_700_000_000);
+        assert_eq!(snapshot.cpu.cores, [20.0, 5.0]);
+        assert_eq!(snapshot.disks[0].available, 40);
+        assert_eq!(snapshot.networks[0].name, "eth0");
+        assert_eq!(snapshot.processes[0].name, "init");
+        assert!(snapshot.sensors.is_empty());
+
+        let error = parse_report("host", true, b"dashoxide: command not found", b"")
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(
+            error.to_string().starts_with("invalid report from host"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn failed_ssh_reports_its_stderr() {
+        let error = parse_report(
+            "host",
+            false,
+            b"",
+            b"ssh: connect to host host port 22: Connection refused\n",
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "ssh host failed: ssh: connect to host host port 22: Connection refused"
+        );
+    }
+}
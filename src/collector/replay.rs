@@ -0,0 +1,95 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use super::{Collector, ReplayCommand, ReplayStatus};
+use crate::util::Snapshot;
+
+/// Plays back a file written by `--record`, one snapshot per line.
+///
+/// Each `collect` advances `speed` frames while playing and stops on the
+/// last frame instead of looping, so the end of an incident stays on screen.
+pub struct ReplayCollector {
+    frames: Vec<Snapshot>,
+    /// Frame most recently returned by `collect`.
+    position: usize,
+    started: bool,
+    paused: bool,
+    speed: usize,
+}
+
+impl ReplayCollector {
+    pub fn open(path: &Path, speed: usize) -> Result<Self, io::Error> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut frames = Vec::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let frame = serde_json::from_str(&line).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: {e}", path.display(), number + 1),
+                )
+            })?;
+            frames.push(frame);
+        }
+        if frames.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} contains no recorded samples", path.display()),
+            ));
+        }
+
+        Ok(Self {
+            frames,
+            position: 0,
+            started: false,
+            paused: false,
+            speed: speed.max(1),
+        })
+    }
+
+    fn last_index(&self) -> usize {
+        self.frames.len() - 1
+    }
+}
+
+impl Collector for ReplayCollector {
+    fn collect(&mut self) -> Result<Snapshot, io::Error> {
+        if self.started && !self.paused {
+            self.position = (self.position + self.speed).min(self.last_index());
+        }
+        self.started = true;
+        Ok(self.frames[self.position].clone())
+    }
+
+    fn provides_rates(&self) -> bool {
+        true
+    }
+
+    fn replay_status(&self) -> Option<ReplayStatus> {
+        Some(ReplayStatus {
+            position: self.position,
+            len: self.frames.len(),
+            paused: self.paused,
+            speed: self.speed,
+        })
+    }
+
+    fn control_replay(&mut self, command: ReplayCommand) {
+        match command {
+            ReplayCommand::TogglePause => self.paused = !self.paused,
+            ReplayCommand::Step(offset) => {
+                self.paused = true;
+                self.position = self
+                    .position
+                    .saturating_add_signed(offset)
+                    .min(self.last_index());
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Per-process swap usage from `/proc/<pid>/smaps_rollup`. The kernel walks
+//! the process's page tables to produce it, so it's only read for the
+//! processes in the Swap column on screen, and the largest few while sorting
+//! on it.
+
+#[cfg(target_os = "linux")]
+pub fn swap(pid: u32) -> Option<u64> {
+    parse(&std::fs::read_to_string(format!("/proc/{pid}/smaps_rollup")).ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn swap(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Bytes on the "Swap:" line, which the kernel gives in kB.
+#[cfg(any(target_os = "linux", test))]
+fn parse(contents: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let kb = line.strip_prefix("Swap:")?.trim().strip_suffix("kB")?;
+        kb.trim().parse::<u64>().ok().map(|kb| kb * 1024)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_swap_line() {
+        let rollup = "55d0c7a4e000-7ffd2b1f7000 ---p 00000000 00:00 0    [rollup]\n\
+                      Rss:                5124 kB\n\
+                      Pss:                1203 kB\n\
+                      SwapPss:             512 kB\n\
+                      Swap:               2048 kB\n\
+                      Locked:                0 kB\n";
+        assert_eq!(parse(rollup), Some(2048 * 1024));
+        assert_eq!(parse("Rss: 5124 kB\n"), None);
+    }
+}
@@ -0,0 +1,217 @@
+use std::{cmp::Ordering, str::FromStr};
+
+use crate::util::{Precision, ProcessSnapshot, format_age, format_timestamp, format_total_bytes};
+
+/// A column the Processes table can show. `--process-columns` picks which
+/// ones, in order, from this fixed menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessColumn {
+    Pid,
+    Name,
+    Cpu,
+    Memory,
+    Status,
+    Threads,
+    User,
+    Started,
+    /// Time since the process started, relative to the snapshot.
+    Age,
+    DiskRead,
+    DiskWrite,
+    ReadRate,
+    WriteRate,
+    OpenFiles,
+    /// Swapped-out memory, Linux only.
+    Swap,
+    Nice,
+}
+
+impl ProcessColumn {
+    pub const ALL: [ProcessColumn; 16] = [
+        ProcessColumn::Pid,
+        ProcessColumn::Name,
+        ProcessColumn::Cpu,
+        ProcessColumn::Memory,
+        ProcessColumn::Status,
+        ProcessColumn::Threads,
+        ProcessColumn::User,
+        ProcessColumn::Started,
+        ProcessColumn::Age,
+        ProcessColumn::DiskRead,
+        ProcessColumn::DiskWrite,
+        ProcessColumn::ReadRate,
+        ProcessColumn::WriteRate,
+        ProcessColumn::OpenFiles,
+        ProcessColumn::Swap,
+        ProcessColumn::Nice,
+    ];
+
+    pub const DEFAULT: [ProcessColumn; 5] = [
+        ProcessColumn::Pid,
+        ProcessColumn::Name,
+        ProcessColumn::Cpu,
+        ProcessColumn::Memory,
+        ProcessColumn::Status,
+    ];
+
+    /// Name used on the command line.
+    pub fn key(self) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "pid",
+            ProcessColumn::Name => "name",
+            ProcessColumn::Cpu => "cpu",
+            ProcessColumn::Memory => "memory",
+            ProcessColumn::Status => "status",
+            ProcessColumn::Threads => "threads",
+            ProcessColumn::User => "user",
+            ProcessColumn::Started => "started",
+            ProcessColumn::Age => "age",
+            ProcessColumn::DiskRead => "disk-read",
+            ProcessColumn::DiskWrite => "disk-write",
+            ProcessColumn::ReadRate => "read-rate",
+            ProcessColumn::WriteRate => "write-rate",
+            ProcessColumn::OpenFiles => "fds",
+            ProcessColumn::Swap => "swap",
+            ProcessColumn::Nice => "nice",
+        }
+    }
+
+    pub fn header(self) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "PID",
+            ProcessColumn::Name => "Name",
+            ProcessColumn::Cpu => "CPU%",
+            ProcessColumn::Memory => "Memory",
+            ProcessColumn::Status => "Status",
+            ProcessColumn::Threads => "Threads",
+            ProcessColumn::User => "User",
+            ProcessColumn::Started => "Started (UTC)",
+            ProcessColumn::Age => "Age",
+            ProcessColumn::DiskRead => "Read",
+            ProcessColumn::DiskWrite => "Written",
+            ProcessColumn::ReadRate => "Read/s",
+            ProcessColumn::WriteRate => "Write/s",
+            ProcessColumn::OpenFiles => "FDs",
+            ProcessColumn::Swap => "Swap",
+            ProcessColumn::Nice => "Nice",
+        }
+    }
+
+    /// Cell text for `process` in a snapshot taken at `now` (seconds since
+    /// the epoch). Status is styled by the table itself, so this only gives
+    /// its label.
+    pub fn text(
+        self,
+        process: &ProcessSnapshot,
+        status_label: &str,
+        precision: &Precision,
+        now: u64,
+    ) -> String {
+        match self {
+            ProcessColumn::Pid => process.pid.to_string(),
+            ProcessColumn::Name => process.name.clone(),
+            ProcessColumn::Cpu => precision.percent(process.cpu_usage.into()),
+            ProcessColumn::Memory => format!("{} MB", process.memory / 1024 / 1024),
+            ProcessColumn::Status => status_label.to_string(),
+            ProcessColumn::Threads => process
+                .threads
+                .map_or_else(|| "-".to_string(), |threads| threads.to_string()),
+            ProcessColumn::User => process.user.clone().unwrap_or_else(|| "-".to_string()),
+            ProcessColumn::Started => format_timestamp(process.start_time),
+            ProcessColumn::Age => format_age(now.saturating_sub(process.start_time)),
+            ProcessColumn::DiskRead => format!("{} KB", process.disk_read / 1024),
+            ProcessColumn::DiskWrite => format!("{} KB", process.disk_written / 1024),
+            ProcessColumn::ReadRate => format!("{} KB/s", process.disk_read_rate / 1024),
+            ProcessColumn::WriteRate => format!("{} KB/s", process.disk_write_rate / 1024),
+            ProcessColumn::OpenFiles => process
+                .open_files
+                .map_or_else(|| "?".to_string(), |count| count.to_string()),
+            ProcessColumn::Swap => process.swap.map_or_else(
+                || "-".to_string(),
+                |swap| format_total_bytes(swap, precision),
+            ),
+            ProcessColumn::Nice => process
+                .nice
+                .map_or_else(|| "-".to_string(), |nice| nice.to_string()),
+        }
+    }
+
+    /// Orders two processes by this column, ascending. Processes missing a
+    /// value (threads, user, open files, swap, nice) sort first.
+    pub fn compare(self, a: &ProcessSnapshot, b: &ProcessSnapshot) -> Ordering {
+        match self {
+            ProcessColumn::Pid => a.pid.cmp(&b.pid),
+            ProcessColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            ProcessColumn::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+            ProcessColumn::Memory => a.memory.cmp(&b.memory),
+            ProcessColumn::Status => a.status.label().cmp(b.status.label()),
+            ProcessColumn::Threads => a.threads.cmp(&b.threads),
+            ProcessColumn::User => a.user.cmp(&b.user),
+            ProcessColumn::Started => a.start_time.cmp(&b.start_time),
+            // Youngest first, like counting up from now.
+            ProcessColumn::Age => b.start_time.cmp(&a.start_time),
+            ProcessColumn::DiskRead => a.disk_read.cmp(&b.disk_read),
+            ProcessColumn::DiskWrite => a.disk_written.cmp(&b.disk_written),
+            ProcessColumn::ReadRate => a.disk_read_rate.cmp(&b.disk_read_rate),
+            ProcessColumn::WriteRate => a.disk_write_rate.cmp(&b.disk_write_rate),
+            ProcessColumn::OpenFiles => a.open_files.cmp(&b.open_files),
+            ProcessColumn::Swap => a.swap.cmp(&b.swap),
+            ProcessColumn::Nice => a.nice.cmp(&b.nice),
+        }
+    }
+}
+
+impl FromStr for ProcessColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ProcessColumn::ALL
+            .into_iter()
+            .find(|column| column.key() == s.trim())
+            .ok_or_else(|| {
+                let known: Vec<_> = ProcessColumn::ALL.iter().map(|c| c.key()).collect();
+                format!(
+                    "unknown column '{s}', expected one of: {}",
+                    known.join(", ")
+                )
+            })
+    }
+}
+
+/// Parses a comma-separated column list such as `pid,name,cpu`.
+pub fn parse_process_columns(list: &str) -> Result<Vec<ProcessColumn>, String> {
+    let columns = list
+        .split(',')
+        .filter(|item| !item.trim().is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    if columns.is_empty() {
+        return Err("at least one column is required".to_string());
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_column_list_in_order() {
+        assert_eq!(
+            parse_process_columns("pid, user,disk-read"),
+            Ok(vec![
+                ProcessColumn::Pid,
+                ProcessColumn::User,
+                ProcessColumn::DiskRead
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_empty_lists() {
+        let error = parse_process_columns("pid,priority").unwrap_err();
+        assert!(error.contains("unknown column 'priority'"), "{error}");
+        assert!(error.contains("threads"), "{error}");
+        assert!(parse_process_columns(" , ").is_err());
+    }
+}
@@ -0,0 +1,495 @@
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::alert::{AlertKind, Thresholds};
+use crate::ascii::unicode_locale;
+use crate::columns::{ProcessColumn, parse_process_columns};
+use crate::keys::KeyMap;
+use crate::record::Rotation;
+use crate::util::{GaugeLabel, HISTORY_LEN, Precision};
+use crate::view::DashboardView;
+
+/// Address the HTTP API binds to when enabled without an explicit address.
+/// Localhost-only so the snapshot isn't exposed to the network by accident.
+pub const DEFAULT_API_ADDR: SocketAddr =
+    SocketAddr::new(std::net::IpAddr::V4(Ipv4Addr::LOCALHOST), 9898);
+
+/// Shortest sampling interval accepted; sysinfo can't compute CPU usage
+/// reliably from refreshes closer together than this.
+pub const MIN_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Longest interval the dashboard's '<' key will slow sampling down to.
+pub const MAX_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Command run on the remote host by `--remote` unless overridden.
+const DEFAULT_REMOTE_COMMAND: &str = "dashoxide --report";
+
+/// Runtime options from the command line, plus key bindings from the config
+/// file. Options marked with a `DASHOXIDE_*` variable can also come from the
+/// environment, for containers where files are awkward; a command line flag
+/// still wins over the variable.
+pub struct Config {
+    /// Print the detection summary before entering the TUI.
+    pub show_splash: bool,
+    /// Print the full detection summary instead of the one-line version.
+    pub verbose: bool,
+    /// Serve the current snapshot as JSON on this address.
+    pub api_addr: Option<SocketAddr>,
+    /// Time between samples taken by the collection thread.
+    /// `DASHOXIDE_INTERVAL`, in milliseconds.
+    pub interval: Duration,
+    /// Time between process refreshes while the Processes tab is hidden.
+    pub process_interval: Duration,
+    /// Refresh every data source on every tick instead of only what the
+    /// open view shows.
+    pub refresh_all: bool,
+    /// Ring the bell when global CPU usage (percent) stays above this.
+    /// `DASHOXIDE_CPU_THRESHOLD`.
+    pub cpu_alert: Option<f32>,
+    /// Ring the bell when memory usage (percent) stays above this.
+    /// `DASHOXIDE_MEM_THRESHOLD`.
+    pub memory_alert: Option<f32>,
+    /// CPU usage (percent) `--check` reports as a warning.
+    pub cpu_warning: Option<f32>,
+    /// Memory usage (percent) `--check` reports as a warning.
+    pub memory_warning: Option<f32>,
+    /// Ring the bell when context switches per second stay above this.
+    /// Linux only, like the rate itself.
+    pub context_switch_alert: Option<u64>,
+    /// Ring the bell when interrupts per second stay above this.
+    pub interrupt_alert: Option<u64>,
+    /// Pause sampling when an alert fires, keeping that moment on screen.
+    pub freeze_on_alert: bool,
+    /// Minimum time between two bells for the same alert.
+    /// `DASHOXIDE_ALERT_COOLDOWN`, in seconds.
+    pub alert_cooldown: Duration,
+    /// Consecutive samples a threshold must be breached before alerting.
+    /// `DASHOXIDE_ALERT_AFTER`.
+    pub alert_after: u32,
+    /// File the Alerts tab exports the alert log to. `DASHOXIDE_ALERT_LOG`.
+    pub alert_log: PathBuf,
+    /// Print a session summary to stdout after leaving the TUI.
+    pub exit_summary: bool,
+    /// Print one snapshot as JSON and exit instead of starting the TUI.
+    pub report: bool,
+    /// Report a one-line status against the thresholds instead of the JSON
+    /// snapshot, exiting with 0, 1, 2 or 3 for ok, warning, critical or
+    /// unknown.
+    pub check: bool,
+    /// Collect from this SSH destination instead of the local machine.
+    pub remote: Option<String>,
+    /// Command run on the remote host; it must print a `--report` snapshot.
+    pub remote_command: String,
+    /// Read every sample from this `--report`-format JSON file.
+    pub snapshot_file: Option<PathBuf>,
+    /// Append every sample to this file for later `--replay`.
+    /// `DASHOXIDE_RECORD`.
+    pub record: Option<PathBuf>,
+    /// When the `--record` file is rotated and how many old ones are kept.
+    pub record_rotation: Rotation,
+    /// Play back a file written by `--record` instead of collecting.
+    pub replay: Option<PathBuf>,
+    /// Recorded samples advanced per tick during replay.
+    pub replay_speed: usize,
+    /// Abbreviate large counts ("1.2k") in titles and summaries.
+    pub compact_numbers: bool,
+    /// Ease gauges towards new values instead of jumping, redrawing faster
+    /// while they move.
+    pub animate: bool,
+    /// Show network rates in bits per second instead of bytes.
+    pub rates_in_bits: bool,
+    /// Fill the history charts down to the axis instead of drawing lines.
+    pub filled_charts: bool,
+    /// Samples the current network and disk rates are averaged over; the
+    /// charts still show every sample.
+    pub rate_window: usize,
+    /// What the CPU, memory, swap and disk gauges print.
+    pub gauge_label: GaugeLabel,
+    /// Decimals on the numbers in gauge labels. Overrides `precision` for
+    /// the gauges; unset, they follow its percent setting.
+    pub gauge_precision: Option<usize>,
+    /// Decimals on percentages, amounts of memory and rates elsewhere.
+    pub precision: Precision,
+    /// Draw with ASCII only, for terminals that garble box drawing and
+    /// block characters. Also on when the locale isn't UTF-8.
+    pub ascii: bool,
+    /// Columns of the Processes table, in display order.
+    pub process_columns: Vec<ProcessColumn>,
+    /// Open a native window instead of the terminal UI (`gui` feature).
+    pub gui: bool,
+    /// Print plain text blocks instead of the terminal UI; also the fallback
+    /// when the terminal can't run it.
+    pub plain: bool,
+    /// View shown at startup.
+    pub tab: DashboardView,
+    /// Start with that view alone, without the tab bar and status line.
+    pub minimized: bool,
+    /// Start with process names and network addresses hidden.
+    pub privacy: bool,
+    /// Switching past the last tab goes back to the first.
+    pub wrap_tabs: bool,
+    /// Moving past the end of a list (processes, CPU core pages, disk
+    /// devices) goes round to its other end instead of stopping there.
+    pub wrap_lists: bool,
+    /// Column the Processes table is sorted by.
+    pub process_sort: ProcessColumn,
+    pub sort_descending: bool,
+    /// Start with same-named processes grouped into one row.
+    pub group_processes: bool,
+    /// Leave dashoxide's own process out of the process lists and the
+    /// session's top process.
+    pub hide_self: bool,
+    /// Most rows the process table lists, from the top of the sorted list.
+    pub max_processes: Option<usize>,
+    /// Process filter to start with, as if typed after '/'.
+    pub process_filter: String,
+    /// Show a process's memory in red above this many bytes.
+    pub highlight_memory: Option<u64>,
+    /// Show a process's memory in red above this percentage of total RAM.
+    pub highlight_memory_percent: Option<f32>,
+    /// List tmpfs, overlay, squashfs and other pseudo file systems in the
+    /// disk table; they're hidden until toggled otherwise.
+    pub show_pseudo_disks: bool,
+    /// Sum every mount into disk totals, pseudo file systems and repeated
+    /// devices included.
+    pub disk_total_all: bool,
+    /// Comma-separated globs, e.g. "eth*,en*", limiting the interfaces
+    /// listed and summed into network rates; empty lists all of them.
+    pub net_filter: String,
+    /// Sum loopback and container interfaces into network totals too.
+    pub net_total_all: bool,
+    /// Biggest consumers named in each Overview box; 0 leaves them out.
+    pub overview_top: usize,
+    /// Key bindings, the defaults overridden by the config file's `[keys]`.
+    pub keys: KeyMap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            show_splash: true,
+            verbose: false,
+            api_addr: None,
+            interval: Duration::from_secs(1),
+            process_interval: Duration::from_secs(5),
+            refresh_all: false,
+            cpu_alert: None,
+            memory_alert: None,
+            cpu_warning: None,
+            memory_warning: None,
+            context_switch_alert: None,
+            interrupt_alert: None,
+            freeze_on_alert: false,
+            alert_cooldown: Duration::from_secs(30),
+            alert_after: 3,
+            alert_log: PathBuf::from("dashoxide-alerts.csv"),
+            exit_summary: false,
+            report: false,
+            check: false,
+            remote: None,
+            remote_command: DEFAULT_REMOTE_COMMAND.to_string(),
+            snapshot_file: None,
+            record: None,
+            record_rotation: Rotation::default(),
+            replay: None,
+            replay_speed: 1,
+            compact_numbers: false,
+            animate: false,
+            rates_in_bits: false,
+            filled_charts: false,
+            rate_window: 1,
+            gauge_label: GaugeLabel::Both,
+            gauge_precision: None,
+            precision: Precision::default(),
+            ascii: false,
+            process_columns: ProcessColumn::DEFAULT.to_vec(),
+            gui: false,
+            plain: false,
+            tab: DashboardView::Overview,
+            minimized: false,
+            wrap_tabs: true,
+            wrap_lists: false,
+            privacy: false,
+            process_sort: ProcessColumn::Pid,
+            sort_descending: false,
+            group_processes: false,
+            hide_self: false,
+            max_processes: None,
+            process_filter: String::new(),
+            highlight_memory: None,
+            highlight_memory_percent: None,
+            show_pseudo_disks: false,
+            disk_total_all: false,
+            net_filter: String::new(),
+            net_total_all: false,
+            overview_top: 1,
+            keys: KeyMap::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn from_args() -> Result<Self, io::Error> {
+        let mut config = Self::default();
+        config.apply_env(|name| env::var(name).ok())?;
+        let mut config_file = None;
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--no-splash" => config.show_splash = false,
+                "-v" | "--verbose" => config.verbose = true,
+                "--summary" => config.exit_summary = true,
+                "--api" => config.api_addr = Some(DEFAULT_API_ADDR),
+                "--api-bind" => config.api_addr = Some(parse_value(&mut args, &arg)?),
+                "-i" | "--interval" => {
+                    let millis = parse_value(&mut args, &arg)?;
+                    config.interval = Duration::from_millis(millis).max(MIN_INTERVAL);
+                }
+                "--process-interval" => {
+                    let millis = parse_value(&mut args, &arg)?;
+                    config.process_interval = Duration::from_millis(millis).max(MIN_INTERVAL);
+                }
+                "--refresh-all" => config.refresh_all = true,
+                "--cpu-alert" => config.cpu_alert = Some(parse_value(&mut args, &arg)?),
+                "--mem-alert" => config.memory_alert = Some(parse_value(&mut args, &arg)?),
+                "--cpu-warn" => config.cpu_warning = Some(parse_value(&mut args, &arg)?),
+                "--mem-warn" => config.memory_warning = Some(parse_value(&mut args, &arg)?),
+                "--ctxt-alert" => {
+                    config.context_switch_alert = Some(parse_value(&mut args, &arg)?);
+                }
+                "--intr-alert" => config.interrupt_alert = Some(parse_value(&mut args, &arg)?),
+                "--freeze-on-alert" => config.freeze_on_alert = true,
+                "--alert-cooldown" => {
+                    config.alert_cooldown = Duration::from_secs(parse_value(&mut args, &arg)?);
+                }
+                "--alert-after" => config.alert_after = parse_value::<u32>(&mut args, &arg)?.max(1),
+                "--alert-log" => config.alert_log = next_value(&mut args, &arg)?.into(),
+                "--report" => config.report = true,
+                "--check" => {
+                    config.report = true;
+                    config.check = true;
+                }
+                "--remote" => config.remote = Some(next_value(&mut args, &arg)?),
+                "--remote-command" => config.remote_command = next_value(&mut args, &arg)?,
+                "--snapshot-file" => {
+                    config.snapshot_file = Some(next_value(&mut args, &arg)?.into());
+                }
+                "--record" => config.record = Some(next_value(&mut args, &arg)?.into()),
+                "--record-max-mb" => {
+                    let megabytes: u64 = parse_value(&mut args, &arg)?;
+                    config.record_rotation.max_size = Some(megabytes.max(1) * 1024 * 1024);
+                }
+                "--record-daily" => config.record_rotation.daily = true,
+                "--record-keep" => config.record_rotation.keep = parse_value(&mut args, &arg)?,
+                "--replay" => config.replay = Some(next_value(&mut args, &arg)?.into()),
+                "--replay-speed" => {
+                    config.replay_speed = parse_value::<usize>(&mut args, &arg)?.max(1);
+                }
+                "--compact-numbers" => config.compact_numbers = true,
+                "--animate" => config.animate = true,
+                "--bits" => config.rates_in_bits = true,
+                "--filled-charts" => config.filled_charts = true,
+                "--rate-window" => {
+                    config.rate_window =
+                        parse_value::<usize>(&mut args, &arg)?.clamp(1, HISTORY_LEN);
+                }
+                "--gauge-label" => config.gauge_label = parse_value(&mut args, &arg)?,
+                "--gauge-precision" => {
+                    config.gauge_precision = Some(parse_value::<usize>(&mut args, &arg)?.min(3));
+                }
+                "--precision" => config.precision = parse_value(&mut args, &arg)?,
+                "--ascii" => config.ascii = true,
+                "--gui" => config.gui = true,
+                "--plain" => config.plain = true,
+                "--config" => config_file = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+                "--tab" => config.tab = parse_value(&mut args, &arg)?,
+                "--only" => {
+                    config.tab = parse_value(&mut args, &arg)?;
+                    config.minimized = true;
+                }
+                "--no-wrap-tabs" => config.wrap_tabs = false,
+                "--wrap-lists" => config.wrap_lists = true,
+                "--privacy" => config.privacy = true,
+                "--sort" => config.process_sort = parse_value(&mut args, &arg)?,
+                "--desc" => config.sort_descending = true,
+                "--group" => config.group_processes = true,
+                "--hide-self" => config.hide_self = true,
+                "--max-processes" => {
+                    config.max_processes = Some(parse_value::<usize>(&mut args, &arg)?.max(1));
+                }
+                "--filter" => config.process_filter = next_value(&mut args, &arg)?,
+                "--show-pseudo-disks" => config.show_pseudo_disks = true,
+                "--disk-total-all" => config.disk_total_all = true,
+                "--net-filter" => config.net_filter = next_value(&mut args, &arg)?,
+                "--net-total-all" => config.net_total_all = true,
+                "--overview-top" => config.overview_top = parse_value(&mut args, &arg)?,
+                "--highlight-mem-mb" => {
+                    let megabytes: u64 = parse_value(&mut args, &arg)?;
+                    config.highlight_memory = Some(megabytes * 1024 * 1024);
+                }
+                "--highlight-mem-percent" => {
+                    config.highlight_memory_percent = Some(parse_value(&mut args, &arg)?);
+                }
+                "--process-columns" => {
+                    let value = next_value(&mut args, &arg)?;
+                    config.process_columns = parse_process_columns(&value)
+                        .map_err(|e| invalid_input(format!("invalid value for {arg}: {e}")))?;
+                }
+                other => return Err(invalid_input(format!("unknown argument: {other}"))),
+            }
+        }
+
+        // An explicit --config must exist; the default location is optional.
+        let contents = match config_file {
+            Some(path) => {
+                let contents = fs::read_to_string(&path)
+                    .map_err(|e| invalid_input(format!("{}: {e}", path.display())))?;
+                Some((contents, path))
+            }
+            None => {
+                default_config_file().and_then(|path| Some((fs::read_to_string(&path).ok()?, path)))
+            }
+        };
+        if let Some((contents, path)) = contents {
+            config
+                .keys
+                .apply_config(&contents)
+                .map_err(|e| invalid_input(format!("{}: {e}", path.display())))?;
+        }
+        config.ascii |= !unicode_locale();
+        Ok(config)
+    }
+
+    /// Warning and alert levels of the metric `kind` watches.
+    pub fn thresholds(&self, kind: AlertKind) -> Thresholds {
+        match kind {
+            AlertKind::Cpu => Thresholds {
+                warning: self.cpu_warning,
+                alert: self.cpu_alert,
+            },
+            AlertKind::Memory => Thresholds {
+                warning: self.memory_warning,
+                alert: self.memory_alert,
+            },
+        }
+    }
+
+    /// Takes the options documented with a `DASHOXIDE_*` variable from
+    /// `var`. Empty variables count as unset.
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), io::Error> {
+        let value = |name: &str| var(name).filter(|value| !value.trim().is_empty());
+        if let Some(millis) = parse_env(value("DASHOXIDE_INTERVAL"), "DASHOXIDE_INTERVAL")? {
+            self.interval = Duration::from_millis(millis).max(MIN_INTERVAL);
+        }
+        if let Some(threshold) =
+            parse_env(value("DASHOXIDE_CPU_THRESHOLD"), "DASHOXIDE_CPU_THRESHOLD")?
+        {
+            self.cpu_alert = Some(threshold);
+        }
+        if let Some(threshold) =
+            parse_env(value("DASHOXIDE_MEM_THRESHOLD"), "DASHOXIDE_MEM_THRESHOLD")?
+        {
+            self.memory_alert = Some(threshold);
+        }
+        if let Some(seconds) = parse_env(
+            value("DASHOXIDE_ALERT_COOLDOWN"),
+            "DASHOXIDE_ALERT_COOLDOWN",
+        )? {
+            self.alert_cooldown = Duration::from_secs(seconds);
+        }
+        if let Some(after) =
+            parse_env::<u32>(value("DASHOXIDE_ALERT_AFTER"), "DASHOXIDE_ALERT_AFTER")?
+        {
+            self.alert_after = after.max(1);
+        }
+        if let Some(path) = value("DASHOXIDE_ALERT_LOG") {
+            self.alert_log = path.into();
+        }
+        if let Some(path) = value("DASHOXIDE_RECORD") {
+            self.record = Some(path.into());
+        }
+        Ok(())
+    }
+}
+
+fn parse_env<T>(value: Option<String>, name: &str) -> Result<Option<T>, io::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|e| invalid_input(format!("invalid value for {name}: {value} ({e})")))
+        })
+        .transpose()
+}
+
+/// `$XDG_CONFIG_HOME/dashoxide/config.toml`, falling back to `~/.config`.
+fn default_config_file() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("dashoxide").join("config.toml"))
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, io::Error> {
+    args.next()
+        .ok_or_else(|| invalid_input(format!("missing value for {flag}")))
+}
+
+fn parse_value<T>(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<T, io::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = next_value(args, flag)?;
+    value
+        .parse()
+        .map_err(|e| invalid_input(format!("invalid value for {flag}: {value} ({e})")))
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn environment_variables_set_options() {
+        let vars = [
+            ("DASHOXIDE_INTERVAL", "2500"),
+            ("DASHOXIDE_CPU_THRESHOLD", "90"),
+            ("DASHOXIDE_ALERT_LOG", "/var/log/alerts.csv"),
+            ("DASHOXIDE_MEM_THRESHOLD", " "),
+        ];
+        let lookup = |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        };
+        let mut config = Config::default();
+        config.apply_env(lookup).unwrap();
+        assert_eq!(config.interval, Duration::from_millis(2500));
+        assert_eq!(config.cpu_alert, Some(90.0));
+        assert_eq!(config.memory_alert, None, "empty counts as unset");
+        assert_eq!(config.alert_log, PathBuf::from("/var/log/alerts.csv"));
+
+        let error = Config::default()
+            .apply_env(|name| (name == "DASHOXIDE_INTERVAL").then(|| "soon".to_string()))
+            .unwrap_err();
+        assert!(error.to_string().contains("DASHOXIDE_INTERVAL"), "{error}");
+    }
+}
@@ -1,112 +1,493 @@
 use crossterm::{
-    event::{self, Event, KeyCode},
+    event::{self, Event, KeyCode, KeyEventKind},
     terminal::{disable_raw_mode, enable_raw_mode},
 };
 use std::{
-    io,
-    os::linux::raw::stat,
-    sync::{Arc, Mutex},
-    time::Duration,
+    borrow::Cow,
+    cell::{self, RefCell},
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tui::{
-    Terminal,
-    backend::CrosstermBackend,
+    Frame, Terminal,
+    backend::{Backend, CrosstermBackend},
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
-    symbols,
     text::{Span, Spans},
     widgets::{
-        Axis, Block, Borders, Cell, Chart, Dataset, Gauge, GraphType, Paragraph, Row, Table, Tabs,
+        Axis, Block, Borders, Cell, Chart, Clear, Dataset, Gauge, GraphType, List, ListItem,
+        ListState, Paragraph, Row, Sparkline, Table, TableState, Tabs,
     },
 };
 
-use crate::util::SystemState;
+use crate::alert::{AlertEvent, AlertKind, AlertLog, AlertTracker, Status, Thresholds};
+use crate::animation::{ANIMATION_FRAME, GaugeAnimations};
+use crate::ascii::{AsciiFallback, Glyphs};
+use crate::clipboard;
+use crate::collector::{NICE_RANGE, RefreshDemand, ReplayCommand};
+use crate::columns::ProcessColumn;
+use crate::config::{Config, MAX_INTERVAL, MIN_INTERVAL};
+use crate::environment::{EnvironmentView, HIDDEN_VALUE};
+use crate::fade::{StaleFade, Staleness};
+use crate::keys::{Action, Key, KeyMap};
+use crate::switcher::{Switcher, Target};
+use crate::util::{
+    DataAvailability, DiskSnapshot, GaugeLabel, Ghost, HISTORY_LEN, InterfaceSnapshot,
+    KernelMemorySnapshot, OomRisk, Precision, ProcessHistory, ProcessSample, ProcessSnapshot,
+    ProcessState, Snapshot, SystemState, abbreviate_count, downsample, format_ago,
+    format_local_time, format_timestamp, format_total_bytes, gauge_label, lock_state, oom_risk,
+    time_axis_labels,
+};
+use crate::view::DashboardView;
+
+#[derive(Clone, Copy)]
+enum NetworkSort {
+    Name,
+    RxRate,
+    TxRate,
+    Total,
+}
+
+impl NetworkSort {
+    fn next(self) -> Self {
+        match self {
+            NetworkSort::Name => NetworkSort::RxRate,
+            NetworkSort::RxRate => NetworkSort::TxRate,
+            NetworkSort::TxRate => NetworkSort::Total,
+            NetworkSort::Total => NetworkSort::Name,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            NetworkSort::Name => "name",
+            NetworkSort::RxRate => "rx rate",
+            NetworkSort::TxRate => "tx rate",
+            NetworkSort::Total => "total",
+        }
+    }
+}
 
+/// Order of the disk table; the fullest mounts come first by default.
 #[derive(Clone, Copy)]
-enum DashboardView {
-    Overview,
-    Cpu,
-    Memory,
-    Disk,
-    Network,
-    Processes,
+enum DiskSort {
+    Usage,
+    Available,
+    Mount,
+}
+
+impl DiskSort {
+    fn next(self) -> Self {
+        match self {
+            DiskSort::Usage => DiskSort::Available,
+            DiskSort::Available => DiskSort::Mount,
+            DiskSort::Mount => DiskSort::Usage,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            DiskSort::Usage => "usage",
+            DiskSort::Available => "available space",
+            DiskSort::Mount => "mount",
+        }
+    }
+}
+
+/// A command that found the collector busy taking a sample, tried again on
+/// each pass of the main loop until it gets through.
+enum CollectorTask {
+    Replay(ReplayCommand),
+    Kill(Vec<u32>),
+    Renice(Vec<u32>, i32),
+    Environment(u32),
+}
+
+/// Which part of the dashboard gets the keyboard. Every mode but `Normal`
+/// returns to `Normal` on Esc.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum InputMode {
+    /// Keys go through the key map; the chart cursor, when shown, takes
+    /// Left/Right.
+    Normal,
+    /// Typing the process filter; processes that don't match are hidden.
+    Filter,
+    /// Typing the process search; matches are highlighted.
+    Search,
+    /// Typing a command after ':'.
+    Command,
+    /// Waiting for 'y' to kill these PIDs; any other key cancels.
+    ConfirmKill(Vec<u32>),
+    /// Waiting for 'y' to change the nice value of these PIDs by the step.
+    ConfirmRenice(Vec<u32>, i32),
+    /// The key binding overlay is shown until any key is pressed.
+    Help,
+    /// The quick switcher is open.
+    Switcher(Switcher),
+    /// The environment viewer is open over the process table.
+    Environment(EnvironmentView),
+}
+
+/// Where the time goes, for the debug overlay. Lock times add up over a
+/// pass of the main loop and are moved to the `last_` fields after each
+/// frame, since the overlay is drawn before the pass is over.
+#[derive(Default)]
+struct FrameTimings {
+    /// Drawing the last frame, flushing to the terminal included.
+    frame: cell::Cell<Duration>,
+    /// From the start of one frame to the start of the next.
+    frame_interval: cell::Cell<Duration>,
+    frame_started: cell::Cell<Option<Instant>>,
+    lock_wait: cell::Cell<Duration>,
+    lock_held: cell::Cell<Duration>,
+    last_lock_wait: cell::Cell<Duration>,
+    last_lock_held: cell::Cell<Duration>,
+}
+
+impl FrameTimings {
+    fn frame_drawn(&self, started: Instant) {
+        self.frame.set(started.elapsed());
+        if let Some(previous) = self.frame_started.replace(Some(started)) {
+            self.frame_interval.set(started - previous);
+        }
+        self.last_lock_wait.set(self.lock_wait.take());
+        self.last_lock_held.set(self.lock_held.take());
+    }
+}
+
+/// The state lock, adding how long it was held to the frame timings when
+/// released.
+struct StateGuard<'a> {
+    guard: MutexGuard<'a, SystemState>,
+    locked_at: Instant,
+    timings: &'a FrameTimings,
+}
+
+impl std::ops::Deref for StateGuard<'_> {
+    type Target = SystemState;
+
+    fn deref(&self) -> &SystemState {
+        &self.guard
+    }
+}
+
+impl std::ops::DerefMut for StateGuard<'_> {
+    fn deref_mut(&mut self) -> &mut SystemState {
+        &mut self.guard
+    }
+}
+
+impl Drop for StateGuard<'_> {
+    fn drop(&mut self) {
+        let held = &self.timings.lock_held;
+        held.set(held.get() + self.locked_at.elapsed());
+    }
 }
 
 pub struct Dashboard {
     current_view: DashboardView,
+    mode: InputMode,
     should_quit: bool,
     system_state: Arc<Mutex<SystemState>>,
+    /// Sampling interval in milliseconds, shared with the collection thread
+    /// so '<' and '>' take effect without a restart.
+    interval: Arc<AtomicU64>,
+    search_query: String,
+    search_match: usize,
+    /// Only processes whose name contains this are listed.
+    process_filter: String,
+    /// Only processes in uninterruptible sleep are listed.
+    stuck_only: bool,
+    /// dashoxide's own process is left out of the table, the top process
+    /// and the session stats, so a fast refresh doesn't put it among the
+    /// offenders. Mirrored in `SystemState::hide_self`.
+    hide_self: bool,
+    own_pid: Option<u32>,
+    /// Rows past this many are never built, keeping frames cheap on boxes
+    /// with thousands of processes.
+    max_processes: Option<usize>,
+    /// The command line being typed in `InputMode::Command`.
+    command: String,
+    network_sort: NetworkSort,
+    disk_sort: DiskSort,
+    /// Device the disk I/O chart shows on its own; `None` for the sum over
+    /// every device.
+    selected_device: Option<String>,
+    gauge_label: GaugeLabel,
+    gauge_precision: usize,
+    precision: Precision,
+    /// Network rates are shown in bits per second, as link speeds are.
+    rates_in_bits: bool,
+    filled_charts: bool,
+    /// Samples the shown current network and disk rates are averaged over.
+    rate_window: usize,
+    hide_idle_cores: bool,
+    /// Page of the per-core gauges shown when there are more cores than
+    /// rows, e.g. on big servers.
+    core_page: usize,
+    /// Number of per-core pages at the last draw, to stop paging past the
+    /// end.
+    core_pages: cell::Cell<usize>,
+    /// First process row shown at the last draw. The table is handed only
+    /// the rows that fit, so which processes are on screen is known here.
+    process_offset: cell::Cell<usize>,
+    /// Processes behind the Swap cells at the last draw, the only ones the
+    /// collector reads swap usage for.
+    swap_rows: RefCell<Vec<u32>>,
+    /// The `swap_rows` last sent to the collector.
+    demanded_swap_rows: Vec<u32>,
+    /// Draw each core's history faintly behind the CPU average.
+    show_core_overlay: bool,
+    /// The terminal advertises 256 or more colors, enough to keep the core
+    /// overlay from drowning out the average.
+    rich_colors: bool,
+    /// Replace the borders and chart dots tui draws with ASCII before each
+    /// frame is shown.
+    ascii: bool,
+    /// Symbols for sort arrows, marks, legends and sparklines.
+    glyphs: &'static Glyphs,
+    /// Show the file system and flag columns in the disk table.
+    show_disk_details: bool,
+    /// List pseudo file systems in the disk table.
+    show_pseudo_disks: bool,
+    /// Show link state, MTU, MAC and addresses in the interface table.
+    show_link_details: bool,
+    /// Show the kernel memory panel on the Memory tab.
+    show_kernel_memory: bool,
+    /// The current view fills the screen, e.g. as a single-metric widget
+    /// in a small pane.
+    minimized: bool,
+    /// Show the debug overlay with frame, lock and update timings.
+    show_debug: bool,
+    timings: FrameTimings,
+    /// Histories frozen by `Action::Ghost`, drawn dimmed behind the live
+    /// charts until cleared.
+    ghost: Option<Ghost>,
+    /// Show process names and network addresses as stand-ins, for
+    /// screenshots. Only the drawing changes, never the data.
+    privacy: bool,
+    /// Tab switching goes round from one end to the other.
+    wrap_tabs: bool,
+    /// So do list cursors; otherwise they stop at the ends.
+    wrap_lists: bool,
+    /// Cores below this usage (percent) count as idle when collapsing.
+    idle_core_threshold: f32,
+    /// Chart cursor position in samples back from the newest one; `Some`
+    /// while the charts have focus and Left/Right move the cursor.
+    chart_cursor: Option<usize>,
+    /// Warning and alert levels of each metric, in `AlertKind::ALL` order.
+    thresholds: [Thresholds; 2],
+    alert_cooldown: Duration,
+    alert_after: u32,
+    alerts: HashMap<AlertKind, AlertTracker>,
+    /// Context switches and interrupts per second that ring the bell. They
+    /// aren't percentages, so they stay out of the alert log and `--check`.
+    kernel_rate_alerts: [Option<u64>; 2],
+    kernel_rate_trackers: [AlertTracker; 2],
+    /// `SystemState::sample_count` the alerts were last evaluated against.
+    last_alert_sample: u64,
+    freeze_on_alert: bool,
+    /// Snapshot time of the alert that paused sampling, until resumed.
+    frozen_on_alert: Option<u64>,
+    /// `SystemState::sample_count` stale marks were last dropped at.
+    last_pruned_sample: u64,
+    /// Alerts fired so far, listed on the Alerts tab.
+    alert_log: AlertLog,
+    /// Where the alert log is exported to.
+    alert_log_path: PathBuf,
+    /// Playing back a recording, so the replay keys are live.
+    replaying: bool,
+    /// Show large counts as "1.2k" instead of in full.
+    compact_numbers: bool,
+    /// Where each gauge is drawn while easing to a new value; `None` when
+    /// gauges jump straight to it. Drawing only borrows the dashboard, so
+    /// this is updated through the cell.
+    gauge_animations: Option<RefCell<GaugeAnimations>>,
+    process_columns: Vec<ProcessColumn>,
+    process_sort: ProcessColumn,
+    sort_descending: bool,
+    /// Memory cells turn red above this many bytes or this percentage of
+    /// total RAM, whichever is lower.
+    highlight_memory: Option<u64>,
+    highlight_memory_percent: Option<f32>,
+    /// Disk totals include pseudo file systems and repeated devices.
+    disk_total_all: bool,
+    /// Biggest consumers named on the last line of each Overview box.
+    overview_top: usize,
+    /// Process table cursor, by PID so it stays on the same process as rows
+    /// come and go.
+    selected_pid: Option<u32>,
+    /// Same-named processes are listed as one summed row.
+    group_processes: bool,
+    /// Name of the group whose summary row has the cursor; takes precedence
+    /// over `selected_pid` while grouping.
+    selected_group: Option<String>,
+    /// Groups listing their members under the summary row.
+    expanded_groups: HashSet<String>,
+    /// Disk mount point or interface name the switcher jumped to, shown
+    /// highlighted until the tab changes.
+    focus: Option<String>,
+    /// Processes marked with space for a batch kill.
+    marked: BTreeSet<u32>,
+    /// Up to two PIDs shown on the Compare tab, in the order picked.
+    compared: Vec<u32>,
+    /// PID followed in the panel under the process table.
+    watched: Option<u32>,
+    keys: KeyMap,
+    /// Feedback from the last action (a kill, an interval change, ...) and
+    /// when it was given; shown in place of the key hints until it expires.
+    status_message: Option<(String, Instant)>,
+    retry: Option<CollectorTask>,
 }
 
 impl Dashboard {
-    pub fn new(system_state: Arc<Mutex<SystemState>>) -> Self {
+    pub fn new(
+        system_state: Arc<Mutex<SystemState>>,
+        interval: Arc<AtomicU64>,
+        config: &Config,
+    ) -> Self {
+        let own_pid = {
+            let mut state = lock_state(&system_state);
+            state.hide_self = config.hide_self;
+            state.own_pid()
+        };
         Self {
-            current_view: DashboardView::Overview,
+            current_view: config.tab,
+            mode: InputMode::Normal,
             should_quit: false,
             system_state,
+            interval,
+            search_query: String::new(),
+            search_match: 0,
+            process_filter: config.process_filter.clone(),
+            stuck_only: false,
+            hide_self: config.hide_self,
+            own_pid,
+            max_processes: config.max_processes,
+            command: String::new(),
+            network_sort: NetworkSort::Name,
+            disk_sort: DiskSort::Usage,
+            selected_device: None,
+            rates_in_bits: config.rates_in_bits,
+            filled_charts: config.filled_charts,
+            rate_window: config.rate_window,
+            gauge_label: config.gauge_label,
+            gauge_precision: config.gauge_precision.unwrap_or(config.precision.percent),
+            precision: config.precision,
+            hide_idle_cores: false,
+            core_page: 0,
+            core_pages: cell::Cell::new(1),
+            process_offset: cell::Cell::new(0),
+            swap_rows: RefCell::new(Vec::new()),
+            demanded_swap_rows: Vec::new(),
+            show_disk_details: true,
+            show_pseudo_disks: config.show_pseudo_disks,
+            show_link_details: false,
+            show_kernel_memory: false,
+            minimized: config.minimized,
+            show_debug: false,
+            wrap_tabs: config.wrap_tabs,
+            wrap_lists: config.wrap_lists,
+            privacy: config.privacy,
+            ghost: None,
+            timings: FrameTimings::default(),
+            show_core_overlay: false,
+            rich_colors: rich_colors(),
+            ascii: config.ascii,
+            glyphs: Glyphs::new(config.ascii),
+            idle_core_threshold: 5.0,
+            chart_cursor: None,
+            thresholds: AlertKind::ALL.map(|kind| config.thresholds(kind)),
+            alert_cooldown: config.alert_cooldown,
+            alert_after: config.alert_after,
+            alerts: HashMap::new(),
+            kernel_rate_alerts: [config.context_switch_alert, config.interrupt_alert],
+            kernel_rate_trackers: Default::default(),
+            last_alert_sample: 0,
+            freeze_on_alert: config.freeze_on_alert,
+            frozen_on_alert: None,
+            last_pruned_sample: 0,
+            alert_log: AlertLog::default(),
+            alert_log_path: config.alert_log.clone(),
+            replaying: config.replay.is_some(),
+            compact_numbers: config.compact_numbers,
+            gauge_animations: config
+                .animate
+                .then(|| RefCell::new(GaugeAnimations::default())),
+            process_columns: config.process_columns.clone(),
+            process_sort: config.process_sort,
+            sort_descending: config.sort_descending,
+            highlight_memory: config.highlight_memory,
+            highlight_memory_percent: config.highlight_memory_percent,
+            disk_total_all: config.disk_total_all,
+            overview_top: config.overview_top,
+            selected_pid: None,
+            group_processes: config.group_processes,
+            selected_group: None,
+            expanded_groups: HashSet::new(),
+            focus: None,
+            marked: BTreeSet::new(),
+            compared: Vec::new(),
+            watched: None,
+            status_message: None,
+            retry: None,
+            keys: config.keys.clone(),
         }
     }
 
+    /// Runs the TUI until the user quits. Fails with `Unsupported` before
+    /// touching the screen if the terminal can't be put in raw mode.
     pub fn run(&mut self) -> Result<(), io::Error> {
-        enable_raw_mode()?;
+        enable_raw_mode().map_err(|e| {
+            let term = std::env::var("TERM").unwrap_or_default();
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "the terminal UI needs an interactive terminal with raw mode support \
+                     (TERM={term:?}): {e}"
+                ),
+            )
+        })?;
         let stdout = io::stdout();
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
         terminal.clear()?;
+        self.set_view(self.current_view);
 
         while !self.should_quit {
-            terminal.draw(|f| {
-                let chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(1)
-                    .constraints(
-                        [
-                            Constraint::Length(3),
-                            Constraint::Min(0),
-                            Constraint::Length(1),
-                        ]
-                        .as_ref(),
-                    )
-                    .split(f.size());
-
-                let tab_titles = vec!["Overview", "CPU", "Memory", "Disk", "Network", "Processes"];
-                let tabs = Tabs::new(
-                    tab_titles
-                        .iter()
-                        .map(|t| Spans::from(vec![Span::styled(*t, Style::default())]))
-                        .collect(),
-                )
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title("System Monitor"),
-                )
-                .highlight_style(
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                )
-                .select(self.current_view as usize);
-                f.render_widget(tabs, chunks[0]);
-
-                match self.current_view {
-                    DashboardView::Overview => self.render_overview(f, chunks[1]),
-                    DashboardView::Cpu => self.render_cpu(f, chunks[1]),
-                    DashboardView::Memory => self.render_memory(f, chunks[1]),
-                    DashboardView::Disk => self.render_disk(f, chunks[1]),
-                    DashboardView::Network => self.render_network(f, chunks[1]),
-                    DashboardView::Processes => self.render_processes(f, chunks[1]),
-                };
+            let started = Instant::now();
+            terminal.draw(|f| self.draw(f))?;
+            self.timings.frame_drawn(started);
 
-                let status = Paragraph::new("Press 'q' to quit, arrow keys to navigate")
-                    .style(Style::default().fg(Color::White));
-                f.render_widget(status, chunks[2]);
-            })?;
+            self.check_alerts(terminal.backend_mut())?;
+            self.prune_stale();
+            self.update_swap_demand();
+            self.retry_collector_task();
 
-            if event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    self.handle_input(key.code);
-                }
+            let animating = self
+                .gauge_animations
+                .as_ref()
+                .is_some_and(|animations| animations.borrow().is_animating(Instant::now()));
+            let timeout = if animating {
+                ANIMATION_FRAME
+            } else {
+                Duration::from_millis(100)
+            };
+            // Windows reports releases too, which would act on every key
+            // twice.
+            if event::poll(timeout)?
+                && let Event::Key(key) = event::read()?
+                && key.kind != KeyEventKind::Release
+            {
+                self.handle_input(key);
             }
         }
 
@@ -116,15 +497,326 @@ impl Dashboard {
         Ok(())
     }
 
-    fn render_overview(
-        &self,
-        f: &mut tui::Frame<'_, CrosstermBackend<io::Stdout>>,
-        area: tui::layout::Rect,
-    ) {
-        let state = match self.system_state.lock() {
-            Ok(guard) => guard,
-            Err(_) => return,
+    fn draw<B: Backend>(&self, f: &mut Frame<'_, B>) {
+        // Minimized, the view fills the screen; the status line only comes
+        // back for prompts and messages. Too narrow for every tab, the bar
+        // shrinks to one line naming the current one.
+        let compact_tabs = f.size().width.saturating_sub(2) < full_tab_bar_width();
+        let (tabs_height, footer_height, margin) = if self.minimized {
+            let quiet = self.mode == InputMode::Normal
+                && self.status_message().is_none()
+                && !self.state().paused;
+            (0, u16::from(!quiet), 0)
+        } else if compact_tabs {
+            (1, 1, 1)
+        } else {
+            (3, 1, 1)
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(margin)
+            .constraints(
+                [
+                    Constraint::Length(tabs_height),
+                    Constraint::Min(0),
+                    Constraint::Length(footer_height),
+                ]
+                .as_ref(),
+            )
+            .split(f.size());
+
+        let tabs = Tabs::new(
+            DashboardView::ALL
+                .iter()
+                .map(|view| Spans::from(vec![Span::styled(view.title(), Style::default())]))
+                .collect(),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(self.header_title()),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .divider(self.glyphs.divider)
+        .select(self.current_view.index());
+        if compact_tabs && tabs_height > 0 {
+            f.render_widget(Paragraph::new(self.compact_tab_bar()), chunks[0]);
+        } else if tabs_height > 0 {
+            f.render_widget(tabs, chunks[0]);
+        }
+
+        match self.current_view {
+            DashboardView::Overview => self.render_overview(f, chunks[1]),
+            DashboardView::Cpu => self.render_cpu(f, chunks[1]),
+            DashboardView::Memory => self.render_memory(f, chunks[1]),
+            DashboardView::Disk => self.render_disk(f, chunks[1]),
+            DashboardView::Network => self.render_network(f, chunks[1]),
+            DashboardView::Processes => self.render_processes(f, chunks[1]),
+            DashboardView::Strip => self.render_strip(f, chunks[1]),
+            DashboardView::Alerts => self.render_alerts(f, chunks[1]),
+            DashboardView::Compare => self.render_compare(f, chunks[1]),
+        };
+        // Prompts and the status line stay readable; only the data fades.
+        f.render_widget(StaleFade(self.staleness()), chunks[0].union(chunks[1]));
+        match &self.mode {
+            InputMode::Help => self.render_help(f, chunks[1]),
+            InputMode::Switcher(switcher) => self.render_switcher(f, chunks[1], switcher),
+            InputMode::Environment(view) => self.render_environment(f, chunks[1], view),
+            _ => {}
+        }
+
+        if footer_height > 0 {
+            self.render_status(f, chunks[2]);
+        }
+        if self.show_debug {
+            self.render_debug(f, f.size());
+        }
+
+        if self.ascii {
+            f.render_widget(AsciiFallback, f.size());
+        }
+    }
+
+    /// Evaluates the alert thresholds once per new sample, logs every alert
+    /// that fires and rings the terminal bell for it.
+    fn check_alerts(&mut self, out: &mut impl Write) -> Result<(), io::Error> {
+        let (sample, timestamp, values, kernel_rates) = {
+            let state = self.state();
+            (
+                state.sample_count,
+                state.snapshot.timestamp,
+                AlertKind::ALL.map(|kind| kind.value(&state.snapshot)),
+                state.kernel_rates,
+            )
+        };
+        if sample == self.last_alert_sample {
+            return Ok(());
+        }
+        self.last_alert_sample = sample;
+
+        let now = Instant::now();
+        let mut ring = false;
+        for ((kind, value), thresholds) in
+            AlertKind::ALL.into_iter().zip(values).zip(self.thresholds)
+        {
+            let Some(threshold) = thresholds.alert else {
+                continue;
+            };
+            let fired = self.alerts.entry(kind).or_default().observe(
+                thresholds.status(value) == Status::Critical,
+                self.alert_after,
+                self.alert_cooldown,
+                now,
+            );
+            if fired {
+                self.alert_log.push(AlertEvent {
+                    timestamp,
+                    kind,
+                    value,
+                    threshold,
+                });
+            }
+            ring |= fired;
+        }
+
+        let mut spikes = Vec::new();
+        if let Some(rates) = kernel_rates {
+            let readings = self.kernel_rate_readings(rates);
+            for ((label, rate, alert), tracker) in
+                readings.into_iter().zip(&mut self.kernel_rate_trackers)
+            {
+                let Some(alert) = alert else {
+                    continue;
+                };
+                if tracker.observe(rate >= alert, self.alert_after, self.alert_cooldown, now) {
+                    spikes.push(format!(
+                        "{label} at {}/s (>= {}/s)",
+                        abbreviate_count(rate),
+                        abbreviate_count(alert)
+                    ));
+                }
+            }
+        }
+        if !spikes.is_empty() {
+            self.set_status(format!("Spike: {}", spikes.join(", ")));
+            ring = true;
+        }
+
+        if ring {
+            if self.freeze_on_alert {
+                self.state().paused = true;
+                self.frozen_on_alert = Some(timestamp);
+            }
+            out.write_all(b"\x07")?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Drops marks and expanded groups of processes that have gone, once
+    /// per sample, so they don't pile up over a long session and a mark
+    /// doesn't carry over to a new process reusing the PID.
+    fn prune_stale(&mut self) {
+        let state = lock_state(&self.system_state);
+        if state.sample_count == self.last_pruned_sample {
+            return;
+        }
+        self.last_pruned_sample = state.sample_count;
+        let processes = &state.snapshot.processes;
+        let pids: HashSet<_> = processes.iter().map(|process| process.pid).collect();
+        self.marked.retain(|pid| pids.contains(pid));
+        self.expanded_groups
+            .retain(|name| processes.iter().any(|process| process.name == *name));
+    }
+
+    /// The status line: prompts, messages and alerts, or the key hints
+    /// when there's nothing to say, with the time of the last sample.
+    fn render_status<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let status_text = if let InputMode::ConfirmKill(pids) = &self.mode {
+            format!(
+                "Kill {} process(es) ({})? 'y' to confirm, any other key to cancel",
+                pids.len(),
+                pids.iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        } else if let InputMode::ConfirmRenice(pids, step) = &self.mode {
+            format!(
+                "Change the nice value of {} process(es) ({}) by {step:+}? 'y' to confirm, any \
+                 other key to cancel",
+                pids.len(),
+                pids.iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        } else if self.mode == InputMode::Search {
+            format!(
+                "Search: {}_  (Enter to confirm, Esc to cancel)",
+                self.search_query
+            )
+        } else if self.mode == InputMode::Filter {
+            format!(
+                "Filter: {}_  (Enter to keep, Esc to clear)",
+                self.process_filter
+            )
+        } else if self.mode == InputMode::Command {
+            format!(":{}_", self.command)
+        } else if self.mode == InputMode::Help {
+            "Press any key to close the help".to_string()
+        } else if matches!(self.mode, InputMode::Switcher(_)) {
+            "Type to search, Up/Down to pick, Tab to change order, Enter to go, Esc to close"
+                .to_string()
+        } else if matches!(self.mode, InputMode::Environment(_)) {
+            "Type to search, Up/Down to scroll, Tab to show or hide secrets, Esc to close"
+                .to_string()
+        } else if let Some(message) = self.status_message() {
+            message.to_string()
+        } else if self.chart_cursor.is_some() {
+            format!(
+                "Chart cursor: Left/Right to move, '{}' or Esc to leave",
+                self.keys.hint(Action::ChartCursor)
+            )
+        } else {
+            format!(
+                "Press '{}' to quit, '{}'/'{}' to switch tabs, '{}' to search processes, '{}' for help",
+                self.keys.hint(Action::Quit),
+                self.keys.hint(Action::PrevTab),
+                self.keys.hint(Action::NextTab),
+                self.keys.hint(Action::Search),
+                self.keys.hint(Action::Help)
+            )
         };
+        let mut status_spans = Vec::new();
+        let active_alerts: Vec<_> = [AlertKind::Cpu, AlertKind::Memory]
+            .into_iter()
+            .filter(|kind| {
+                self.alerts
+                    .get(kind)
+                    .is_some_and(|tracker| tracker.is_active(self.alert_after))
+            })
+            .map(AlertKind::label)
+            .collect();
+        // A poisoned lock means the collection thread panicked, so nothing
+        // will update again; the last data it stored is still shown.
+        if self.system_state.is_poisoned() {
+            status_spans.push(Span::styled(
+                "Collector crashed, data is frozen  ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        let state = self.state();
+        if state.paused {
+            let reason = match self.frozen_on_alert {
+                Some(at) => format!("Frozen on alert at {} UTC", &format_timestamp(at)[11..]),
+                None => "Paused".to_string(),
+            };
+            status_spans.push(Span::styled(
+                format!(
+                    "{reason} - press '{}' to resume  ",
+                    self.keys.hint(Action::Pause)
+                ),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some(error) = &state.last_error {
+            status_spans.push(Span::styled(
+                format!("Collection failed: {error}  "),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        let mut clock = format!(
+            "updated {}  ",
+            format_ago(state.last_sample_age().as_secs_f64())
+        );
+        drop(state);
+        let staleness = self.staleness();
+        if staleness >= Staleness::Stale {
+            clock.insert_str(0, "STALE, ");
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        // Only the time of day; the date rarely matters while watching.
+        clock.push_str(&format_local_time(now));
+        if !active_alerts.is_empty() {
+            status_spans.push(Span::styled(
+                format!("ALERT: {}  ", active_alerts.join(", ")),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        status_spans.push(Span::raw(status_text));
+        let status_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(clock.chars().count() as u16 + 1),
+            ])
+            .split(area);
+        let status =
+            Paragraph::new(Spans::from(status_spans)).style(Style::default().fg(Color::White));
+        f.render_widget(status, status_chunks[0]);
+        let clock = Paragraph::new(clock)
+            .style(Style::default().fg(match staleness {
+                Staleness::Fresh => Color::DarkGray,
+                Staleness::Late => Color::Yellow,
+                Staleness::Stale | Staleness::Frozen => Color::Red,
+            }))
+            .alignment(Alignment::Right);
+        f.render_widget(clock, status_chunks[1]);
+    }
+
+    fn render_overview<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let state = self.state();
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -134,611 +826,6302 @@ impl Dashboard {
                     Constraint::Percentage(25),
                     Constraint::Percentage(25),
                     Constraint::Percentage(25),
-                    Constraint::Percentage(25),
                 ]
                 .as_ref(),
             )
             .split(area);
 
-        let cpu_usage = state.system.global_cpu_usage();
-        let cpu_summary = Paragraph::new(vec![
-            Spans::from(vec![Span::raw(format!("CPU Usage: {:.1}%", cpu_usage))]),
+        for ((title, lines), area) in self.overview_boxes(&state).into_iter().zip(chunks) {
+            match lines {
+                Ok(lines) => {
+                    let summary = Paragraph::new(lines)
+                        .block(Block::default().title(title).borders(Borders::ALL));
+                    f.render_widget(summary, area);
+                }
+                Err(availability) => Self::render_unavailable(f, area, title, availability),
+            }
+        }
+    }
+
+    /// Title and lines of each Overview box, or why a box has nothing to
+    /// show.
+    fn overview_boxes(
+        &self,
+        state: &SystemState,
+    ) -> [(&'static str, Result<Vec<Spans<'static>>, DataAvailability>); 4] {
+        [
+            ("CPU Summary", self.overview_cpu_lines(state)),
+            ("Memory Summary", self.overview_memory_lines(state)),
+            ("Disk Summary", self.overview_disk_lines(state)),
+            ("Network Summary", self.overview_network_lines(state)),
+        ]
+    }
+
+    fn overview_cpu_lines(
+        &self,
+        state: &SystemState,
+    ) -> Result<Vec<Spans<'static>>, DataAvailability> {
+        if state.cpu_availability() == DataAvailability::Unavailable {
+            return Err(DataAvailability::Unavailable);
+        }
+        let cpu_usage = state.snapshot.cpu.usage;
+        let mut lines = vec![
+            Spans::from(vec![Span::raw(format!(
+                "CPU Usage: {}",
+                self.precision.percent(cpu_usage.into())
+            ))]),
             Spans::from(vec![Span::raw(format!(
                 "Cores: {}",
-                state.system.cpus().iter().count()
+                state.snapshot.cpu.cores.len()
             ))]),
-        ])
-        .block(Block::default().title("CPU Summary").borders(Borders::ALL));
-        f.render_widget(cpu_summary, chunks[0]);
+        ];
+        if let Some(limit) = state.snapshot.cgroup.as_ref().and_then(|c| c.cpu_limit) {
+            lines.push(Spans::from(vec![Span::raw(format!(
+                "Container limit: {:.1} cores",
+                limit
+            ))]));
+        }
+        if let Some(own) = state.own_process() {
+            lines.push(Spans::from(vec![Span::styled(
+                format!(
+                    "dashoxide itself: {} CPU, {}",
+                    self.precision.percent(own.cpu_usage.into()),
+                    self.precision
+                        .memory(own.memory as f64 / (1024.0 * 1024.0), "MB")
+                ),
+                Style::default().fg(Color::DarkGray),
+            )]));
+        }
+        let top = self.top_consumers(
+            self.overview_processes(&state.snapshot),
+            |process| f64::from(process.cpu_usage),
+            |process| {
+                format!(
+                    "{} {}",
+                    self.process_name(&process.name, process.pid),
+                    self.precision.percent(process.cpu_usage.into())
+                )
+            },
+        );
+        lines.extend(overview_top_line("Top", top));
+        Ok(lines)
+    }
 
-        let mem_used = state.system.used_memory();
-        let mem_total = state.system.total_memory();
-        let mem_percent = (mem_used as f64 / mem_total as f64 * 100.0) as u64;
+    fn overview_memory_lines(
+        &self,
+        state: &SystemState,
+    ) -> Result<Vec<Spans<'static>>, DataAvailability> {
+        let memory_availability = state.memory_availability();
+        if memory_availability == DataAvailability::Unavailable {
+            return Err(memory_availability);
+        }
+        let mem_used = state.snapshot.memory.used;
+        let mem_total = state.snapshot.memory.total;
+        let mem_percent = mem_used as f64 / mem_total as f64 * 100.0;
+        let risk = oom_risk(&state.snapshot.memory, &state.memory_history);
 
-        let memory_summary = Paragraph::new(vec![
-            Spans::from(vec![Span::raw(format!("Memory Usage: {}%", mem_percent))]),
+        let mut lines = vec![
             Spans::from(vec![Span::raw(format!(
-                "Used: {:.2} GB",
-                mem_used as f64 / 1_000_000_000.0
+                "Memory Usage: {}",
+                self.precision.percent(mem_percent)
             ))]),
             Spans::from(vec![Span::raw(format!(
-                "Total: {:.2} GB",
-                mem_total as f64 / 1_000_000_000.0
+                "Used: {}",
+                self.precision
+                    .memory(mem_used as f64 / 1_000_000_000.0, "GB")
             ))]),
-        ])
-        .block(
-            Block::default()
-                .title("Memory Summary")
-                .borders(Borders::ALL),
+            Spans::from(vec![Span::raw(format!(
+                "Total: {}",
+                self.precision
+                    .memory(mem_total as f64 / 1_000_000_000.0, "GB")
+            ))]),
+            Spans::from(vec![
+                Span::raw("OOM risk: "),
+                Span::styled(risk.label(), Style::default().fg(risk_color(risk))),
+            ]),
+        ];
+        // Host totals understate how close a container is to being
+        // OOM-killed, so its own limit gets a line of its own.
+        if let Some((used, limit)) = state.snapshot.cgroup.as_ref().and_then(|c| c.memory()) {
+            lines.push(Spans::from(vec![Span::raw(format!(
+                "Container: {} of {} limit",
+                self.precision.percent(used as f64 / limit as f64 * 100.0),
+                self.precision.memory(limit as f64 / 1_000_000_000.0, "GB")
+            ))]));
+        }
+        let top = self.top_consumers(
+            self.overview_processes(&state.snapshot),
+            |process| process.memory as f64,
+            |process| {
+                format!(
+                    "{} {}",
+                    self.process_name(&process.name, process.pid),
+                    self.precision
+                        .memory(process.memory as f64 / (1024.0 * 1024.0), "MB")
+                )
+            },
         );
-        f.render_widget(memory_summary, chunks[1]);
+        lines.extend(overview_top_line("Top", top));
+        Ok(lines)
+    }
 
-        let mut total_space = 0;
-        let mut total_used = 0;
-        for disk in state.disks.list() {
-            total_space += disk.total_space();
-            total_used += disk.total_space() - disk.available_space();
+    fn overview_disk_lines(
+        &self,
+        state: &SystemState,
+    ) -> Result<Vec<Spans<'static>>, DataAvailability> {
+        let disk_availability = state.disk_availability();
+        if disk_availability.is_missing() {
+            return Err(disk_availability);
         }
+
+        let (total_used, total_space) = state.snapshot.disk_space(self.disk_total_all);
         let disk_percent = if total_space > 0 {
             total_used as f64 / total_space as f64 * 100.0
         } else {
             0.0
         };
         let disk_unit = 1_000_000_000;
-        let disk_summary = Paragraph::new(vec![
-            Spans::from(format!("Usage: {:.1}%", disk_percent)),
+        let mut lines = vec![
+            Spans::from(format!("Usage: {}", self.precision.percent(disk_percent))),
             Spans::from(format!(
-                "Used: {:.} GB",
-                total_used as f64 / disk_unit as f64
+                "Used: {}",
+                self.precision
+                    .memory(total_used as f64 / disk_unit as f64, "GB")
             )),
-        ])
-        .block(Block::default().title("Disk Summary").borders(Borders::ALL));
-        f.render_widget(disk_summary, chunks[2]);
-
-        let (rx_rate, tx_rate) = if state.network_history.len() >= 2 {
-            let current = state.network_history.iter().nth_back(0).unwrap();
-            let previous = state.network_history.iter().nth_back(1).unwrap();
-            (
-                current.0.saturating_sub(previous.0),
-                current.1.saturating_sub(previous.1),
-            )
-        } else {
-            (0, 0)
-        };
+        ];
+        let used_percent =
+            |disk: &DiskSnapshot| disk.used() as f64 / disk.total.max(1) as f64 * 100.0;
+        let fullest = self.top_consumers(
+            state
+                .snapshot
+                .disks
+                .iter()
+                .filter(|disk| self.show_pseudo_disks || !disk.is_pseudo()),
+            |disk| used_percent(disk),
+            |disk| {
+                format!(
+                    "{} {}",
+                    disk.mount_point,
+                    self.precision.percent(used_percent(disk))
+                )
+            },
+        );
+        lines.extend(overview_top_line("Fullest", fullest));
+        Ok(lines)
+    }
 
-        fn format_rate(bytes_per_sec: u64) -> String {
-            const KB: f64 = 1024.0;
-            const MB: f64 = 1024.0 * KB;
-            if bytes_per_sec == 0 {
-                return "0 B/s".to_string();
-            }
-            let rate = bytes_per_sec as f64;
-            if rate < KB {
-                format!("{} B/s", rate / KB)
-            } else {
-                format!("{:.1} MB/s", rate / MB)
-            }
+    fn overview_network_lines(
+        &self,
+        state: &SystemState,
+    ) -> Result<Vec<Spans<'static>>, DataAvailability> {
+        let network_availability = state.network_availability();
+        if network_availability != DataAvailability::Available {
+            return Err(network_availability);
         }
 
-        let network_summary = Paragraph::new(vec![
+        let (rx_rate, tx_rate) = state.network_rate(self.rate_window);
+
+        let mut lines = vec![
             Spans::from(vec![
                 Span::styled("Down: ", Style::default().fg(Color::Green)),
-                Span::raw(format_rate(rx_rate)),
+                Span::raw(self.network_rate(rx_rate)),
             ]),
             Spans::from(vec![
                 Span::styled("Up: ", Style::default().fg(Color::Red)),
-                Span::raw(format_rate(tx_rate)),
+                Span::raw(self.network_rate(tx_rate)),
             ]),
-        ])
-        .block(
-            Block::default()
-                .title("Network Summary")
-                .borders(Borders::ALL),
+        ];
+        let busiest = self.top_consumers(
+            state
+                .snapshot
+                .networks
+                .iter()
+                .filter(|interface| state.counts_interface(interface)),
+            |interface| (interface.rx_rate + interface.tx_rate) as f64,
+            |interface| {
+                format!(
+                    "{} {}",
+                    interface.name,
+                    self.network_rate(interface.rx_rate + interface.tx_rate)
+                )
+            },
         );
-        f.render_widget(network_summary, chunks[3]);
+        lines.extend(overview_top_line("Busiest", busiest));
+        Ok(lines)
+    }
+
+    /// One line each for CPU, memory, swap, disk, network and the busiest
+    /// process: a label, the current value and a sparkline of the history.
+    fn render_strip<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let state = self.state();
+        let lines = self.strip_lines(&state);
+
+        let block = Block::default().title("Strip").borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let mut constraints = vec![Constraint::Length(1); lines.len()];
+        constraints.push(Constraint::Min(0));
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(inner);
+        for ((label, text, history), row) in lines.into_iter().zip(rows) {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(
+                    [
+                        Constraint::Length(8),
+                        Constraint::Length(32),
+                        Constraint::Min(0),
+                    ]
+                    .as_ref(),
+                )
+                .split(row);
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    label,
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                columns[0],
+            );
+            f.render_widget(Paragraph::new(text), columns[1]);
+            // Sparklines draw from the left edge, so keep the newest samples
+            // that fit. Rates have no natural ceiling and scale to their peak.
+            let recent = &history[history.len().saturating_sub(columns[2].width as usize)..];
+            let max = if label == "Network" {
+                recent.iter().copied().max().unwrap_or(0).max(1)
+            } else {
+                1000
+            };
+            f.render_widget(
+                Sparkline::default()
+                    .bar_set(self.glyphs.bars.clone())
+                    .data(recent)
+                    .max(max)
+                    .style(Style::default().fg(Color::Cyan)),
+                columns[2],
+            );
+        }
+    }
+
+    /// Label, current value and sparkline history of each Strip line.
+    fn strip_lines(&self, state: &SystemState) -> [(&'static str, String, Vec<u64>); 6] {
+        const GB: f64 = 1_000_000_000.0;
+
+        let snapshot = &state.snapshot;
+        // Sparklines take integers, so percentages keep a decimal place.
+        let permille = |history: &[(u64, u64)]| -> Vec<u64> {
+            history
+                .iter()
+                .map(|&(used, total)| (used * 1000).checked_div(total).unwrap_or(0))
+                .collect()
+        };
+        let usage = |availability: DataAvailability, used: u64, total: u64| match availability {
+            DataAvailability::Available => format!(
+                "{:>6}  {:.*}/{}",
+                self.precision
+                    .percent(used as f64 / total.max(1) as f64 * 100.0),
+                self.precision.memory,
+                used as f64 / GB,
+                self.precision.memory(total as f64 / GB, "GB")
+            ),
+            availability => availability.message().to_string(),
+        };
+
+        let memory = &snapshot.memory;
+        let (disk_used, disk_total) = snapshot.disk_space(self.disk_total_all);
+        let (rx_rate, tx_rate) = state.network_rate(self.rate_window);
+        let top = snapshot
+            .processes
+            .iter()
+            .filter(|process| !self.is_hidden_self(process))
+            .max_by(|a, b| a.cpu_usage.total_cmp(&b.cpu_usage));
+        [
+            (
+                "CPU",
+                match state.cpu_availability() {
+                    DataAvailability::Unavailable => {
+                        DataAvailability::Unavailable.message().to_string()
+                    }
+                    _ => format!(
+                        "{:5.1}%  {} cores",
+                        snapshot.cpu.usage,
+                        snapshot.cpu.cores.len()
+                    ),
+                },
+                state
+                    .cpu_history
+                    .iter()
+                    .map(|&usage| (usage * 10.0) as u64)
+                    .collect(),
+            ),
+            (
+                "Memory",
+                usage(state.memory_availability(), memory.used, memory.total),
+                permille(&state.memory_history),
+            ),
+            (
+                memory.swap_label(),
+                usage(
+                    state.swap_availability(),
+                    memory.swap_used,
+                    memory.swap_total,
+                ),
+                permille(&state.swap_history),
+            ),
+            (
+                "Disk",
+                usage(state.disk_availability(), disk_used, disk_total),
+                permille(&state.disk_space_history),
+            ),
+            (
+                "Network",
+                match state.network_availability() {
+                    DataAvailability::Available => {
+                        format!(
+                            "down {}, up {}",
+                            self.network_rate(rx_rate),
+                            self.network_rate(tx_rate)
+                        )
+                    }
+                    availability => availability.message().to_string(),
+                },
+                state
+                    .network_history
+                    .iter()
+                    .map(|&(rx, tx)| rx + tx)
+                    .collect(),
+            ),
+            (
+                "Top",
+                top.map_or_else(
+                    || "no processes".to_string(),
+                    |process| {
+                        format!(
+                            "{:5.1}%  {} ({})",
+                            process.cpu_usage,
+                            self.process_name(&process.name, process.pid),
+                            process.pid
+                        )
+                    },
+                ),
+                Vec::new(),
+            ),
+        ]
+    }
+
+    fn render_unavailable<B: Backend>(
+        f: &mut Frame<'_, B>,
+        area: tui::layout::Rect,
+        title: &str,
+        availability: DataAvailability,
+    ) {
+        let message = Paragraph::new(availability.message())
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .alignment(Alignment::Center);
+        f.render_widget(message, area);
+    }
+
+    /// Percentage to fill the gauge `key` to: `percent` itself, or with
+    /// animation on, wherever the gauge is on its way there. Labels keep
+    /// showing the exact value.
+    fn gauge_value(&self, key: &str, percent: f64) -> f64 {
+        match &self.gauge_animations {
+            Some(animations) => animations.borrow_mut().value(key, percent, Instant::now()),
+            None => percent,
+        }
     }
 
-    fn handle_input(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Char('q') => self.should_quit = true,
-            KeyCode::Left => {
-                self.current_view = match self.current_view {
-                    DashboardView::Overview => DashboardView::Processes,
-                    DashboardView::Cpu => DashboardView::Overview,
-                    DashboardView::Memory => DashboardView::Cpu,
-                    DashboardView::Disk => DashboardView::Memory,
-                    DashboardView::Network => DashboardView::Disk,
-                    DashboardView::Processes => DashboardView::Network,
+    /// The compared processes in two columns, each with its CPU, memory,
+    /// open file and disk I/O history.
+    fn render_compare<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        if self.compared.is_empty() {
+            let message = Paragraph::new(format!(
+                "Select a process on the Processes tab and press '{}' to compare it; \
+                 up to two are shown side by side",
+                self.keys.hint(Action::Compare)
+            ))
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().title("Compare").borders(Borders::ALL))
+            .alignment(Alignment::Center);
+            f.render_widget(message, area);
+            return;
+        }
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(area);
+        let state = self.state();
+        for (index, column) in columns.into_iter().enumerate() {
+            match self
+                .compared
+                .get(index)
+                .and_then(|&pid| state.process_history(pid))
+            {
+                Some(history) => {
+                    self.render_process_history(f, column, history, Direction::Vertical, "");
                 }
-            }
-            KeyCode::Right => {
-                self.current_view = match self.current_view {
-                    DashboardView::Overview => DashboardView::Cpu,
-                    DashboardView::Cpu => DashboardView::Memory,
-                    DashboardView::Memory => DashboardView::Disk,
-                    DashboardView::Disk => DashboardView::Network,
-                    DashboardView::Network => DashboardView::Processes,
-                    DashboardView::Processes => DashboardView::Overview,
+                None => {
+                    let message = Paragraph::new(format!(
+                        "Pick a second process with '{}'",
+                        self.keys.hint(Action::Compare)
+                    ))
+                    .style(Style::default().fg(Color::DarkGray))
+                    .block(Block::default().borders(Borders::ALL))
+                    .alignment(Alignment::Center);
+                    f.render_widget(message, column);
                 }
             }
-
-            _ => {}
         }
     }
 
-    fn render_cpu(
+    /// One small chart per metric of a tracked process, laid out in
+    /// `direction` inside a block titled with the process and `hint`.
+    fn render_process_history<B: Backend>(
         &self,
-        f: &mut tui::Frame<'_, CrosstermBackend<io::Stdout>>,
+        f: &mut Frame<'_, B>,
         area: tui::layout::Rect,
+        history: &ProcessHistory,
+        direction: Direction,
+        hint: &str,
     ) {
-        let state = match self.system_state.lock() {
-            Ok(guard) => guard,
-            Err(_) => return,
-        };
-
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
-            .split(area);
-        let cpu_usage = state.system.global_cpu_usage();
-        let cpu_usage_text = format!("CPU Usage: {:.1}%", cpu_usage);
+        const MB: f64 = 1024.0 * 1024.0;
 
-        let cpu_gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .title("Current CPU Usage")
-                    .borders(Borders::ALL),
-            )
-            .gauge_style(Style::default().fg(Color::Cyan))
-            .percent(cpu_usage as u16);
+        let mut title = vec![Span::styled(
+            format!(
+                "{} ({})",
+                self.process_name(&history.name, history.pid),
+                history.pid
+            ),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )];
+        if history.exited {
+            title.push(Span::styled(
+                " - exited, last data kept",
+                Style::default().fg(Color::Red),
+            ));
+        }
+        title.push(Span::raw(hint));
+        let block = Block::default()
+            .title(Spans::from(title))
+            .borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
 
-        f.render_widget(cpu_gauge, chunks[0]);
+        let series = |value: &dyn Fn(&ProcessSample) -> Option<f64>| -> Vec<(f64, f64)> {
+            let data = history
+                .samples
+                .iter()
+                .enumerate()
+                .filter_map(|(i, sample)| Some((i as f64, value(sample)?)))
+                .collect();
+            self.chart_points(data)
+        };
+        let cpu = series(&|sample| Some(f64::from(sample.cpu_usage)));
+        let memory = series(&|sample| Some(sample.memory as f64 / MB));
+        let open_files = series(&|sample| sample.open_files.map(f64::from));
+        let read = series(&|sample| Some(sample.disk_read_rate as f64 / 1024.0));
+        let written = series(&|sample| Some(sample.disk_write_rate as f64 / 1024.0));
 
-        let cpu_history = &state.cpu_history;
+        let [cpu_title, memory_title, open_files_title, io_title] =
+            self.process_history_titles(history);
+        let charts = [
+            (cpu_title, vec![("CPU %", Color::Cyan, &cpu)]),
+            (memory_title, vec![("MB", Color::Magenta, &memory)]),
+            (open_files_title, vec![("FDs", Color::Yellow, &open_files)]),
+            (
+                io_title,
+                vec![
+                    ("Read KB/s", Color::Green, &read),
+                    ("Written KB/s", Color::Red, &written),
+                ],
+            ),
+        ];
 
-        let mut chart_data: Vec<(f64, f64)> = Vec::new();
-        for (i, &usage) in cpu_history.iter().enumerate() {
-            chart_data.push((i as f64, usage as f64));
+        let rows = Layout::default()
+            .direction(direction)
+            .constraints([Constraint::Ratio(1, 4); 4].as_ref())
+            .split(inner);
+        for ((title, lines), row) in charts.into_iter().zip(rows) {
+            let top = lines
+                .iter()
+                .flat_map(|(_, _, data)| data.iter().map(|&(_, y)| y))
+                .fold(0.0, f64::max);
+            let datasets = lines
+                .into_iter()
+                .map(|(name, color, data)| {
+                    Dataset::default()
+                        .name(name)
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(color))
+                        .data(data)
+                })
+                .collect();
+            let chart = Chart::new(datasets)
+                .block(Block::default().title(title).borders(Borders::TOP))
+                .x_axis(Axis::default().bounds([0.0, HISTORY_LEN as f64]))
+                .y_axis(
+                    Axis::default()
+                        .style(Style::default().fg(Color::Gray))
+                        .bounds([0.0, (top * 1.1).max(1.0)])
+                        .labels(vec![
+                            Span::raw("0"),
+                            Span::raw(format!("{:.0}", (top * 1.1).max(1.0))),
+                        ]),
+                );
+            f.render_widget(chart, row);
         }
+    }
 
-        let datasets = vec![
-            Dataset::default()
-                .name("CPU Usage")
-                .graph_type(GraphType::Line)
-                .style(Style::default().fg(Color::Cyan))
-                .data(&chart_data),
-        ];
+    /// Titles of a tracked process's charts, each with the latest value.
+    fn process_history_titles(&self, history: &ProcessHistory) -> [String; 4] {
+        let last = history.last();
+        [
+            format!(
+                "CPU {}",
+                self.precision
+                    .percent(last.map_or(0.0, |sample| sample.cpu_usage).into())
+            ),
+            format!(
+                "Memory {}",
+                self.precision.memory(
+                    last.map_or(0.0, |sample| sample.memory as f64 / (1024.0 * 1024.0)),
+                    "MB"
+                )
+            ),
+            match last.and_then(|sample| sample.open_files) {
+                Some(open) => format!("Open files {open}"),
+                None => "Open files n/a".to_string(),
+            },
+            format!(
+                "Disk I/O {} read / {} written",
+                format_rate(
+                    last.map_or(0, |sample| sample.disk_read_rate),
+                    &self.precision
+                ),
+                format_rate(
+                    last.map_or(0, |sample| sample.disk_write_rate),
+                    &self.precision
+                )
+            ),
+        ]
+    }
 
-        let chart = Chart::new(datasets)
-            .block(Block::default().title("CPU History").borders(Borders::ALL))
-            .x_axis(
-                Axis::default()
-                    .title(Span::styled("Time", Style::default().fg(Color::Red)))
-                    .style(Style::default().fg(Color::White))
-                    .bounds([0.0, 60.0])
-                    .labels(
-                        ["60s ago", "30s ago", "now"]
-                            .iter()
-                            .map(|s| Span::styled(*s, Style::default().fg(Color::White)))
-                            .collect(),
+    /// The alert log, newest first, or why it's empty.
+    fn render_alerts<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let title = format!(
+            "Alerts ({} logged, '{}' to export to {}, '{}' to clear)",
+            self.alert_log.len(),
+            self.keys.hint(Action::ExportAlerts),
+            self.alert_log_path.display(),
+            self.keys.hint(Action::ClearAlerts)
+        );
+        if self.alert_log.is_empty() {
+            let message = Paragraph::new(self.no_alerts_message())
+                .style(Style::default().fg(Color::DarkGray))
+                .block(Block::default().title(title).borders(Borders::ALL))
+                .alignment(Alignment::Center);
+            f.render_widget(message, area);
+            return;
+        }
+
+        let header = Row::new(
+            ALERT_HEADERS
+                .iter()
+                .map(|h| Cell::from(Span::styled(*h, Style::default().fg(Color::Yellow)))),
+        )
+        .style(Style::default().bg(Color::DarkGray))
+        .height(1);
+        let rows = self.alert_texts().into_iter().map(Row::new);
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .widths(&[
+                Constraint::Length(21),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ]);
+        f.render_widget(table, area);
+    }
+
+    /// Why the alert log is empty.
+    fn no_alerts_message(&self) -> &'static str {
+        if self
+            .thresholds
+            .iter()
+            .all(|thresholds| thresholds.alert.is_none())
+        {
+            "No thresholds set; start with --cpu-alert or --mem-alert"
+        } else {
+            "No alerts fired yet"
+        }
+    }
+
+    /// Cells of the alert table, newest alert first.
+    fn alert_texts(&self) -> Vec<Vec<String>> {
+        self.alert_log
+            .iter()
+            .rev()
+            .map(|event| {
+                vec![
+                    format_timestamp(event.timestamp),
+                    event.kind.label().to_string(),
+                    self.precision.percent(event.value.into()),
+                    self.precision.percent(event.threshold.into()),
+                ]
+            })
+            .collect()
+    }
+
+    /// Writes the alert log to `path` as CSV and reports how it went.
+    fn export_alerts(&mut self, path: &Path) {
+        let message = match fs::write(path, self.alert_log.to_csv()) {
+            Ok(()) => format!(
+                "Exported {} alerts to {}",
+                self.alert_log.len(),
+                path.display()
+            ),
+            Err(e) => format!("Exporting alerts to {} failed: {e}", path.display()),
+        };
+        self.set_status(message);
+    }
+
+    /// Copies the current view as text to the clipboard, or writes it to
+    /// `path`. Without a clipboard tool it ends up in
+    /// `dashoxide-<view>.txt` in the working directory instead.
+    fn copy_view(&mut self, path: Option<&Path>) {
+        let text = self.view_text();
+        let view = self.current_view.title();
+        let write = |path: &Path| fs::write(path, &text);
+        let message = match path {
+            Some(path) => match write(path) {
+                Ok(()) => format!("Wrote the {view} view to {}", path.display()),
+                Err(e) => format!("Writing the {view} view to {} failed: {e}", path.display()),
+            },
+            None => match clipboard::copy(&text) {
+                Ok(tool) => format!("Copied the {view} view to the clipboard ({tool})"),
+                Err(e) => {
+                    let path = PathBuf::from(format!("dashoxide-{}.txt", self.current_view.key()));
+                    match write(&path) {
+                        Ok(()) => format!("{e}; wrote the {view} view to {}", path.display()),
+                        Err(write_error) => format!(
+                            "{e}; writing the {view} view to {} failed: {write_error}",
+                            path.display()
+                        ),
+                    }
+                }
+            },
+        };
+        self.set_status(message);
+    }
+
+    /// The current view as plain text for pasting into a chat or ticket:
+    /// the values and table rows on screen, with the same sort, filter and
+    /// privacy settings, but without charts or key hints.
+    fn view_text(&self) -> String {
+        let state = self.state();
+        let mut lines = vec![format!(
+            "{} at {} UTC",
+            self.current_view.title(),
+            format_timestamp(state.snapshot.timestamp)
+        )];
+        match self.current_view {
+            DashboardView::Overview => {
+                for (title, box_lines) in self.overview_boxes(&state) {
+                    lines.push(String::new());
+                    lines.push(title.to_string());
+                    match box_lines {
+                        Ok(box_lines) => lines.extend(box_lines.iter().map(spans_text)),
+                        Err(availability) => lines.push(availability.message().to_string()),
+                    }
+                }
+            }
+            DashboardView::Cpu => match state.cpu_availability() {
+                DataAvailability::Unavailable => {
+                    lines.push(DataAvailability::Unavailable.message().to_string());
+                }
+                _ => {
+                    lines.push(self.cpu_usage_text(&state.snapshot));
+                    if let Some(rates) = state.kernel_rates {
+                        lines.push(spans_text(&Spans::from(self.kernel_rate_spans(rates))));
+                    }
+                    let (shown, idle) = self.shown_cores(&state.snapshot.cpu.cores);
+                    lines.extend(
+                        shown
+                            .into_iter()
+                            .map(|(index, usage)| self.core_label(index, usage)),
+                    );
+                    if idle > 0 {
+                        lines.push(self.idle_cores_text(idle));
+                    }
+                }
+            },
+            DashboardView::Memory => lines.extend(self.memory_text(&state)),
+            DashboardView::Disk => {
+                let availability = state.disk_availability();
+                if availability.is_missing() {
+                    lines.push(availability.message().to_string());
+                } else {
+                    let (used, total) = state.snapshot.disk_space(self.disk_total_all);
+                    lines.push(format!(
+                        "Total Disk Usage: {}",
+                        self.gauge_text(
+                            used as f64 / 1_000_000_000.0,
+                            total as f64 / 1_000_000_000.0,
+                            "GB"
+                        )
+                    ));
+                    let (headers, texts) = self.disk_table(&state.snapshot);
+                    lines.push(String::new());
+                    lines.push(text_table(&headers, &texts));
+                }
+            }
+            DashboardView::Network => {
+                let availability = state.network_availability();
+                if availability.is_missing() {
+                    lines.push(availability.message().to_string());
+                } else {
+                    let (rx_rate, tx_rate) = state.network_rate(self.rate_window);
+                    let (session_rx, session_tx) = state.session_totals;
+                    lines.push(format!(
+                        "Down: {}  Up: {}",
+                        self.network_rate(rx_rate),
+                        self.network_rate(tx_rate)
+                    ));
+                    lines.push(format!(
+                        "Since start: {} down, {} up",
+                        format_total_bytes(session_rx, &self.precision),
+                        format_total_bytes(session_tx, &self.precision)
+                    ));
+                    if !state.interface_filter.is_empty() {
+                        lines.push(format!("Interfaces matching '{}'", state.interface_filter));
+                    }
+                    let interfaces = self.listed_interfaces(&state);
+                    let (headers, texts) = self.network_table(&state, &interfaces);
+                    lines.push(String::new());
+                    lines.push(text_table(&headers, &texts));
+                }
+            }
+            DashboardView::Processes => {
+                if !self.process_filter.is_empty() {
+                    lines.push(format!("Filter '{}'", self.process_filter));
+                }
+                let rows = self.visible_rows(&state.snapshot);
+                let headers = self.process_headers();
+                let headers: Vec<_> = headers.iter().map(String::as_str).collect();
+                lines.push(text_table(&headers, &self.process_texts(&state, &rows)));
+            }
+            DashboardView::Strip => {
+                lines.extend(
+                    self.strip_lines(&state)
+                        .into_iter()
+                        .map(|(label, text, _)| format!("{label:<8}{text}")),
+                );
+            }
+            DashboardView::Alerts => {
+                if self.alert_log.is_empty() {
+                    lines.push(self.no_alerts_message().to_string());
+                } else {
+                    lines.push(text_table(&ALERT_HEADERS, &self.alert_texts()));
+                }
+            }
+            DashboardView::Compare => {
+                if self.compared.is_empty() {
+                    lines.push("No processes picked to compare".to_string());
+                }
+                for history in self
+                    .compared
+                    .iter()
+                    .filter_map(|&pid| state.process_history(pid))
+                {
+                    lines.push(String::new());
+                    lines.push(format!(
+                        "{} ({}){}",
+                        self.process_name(&history.name, history.pid),
+                        history.pid,
+                        if history.exited {
+                            " - exited, last data kept"
+                        } else {
+                            ""
+                        }
+                    ));
+                    lines.extend(self.process_history_titles(history));
+                }
+            }
+        }
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    /// The Memory view's gauges and tables as lines of text.
+    fn memory_text(&self, state: &SystemState) -> Vec<String> {
+        const GIB: f64 = 1_024.0 * 1_024.0 * 1_024.0;
+        let memory = &state.snapshot.memory;
+        let mut lines = Vec::new();
+        if let Some((version, (used, limit))) = state
+            .snapshot
+            .cgroup
+            .as_ref()
+            .and_then(|c| Some((c.version, c.memory()?)))
+        {
+            lines.push(format!(
+                "Container Memory (cgroup v{version} limit): {}",
+                self.gauge_text(used as f64 / GIB, limit as f64 / GIB, "GiB")
+            ));
+        }
+        let availability = state.memory_availability();
+        if availability == DataAvailability::Unavailable {
+            lines.push(format!("RAM Usage: {}", availability.message()));
+        } else {
+            let risk = oom_risk(memory, &state.memory_history);
+            lines.push(format!(
+                "RAM Usage: {} - OOM risk: {}",
+                self.gauge_text(memory.used as f64 / GIB, memory.total as f64 / GIB, "GiB"),
+                risk.label()
+            ));
+        }
+        if state.swap_availability() == DataAvailability::Available {
+            lines.push(format!(
+                "{} Usage: {}",
+                memory.swap_label(),
+                self.gauge_text(
+                    memory.swap_used as f64 / GIB,
+                    memory.swap_total as f64 / GIB,
+                    "GiB"
+                )
+            ));
+        } else {
+            lines.push(format!(
+                "{} Usage: none configured or reported",
+                memory.swap_label()
+            ));
+        }
+        if state.snapshot.numa_nodes.len() > 1 {
+            let rows: Vec<_> = self
+                .numa_rows(&state.snapshot)
+                .into_iter()
+                .map(|(_, cells)| cells)
+                .collect();
+            lines.push(String::new());
+            lines.push(text_table(&NUMA_HEADERS, &rows));
+        }
+        if self.show_kernel_memory
+            && let Some(kernel) = &state.snapshot.kernel_memory
+        {
+            lines.push(String::new());
+            lines.extend(self.kernel_memory_lines(kernel).into_iter().map(|line| {
+                line.iter()
+                    .map(|(label, value)| format!("{label}: {value}"))
+                    .collect::<Vec<_>>()
+                    .join("   ")
+            }));
+        }
+        lines
+    }
+
+    /// Index and usage of the cores on the Per-Core Usage panel, and how
+    /// many were left off as idle.
+    fn shown_cores(&self, cores: &[f32]) -> (Vec<(usize, f32)>, usize) {
+        let shown: Vec<_> = cores
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, usage)| !self.hide_idle_cores || usage >= self.idle_core_threshold)
+            .collect();
+        let idle = cores.len() - shown.len();
+        (shown, idle)
+    }
+
+    /// "cpu3 42.0%"
+    fn core_label(&self, index: usize, usage: f32) -> String {
+        format!("cpu{index} {}", self.precision.percent(f64::from(usage)))
+    }
+
+    fn idle_cores_text(&self, idle: usize) -> String {
+        format!("{idle} idle cores (< {:.0}%)", self.idle_core_threshold)
+    }
+
+    /// Usage percent and `NUMA_HEADERS` cells of each NUMA node.
+    fn numa_rows(&self, snapshot: &Snapshot) -> Vec<(f64, Vec<String>)> {
+        let gib = |bytes: u64| self.precision.memory(bytes as f64 / 1_073_741_824.0, "GiB");
+        snapshot
+            .numa_nodes
+            .iter()
+            .map(|node| {
+                let used = node.total.saturating_sub(node.free);
+                let percent = used as f64 / node.total.max(1) as f64 * 100.0;
+                let cells = vec![
+                    format!("node{}", node.id),
+                    format!("{} / {}", gib(used), gib(node.total)),
+                    gib(node.free),
+                    self.precision.percent(percent),
+                    node.cpus.len().to_string(),
+                    node.cpu_usage(&snapshot.cpu.cores).map_or_else(
+                        || "-".to_string(),
+                        |usage| self.precision.percent(usage.into()),
                     ),
-            )
-            .y_axis(
-                Axis::default()
-                    .title(Span::styled("Usage (%)", Style::default().fg(Color::Red)))
-                    .style(Style::default().fg(Color::White))
-                    .bounds([0.0, 100.0])
-                    .labels(
-                        ["0%", "50%", "100%"]
-                            .iter()
-                            .map(|s| Span::styled(*s, Style::default().fg(Color::White)))
-                            .collect(),
+                ];
+                (percent, cells)
+            })
+            .collect()
+    }
+
+    /// The Kernel Memory panel as lines of label and value pairs.
+    fn kernel_memory_lines(
+        &self,
+        kernel: &KernelMemorySnapshot,
+    ) -> [Vec<(&'static str, String)>; 3] {
+        let mib = |bytes: u64| self.precision.memory(bytes as f64 / 1_048_576.0, "MiB");
+        [
+            vec![(
+                "Slab",
+                format!(
+                    "{} ({} reclaimable, {} not)",
+                    mib(kernel.slab()),
+                    mib(kernel.slab_reclaimable),
+                    mib(kernel.slab_unreclaimable)
+                ),
+            )],
+            vec![
+                ("Page tables", mib(kernel.page_tables)),
+                ("Kernel stacks", mib(kernel.kernel_stack)),
+            ],
+            vec![
+                ("Dirty", mib(kernel.dirty)),
+                ("Writeback", mib(kernel.writeback)),
+            ],
+        ]
+    }
+
+    /// Key bindings over the current view, one action per line.
+    fn render_help<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let lines: Vec<_> = Action::ALL
+            .into_iter()
+            .map(|action| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:>9}  ", self.keys.hint(action)),
+                        Style::default().fg(Color::Yellow),
                     ),
-            );
-        f.render_widget(chart, chunks[1]);
+                    Span::raw(action.name().replace('_', " ")),
+                ])
+            })
+            .collect();
+        let width = 36.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let area = tui::layout::Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines).block(Block::default().title("Keys").borders(Borders::ALL)),
+            area,
+        );
+    }
+
+    /// Frame, lock and update timings in the top right corner. The lock
+    /// times cover the UI's side; the collection thread only holds the lock
+    /// to apply a sample, not while collecting it.
+    fn render_debug<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let update = self.state().update_duration;
+        let timings = &self.timings;
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let interval = timings.frame_interval.get();
+        let fps = if interval.is_zero() {
+            "-".to_string()
+        } else {
+            format!("{:.0}", 1.0 / interval.as_secs_f64())
+        };
+        let lines = vec![
+            Spans::from(format!(
+                "frame  {:>6.2} ms, {fps} fps",
+                ms(timings.frame.get())
+            )),
+            Spans::from(format!(
+                "lock   {:>6.2} ms held, {:.2} ms waited",
+                ms(timings.last_lock_held.get()),
+                ms(timings.last_lock_wait.get())
+            )),
+            Spans::from(format!("update {:>6.2} ms", ms(update))),
+        ];
+        let width = 42.min(area.width);
+        let height = 5.min(area.height);
+        let area = tui::layout::Rect {
+            x: area.right() - width,
+            y: area.y,
+            width,
+            height,
+        };
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines).block(Block::default().title("Debug").borders(Borders::ALL)),
+            area,
+        );
+    }
+
+    /// The switcher as a popup over the current view: the query, then the
+    /// matching items, scrolled to keep the selected one in sight.
+    fn render_switcher<B: Backend>(
+        &self,
+        f: &mut Frame<'_, B>,
+        area: tui::layout::Rect,
+        switcher: &Switcher,
+    ) {
+        let width = 60.min(area.width);
+        let height = 16.min(area.height);
+        let area = tui::layout::Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        let results = switcher.results();
+        let block = Block::default()
+            .title(format!(
+                "Go to ({} matches, {})",
+                results.len(),
+                if switcher.by_name {
+                    "by name"
+                } else {
+                    "best first"
+                }
+            ))
+            .borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+            .split(inner);
+        f.render_widget(Paragraph::new(format!("> {}_", switcher.query)), chunks[0]);
+        let items: Vec<_> = results
+            .iter()
+            .map(|entry| {
+                ListItem::new(Spans::from(vec![
+                    Span::styled(
+                        format!("{:<5}", entry.kind()),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::raw(match entry.target {
+                        Target::Process(pid) if self.privacy => {
+                            format!("{} ({pid})", redacted_name(pid))
+                        }
+                        _ => entry.label.clone(),
+                    }),
+                ]))
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select((!results.is_empty()).then(|| switcher.selected.min(results.len() - 1)));
+        f.render_stateful_widget(
+            List::new(items).highlight_style(focus_style()),
+            chunks[1],
+            &mut list_state,
+        );
+    }
+
+    /// The environment viewer as a popup over the current view: the query,
+    /// then the matching variables from the scroll position on, or why
+    /// there are none. Privacy mode hides every value.
+    fn render_environment<B: Backend>(
+        &self,
+        f: &mut Frame<'_, B>,
+        area: tui::layout::Rect,
+        view: &EnvironmentView,
+    ) {
+        let width = 80.min(area.width);
+        let height = 20.min(area.height);
+        let area = tui::layout::Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        let name = if self.privacy {
+            redacted_name(view.pid)
+        } else {
+            self.state()
+                .snapshot
+                .processes
+                .iter()
+                .find(|process| process.pid == view.pid)
+                .map_or_else(|| "?".to_string(), |process| process.name.clone())
+        };
+        let rows = view.rows();
+        let block = Block::default()
+            .title(format!(
+                "Environment of {name} ({}) ({}, secrets {})",
+                view.pid,
+                match &rows {
+                    Ok(rows) => format!("{} variables", rows.len()),
+                    Err(_) => "unreadable".to_string(),
+                },
+                if view.reveal { "shown" } else { "hidden" }
+            ))
+            .borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+            .split(inner);
+        f.render_widget(Paragraph::new(format!("> {}_", view.query)), chunks[0]);
+        let lines: Vec<_> = match rows {
+            Ok(rows) => rows
+                .into_iter()
+                .skip(view.scroll)
+                .map(|(key, value)| {
+                    Spans::from(vec![
+                        Span::styled(key.to_string(), Style::default().fg(Color::Yellow)),
+                        Span::raw(format!(
+                            "={}",
+                            if self.privacy { HIDDEN_VALUE } else { value }
+                        )),
+                    ])
+                })
+                .collect(),
+            Err(e) => vec![Spans::from(Span::styled(
+                format!("Can't read the environment: {e}"),
+                Style::default().fg(Color::Red),
+            ))],
+        };
+        f.render_widget(Paragraph::new(lines), chunks[1]);
+    }
+
+    /// The tab bar on narrow screens: the current tab and where it is among
+    /// the others, then the header.
+    fn compact_tab_bar(&self) -> Spans<'static> {
+        let mut spans = vec![
+            Span::styled(
+                self.current_view.title(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                " [{}/{}] - ",
+                self.current_view.index() + 1,
+                DashboardView::ALL.len()
+            )),
+        ];
+        spans.extend(self.header_title().0);
+        Spans::from(spans)
+    }
+
+    /// "System Monitor", or the replay position and recorded time when
+    /// playing back, which has to be impossible to mistake for live data.
+    fn header_title(&self) -> Spans<'static> {
+        let name = if self.privacy {
+            "System Monitor [private]"
+        } else {
+            "System Monitor"
+        };
+        let state = self.state();
+        let status = state
+            .replay_status()
+            .map(|status| (status, state.snapshot.timestamp));
+        drop(state);
+        let Some((status, timestamp)) = status else {
+            return Spans::from(name);
+        };
+
+        let playback = if status.paused {
+            "paused".to_string()
+        } else {
+            format!("x{}", status.speed)
+        };
+        Spans::from(vec![
+            Span::raw(format!("{name} - ")),
+            Span::styled(
+                format!("REPLAY {} UTC", format_timestamp(timestamp)),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                " [{}/{}, {playback}] ('{}' pause, '{}' '{}' step)",
+                status.position + 1,
+                status.len,
+                self.keys.hint(Action::Pause),
+                self.keys.hint(Action::ReplayBack),
+                self.keys.hint(Action::ReplayForward)
+            )),
+        ])
+    }
+
+    /// Locks the shared state, see `lock_state`, timing the wait and the
+    /// hold for the debug overlay.
+    fn state(&self) -> StateGuard<'_> {
+        let asked = Instant::now();
+        let guard = lock_state(&self.system_state);
+        let locked_at = Instant::now();
+        let wait = &self.timings.lock_wait;
+        wait.set(wait.get() + (locked_at - asked));
+        StateGuard {
+            guard,
+            locked_at,
+            timings: &self.timings,
+        }
+    }
+
+    fn control_replay(&mut self, command: ReplayCommand) {
+        if self.state().control_replay(command).is_err() {
+            self.retry_later(CollectorTask::Replay(command));
+        }
+    }
+
+    fn show_environment(&mut self, pid: u32) {
+        let environment = self.state().environment(pid);
+        match environment {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.retry_later(CollectorTask::Environment(pid));
+            }
+            environment => {
+                self.mode = InputMode::Environment(EnvironmentView::new(pid, environment));
+            }
+        }
+    }
+
+    /// Holds `task` back until the collector is free, saying so.
+    fn retry_later(&mut self, task: CollectorTask) {
+        self.retry = Some(task);
+        self.set_status("Collector busy, retrying".to_string());
+    }
+
+    fn retry_collector_task(&mut self) {
+        match self.retry.take() {
+            Some(CollectorTask::Replay(command)) => self.control_replay(command),
+            Some(CollectorTask::Kill(pids)) => self.kill_processes(&pids),
+            Some(CollectorTask::Renice(pids, step)) => self.renice_processes(&pids, step),
+            // Unless something else has taken the keyboard meanwhile.
+            Some(CollectorTask::Environment(pid)) if self.mode == InputMode::Normal => {
+                self.show_environment(pid);
+            }
+            Some(CollectorTask::Environment(_)) => {}
+            None => {}
+        }
+    }
+
+    fn handle_input(&mut self, key: impl Into<Key>) {
+        if let Some(action) = self.action_for(key) {
+            self.apply(action);
+        }
+    }
+
+    /// What `key` means in the current mode. The prompts take every key;
+    /// the chart cursor only takes the keys that move or close it, so the
+    /// rest still work while it's shown.
+    fn action_for(&self, key: impl Into<Key>) -> Option<Action> {
+        let key = key.into();
+        match self.mode {
+            InputMode::ConfirmKill(_) | InputMode::ConfirmRenice(..)
+                if key == KeyCode::Char('y').into() =>
+            {
+                Some(Action::Confirm)
+            }
+            InputMode::ConfirmKill(_) | InputMode::ConfirmRenice(..) | InputMode::Help => {
+                Some(Action::Cancel)
+            }
+            // The switcher lists its results below the query, so Up/Down
+            // pick one and Tab changes their order.
+            InputMode::Switcher(_) if matches!(key.code, KeyCode::Up | KeyCode::Down) => {
+                Some(if key.code == KeyCode::Up {
+                    Action::CursorUp
+                } else {
+                    Action::CursorDown
+                })
+            }
+            InputMode::Switcher(_) if key.code == KeyCode::Tab => Some(Action::Sort),
+            // The environment viewer scrolls the same way, and Tab reveals.
+            InputMode::Environment(_) if matches!(key.code, KeyCode::Up | KeyCode::Down) => {
+                Some(if key.code == KeyCode::Up {
+                    Action::CursorUp
+                } else {
+                    Action::CursorDown
+                })
+            }
+            InputMode::Environment(_) if key.code == KeyCode::Tab => Some(Action::RevealSecrets),
+            InputMode::Filter
+            | InputMode::Search
+            | InputMode::Command
+            | InputMode::Switcher(_)
+            | InputMode::Environment(_) => match key.code {
+                _ if key.ctrl => None,
+                KeyCode::Enter => Some(Action::Submit),
+                KeyCode::Esc => Some(Action::Cancel),
+                KeyCode::Backspace => Some(Action::DeleteChar),
+                KeyCode::Char(c) => Some(Action::InsertChar(c)),
+                _ => None,
+            },
+            InputMode::Normal if key.ctrl => self.keys.action(key),
+            InputMode::Normal => match key.code {
+                KeyCode::Left if self.chart_cursor.is_some() => Some(Action::CursorBack),
+                KeyCode::Right if self.chart_cursor.is_some() => Some(Action::CursorForward),
+                KeyCode::Esc if self.chart_cursor.is_some() => Some(Action::Cancel),
+                _ => self.keys.action(key),
+            },
+        }
+    }
+
+    /// Edits the text typed into the active prompt, if any, and moves the
+    /// search and the switcher back to their first result.
+    fn edit_prompt(&mut self, edit: impl FnOnce(&mut String)) {
+        let input = match &mut self.mode {
+            InputMode::Filter => &mut self.process_filter,
+            InputMode::Search => &mut self.search_query,
+            InputMode::Command => &mut self.command,
+            InputMode::Switcher(switcher) => {
+                switcher.selected = 0;
+                &mut switcher.query
+            }
+            InputMode::Environment(view) => {
+                view.scroll = 0;
+                &mut view.query
+            }
+            _ => return,
+        };
+        edit(input);
+        self.search_match = 0;
+    }
+
+    /// Performs `action` if it means something in the current view.
+    fn apply(&mut self, action: Action) {
+        let view = self.current_view;
+        match action {
+            Action::Confirm => match std::mem::replace(&mut self.mode, InputMode::Normal) {
+                InputMode::ConfirmKill(pids) => self.kill_processes(&pids),
+                InputMode::ConfirmRenice(pids, step) => self.renice_processes(&pids, step),
+                mode => self.mode = mode,
+            },
+            Action::Cancel => match std::mem::replace(&mut self.mode, InputMode::Normal) {
+                InputMode::Normal => self.chart_cursor = None,
+                InputMode::Filter => self.process_filter.clear(),
+                InputMode::Search => self.search_query.clear(),
+                InputMode::Command => self.command.clear(),
+                InputMode::ConfirmKill(_)
+                | InputMode::ConfirmRenice(..)
+                | InputMode::Help
+                | InputMode::Switcher(_)
+                | InputMode::Environment(_) => {}
+            },
+            Action::Submit => match std::mem::replace(&mut self.mode, InputMode::Normal) {
+                InputMode::Search => self.step_search_match(0),
+                InputMode::Command => {
+                    let command = std::mem::take(&mut self.command);
+                    self.run_command(&command);
+                }
+                InputMode::Switcher(switcher) => {
+                    if let Some(target) = switcher.selected_target() {
+                        self.jump_to(target);
+                    }
+                }
+                _ => {}
+            },
+            Action::InsertChar(c) => self.edit_prompt(|input| input.push(c)),
+            Action::DeleteChar => self.edit_prompt(|input| {
+                input.pop();
+            }),
+            Action::CursorUp | Action::CursorDown
+                if matches!(self.mode, InputMode::Switcher(_)) =>
+            {
+                if let InputMode::Switcher(switcher) = &mut self.mode {
+                    switcher.move_selection(action == Action::CursorDown);
+                }
+            }
+            Action::CursorUp | Action::CursorDown
+                if matches!(self.mode, InputMode::Environment(_)) =>
+            {
+                if let InputMode::Environment(view) = &mut self.mode {
+                    view.scroll_by(action == Action::CursorDown);
+                }
+            }
+            Action::RevealSecrets => {
+                if let InputMode::Environment(view) = &mut self.mode {
+                    view.reveal = !view.reveal;
+                }
+            }
+            Action::Sort if matches!(self.mode, InputMode::Switcher(_)) => {
+                if let InputMode::Switcher(switcher) = &mut self.mode {
+                    switcher.by_name = !switcher.by_name;
+                    switcher.selected = 0;
+                }
+            }
+            Action::CursorBack => {
+                if let Some(back) = self.chart_cursor {
+                    self.chart_cursor = Some((back + 1).min(HISTORY_LEN - 1));
+                }
+            }
+            Action::CursorForward => {
+                if let Some(back) = self.chart_cursor {
+                    self.chart_cursor = Some(back.saturating_sub(1));
+                }
+            }
+            Action::ChartCursor if self.chart_cursor.is_some() => self.chart_cursor = None,
+            Action::Quit => self.should_quit = true,
+            Action::Help => self.mode = InputMode::Help,
+            Action::Command => self.mode = InputMode::Command,
+            Action::Switcher => {
+                let state = self.state();
+                let switcher = Switcher::new(&state.snapshot, &state.interface_filter);
+                drop(state);
+                self.mode = InputMode::Switcher(switcher);
+            }
+            Action::Search if matches!(view, DashboardView::Processes) => {
+                self.search_query.clear();
+                self.search_match = 0;
+                self.mode = InputMode::Search;
+            }
+            Action::Filter if matches!(view, DashboardView::Processes) => {
+                self.process_filter.clear();
+                self.mode = InputMode::Filter;
+            }
+            Action::ChartCursor
+                if matches!(
+                    view,
+                    DashboardView::Cpu | DashboardView::Memory | DashboardView::Network
+                ) =>
+            {
+                self.chart_cursor = Some(0);
+            }
+            Action::CursorUp if matches!(view, DashboardView::Processes) => {
+                self.move_process_cursor(false);
+            }
+            Action::CursorDown if matches!(view, DashboardView::Processes) => {
+                self.move_process_cursor(true);
+            }
+            Action::CursorUp | Action::CursorDown if matches!(view, DashboardView::Disk) => {
+                self.move_device_selection(action == Action::CursorDown);
+            }
+            Action::CursorUp | Action::CursorDown if matches!(view, DashboardView::Cpu) => {
+                self.core_page = step(
+                    self.core_page,
+                    self.core_pages.get() - 1,
+                    action == Action::CursorDown,
+                    self.wrap_lists,
+                );
+            }
+            Action::Mark if matches!(view, DashboardView::Processes) => {
+                let pids = self.selected_pids();
+                if pids.iter().all(|pid| self.marked.contains(pid)) {
+                    for pid in &pids {
+                        self.marked.remove(pid);
+                    }
+                } else {
+                    self.marked.extend(pids);
+                }
+            }
+            Action::GroupByName if matches!(view, DashboardView::Processes) => {
+                self.group_processes = !self.group_processes;
+                self.selected_group = None;
+            }
+            Action::ExpandGroup if matches!(view, DashboardView::Processes) => {
+                self.toggle_group();
+            }
+            Action::ToggleStuck if matches!(view, DashboardView::Processes) => {
+                self.stuck_only = !self.stuck_only;
+            }
+            Action::ToggleSelf if matches!(view, DashboardView::Processes) => {
+                self.set_hide_self(!self.hide_self);
+            }
+            Action::Kill if matches!(view, DashboardView::Processes) => {
+                let pids: Vec<_> = if self.marked.is_empty() {
+                    self.selected_pids()
+                } else {
+                    self.marked.iter().copied().collect()
+                };
+                if !pids.is_empty() {
+                    self.mode = InputMode::ConfirmKill(pids);
+                }
+            }
+            Action::NiceUp | Action::NiceDown if matches!(view, DashboardView::Processes) => {
+                let pids: Vec<_> = if self.marked.is_empty() {
+                    self.selected_pids()
+                } else {
+                    self.marked.iter().copied().collect()
+                };
+                let step = if action == Action::NiceUp { 1 } else { -1 };
+                if !pids.is_empty() {
+                    self.mode = InputMode::ConfirmRenice(pids, step);
+                }
+            }
+            Action::Compare if matches!(view, DashboardView::Processes) => {
+                if let Some(pid) = self.selected_pid {
+                    self.toggle_compared(pid);
+                }
+            }
+            Action::Watch if matches!(view, DashboardView::Processes) => {
+                if let Some(pid) = self.selected_pid {
+                    self.watched = Some(pid);
+                    self.set_view(view);
+                }
+            }
+            Action::Environment if matches!(view, DashboardView::Processes) => {
+                if let Some(pid) = self.selected_pid {
+                    self.show_environment(pid);
+                }
+            }
+            Action::Unwatch if self.watched.is_some() => {
+                self.watched = None;
+                self.set_view(view);
+            }
+            Action::Compare if matches!(view, DashboardView::Compare) => {
+                self.compared.clear();
+                self.set_view(view);
+            }
+            Action::ToggleCoreOverlay if matches!(view, DashboardView::Cpu) => {
+                self.show_core_overlay = !self.show_core_overlay;
+            }
+            Action::ToggleIdleCores if matches!(view, DashboardView::Cpu) => {
+                self.hide_idle_cores = !self.hide_idle_cores;
+            }
+            Action::ToggleDiskDetails if matches!(view, DashboardView::Disk) => {
+                self.show_disk_details = !self.show_disk_details;
+            }
+            Action::TogglePseudoDisks if matches!(view, DashboardView::Disk) => {
+                self.show_pseudo_disks = !self.show_pseudo_disks;
+            }
+            Action::ToggleMinimized => self.minimized = !self.minimized,
+            Action::ToggleKernelMemory if matches!(view, DashboardView::Memory) => {
+                self.show_kernel_memory = !self.show_kernel_memory;
+            }
+            Action::ToggleDebug => self.show_debug = !self.show_debug,
+            Action::TogglePrivacy => self.privacy = !self.privacy,
+            Action::Ghost => {
+                if self.ghost.take().is_some() {
+                    self.set_status("Ghost cleared".to_string());
+                } else {
+                    let ghost = self.state().ghost();
+                    self.ghost = Some(ghost);
+                    self.set_status(format!(
+                        "Charts kept as a ghost behind the live lines; '{}' to clear",
+                        self.keys.hint(Action::Ghost)
+                    ));
+                }
+            }
+            Action::CopyView => self.copy_view(None),
+            Action::ToggleLinkDetails if matches!(view, DashboardView::Network) => {
+                self.show_link_details = !self.show_link_details;
+            }
+            Action::ResetTotals if matches!(view, DashboardView::Network) => {
+                self.state().reset_session_totals();
+            }
+            Action::ToggleBits => {
+                self.rates_in_bits = !self.rates_in_bits;
+                self.set_status(format!(
+                    "Network rates in {}",
+                    if self.rates_in_bits { "bits" } else { "bytes" }
+                ));
+            }
+            Action::ToggleFill => {
+                self.filled_charts = !self.filled_charts;
+                self.set_status(format!(
+                    "Charts drawn as {}",
+                    if self.filled_charts {
+                        "filled areas"
+                    } else {
+                        "lines"
+                    }
+                ));
+            }
+            Action::Sort if matches!(view, DashboardView::Disk) => {
+                self.disk_sort = self.disk_sort.next();
+            }
+            Action::Sort if matches!(view, DashboardView::Network) => {
+                self.network_sort = self.network_sort.next();
+            }
+            Action::Sort if matches!(view, DashboardView::Processes) => {
+                // Cycles through the shown columns; a hidden column given to
+                // --sort drops out of the cycle once left.
+                let next = self
+                    .process_columns
+                    .iter()
+                    .position(|&column| column == self.process_sort)
+                    .map_or(0, |i| (i + 1) % self.process_columns.len());
+                self.process_sort = self.process_columns[next];
+            }
+            Action::ReverseSort if matches!(view, DashboardView::Processes) => {
+                self.sort_descending = !self.sort_descending;
+            }
+            Action::ResetCharts => {
+                self.chart_cursor = None;
+                self.state().reset_history();
+            }
+            Action::ExportAlerts if matches!(view, DashboardView::Alerts) => {
+                self.export_alerts(&self.alert_log_path.clone());
+            }
+            Action::ClearAlerts if matches!(view, DashboardView::Alerts) => {
+                self.alert_log.clear();
+            }
+            Action::Pause => {
+                let mut state = lock_state(&self.system_state);
+                // A freeze on alert is lifted first, even during a replay.
+                if self.replaying && !state.paused {
+                    drop(state);
+                    self.control_replay(ReplayCommand::TogglePause);
+                } else {
+                    state.paused = !state.paused;
+                    self.frozen_on_alert = None;
+                }
+            }
+            Action::ReplayForward if self.replaying => self.control_replay(ReplayCommand::Step(1)),
+            Action::ReplayBack if self.replaying => self.control_replay(ReplayCommand::Step(-1)),
+            Action::Slower => self.scale_interval(2.0),
+            Action::Faster => self.scale_interval(0.5),
+            Action::NextMatch if !self.search_query.is_empty() => self.step_search_match(1),
+            Action::PrevMatch if !self.search_query.is_empty() => self.step_search_match(-1),
+            Action::ClearSearch => self.search_query.clear(),
+            Action::PrevTab | Action::NextTab => {
+                let index = step(
+                    view.index(),
+                    DashboardView::ALL.len() - 1,
+                    action == Action::NextTab,
+                    self.wrap_tabs,
+                );
+                self.set_view(DashboardView::ALL[index]);
+            }
+            _ => {}
+        }
+    }
+
+    /// Runs a command typed after ':'. Unknown commands and bad arguments
+    /// are reported in the status bar.
+    fn run_command(&mut self, command: &str) {
+        let (name, argument) = command
+            .trim()
+            .split_once(' ')
+            .map_or((command.trim(), ""), |(name, argument)| {
+                (name, argument.trim())
+            });
+        let result = match name {
+            "" => Ok(()),
+            "q" | "quit" => {
+                self.should_quit = true;
+                Ok(())
+            }
+            "tab" => argument.parse().map(|view| self.set_view(view)),
+            "sort" => argument.parse().map(|column| {
+                self.process_sort = column;
+                self.set_view(self.current_view);
+            }),
+            "filter" => {
+                self.process_filter = argument.to_string();
+                Ok(())
+            }
+            "net" => {
+                self.state().interface_filter = argument.to_string();
+                Ok(())
+            }
+            "export" => {
+                let path = if argument.is_empty() {
+                    self.alert_log_path.clone()
+                } else {
+                    PathBuf::from(argument)
+                };
+                self.export_alerts(&path);
+                Ok(())
+            }
+            "copy" => {
+                self.copy_view((!argument.is_empty()).then(|| Path::new(argument)));
+                Ok(())
+            }
+            _ => Err(format!("unknown command '{name}'")),
+        };
+        if let Err(e) = result {
+            self.set_status(format!(":{command}: {e}"));
+        }
+    }
+
+    /// Index into a series of `len` samples that the chart cursor points at.
+    fn cursor_index(&self, len: usize) -> Option<usize> {
+        let back = self.chart_cursor?;
+        let last = len.checked_sub(1)?;
+        Some(last - back.min(last))
+    }
+
+    /// Chart title with the cursor's time offset and readout appended.
+    fn cursor_title(&self, title: &str, index: usize, len: usize, readout: String) -> String {
+        let back = len.saturating_sub(1 + index);
+        let ago = self.interval().as_secs_f64() * back as f64;
+        format!("{title} | cursor {}: {readout}", format_ago(ago))
+    }
+
+    fn set_status(&mut self, message: String) {
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    fn status_message(&self) -> Option<&str> {
+        self.status_message
+            .as_ref()
+            .filter(|(_, at)| at.elapsed() < STATUS_MESSAGE_TIMEOUT)
+            .map(|(message, _)| message.as_str())
+    }
+
+    /// Gauge label in the configured style and precision.
+    fn gauge_text(&self, used: f64, total: f64, unit: &str) -> String {
+        gauge_label(self.gauge_label, self.gauge_precision, used, total, unit)
+    }
+
+    /// A series as its chart draws it: as is, or filled down to the axis
+    /// after `Action::ToggleFill`. Ghosts, the per-core overlay and the
+    /// cursor stay lines, so they show over a fill.
+    fn chart_points(&self, data: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+        if self.filled_charts {
+            fill_under(&data)
+        } else {
+            data
+        }
+    }
+
+    /// A network rate in the unit picked with `Action::ToggleBits`.
+    fn network_rate(&self, bytes_per_sec: u64) -> String {
+        if self.rates_in_bits {
+            format_bit_rate(bytes_per_sec, &self.precision)
+        } else {
+            format_rate(bytes_per_sec, &self.precision)
+        }
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval.load(Ordering::Relaxed))
+    }
+
+    /// How far behind the data on screen is. Pausing stops samples on
+    /// purpose, so paused data never counts as stale.
+    fn staleness(&self) -> Staleness {
+        let state = self.state();
+        if state.paused || state.replay_status().is_some_and(|status| status.paused) {
+            return Staleness::Fresh;
+        }
+        Staleness::of(state.last_sample_age(), self.interval())
+    }
+
+    /// Multiplies the sampling interval by `factor`, within the range the
+    /// collectors can keep up with. The charts' time axes follow, though
+    /// samples already in the history keep their old spacing.
+    fn scale_interval(&mut self, factor: f64) {
+        let interval = self
+            .interval()
+            .mul_f64(factor)
+            .clamp(MIN_INTERVAL, MAX_INTERVAL);
+        self.interval
+            .store(interval.as_millis() as u64, Ordering::Relaxed);
+        self.set_status(format!("Sampling every {} ms", interval.as_millis()));
+    }
+
+    fn set_view(&mut self, view: DashboardView) {
+        self.current_view = view;
+        self.chart_cursor = None;
+        self.focus = None;
+
+        // Compared and watched processes are sampled every tick wherever the
+        // user is, so their histories have no gaps.
+        let mut tracked = self.compared.clone();
+        tracked.extend(self.watched.filter(|pid| !self.compared.contains(pid)));
+        let demand = self.refresh_demand();
+        let mut state = self.state();
+        state.set_demand(demand);
+        state.track_processes(&tracked);
+    }
+
+    /// What the collector needs to refresh for the current view.
+    fn refresh_demand(&self) -> RefreshDemand {
+        let view = self.current_view;
+        let processes = matches!(view, DashboardView::Processes);
+        let swap_sorted = processes && self.process_sort == ProcessColumn::Swap;
+        RefreshDemand {
+            processes: matches!(view, DashboardView::Processes | DashboardView::Strip)
+                || !self.compared.is_empty()
+                || self.watched.is_some(),
+            disks: matches!(
+                view,
+                DashboardView::Overview | DashboardView::Disk | DashboardView::Strip
+            ),
+            open_files: (processes
+                && (self.process_columns.contains(&ProcessColumn::OpenFiles)
+                    || self.process_sort == ProcessColumn::OpenFiles))
+                || matches!(view, DashboardView::Compare),
+            threads: processes
+                && (self.process_columns.contains(&ProcessColumn::Threads)
+                    || self.process_sort == ProcessColumn::Threads),
+            swap_pids: if processes {
+                self.swap_rows.borrow().clone()
+            } else {
+                Vec::new()
+            },
+            swap_all: swap_sorted,
+        }
+    }
+
+    /// Asks for swap usage of the rows the last draw showed, once they've
+    /// changed; scrolling or a new sort brings other processes on screen.
+    fn update_swap_demand(&mut self) {
+        if *self.swap_rows.borrow() == self.demanded_swap_rows {
+            return;
+        }
+        self.demanded_swap_rows = self.swap_rows.borrow().clone();
+        let demand = self.refresh_demand();
+        self.state().set_demand(demand);
+    }
+
+    /// Adds `pid` to the comparison, replacing the older of two already
+    /// there, or takes it off if it's already compared.
+    fn toggle_compared(&mut self, pid: u32) {
+        if let Some(index) = self.compared.iter().position(|&compared| compared == pid) {
+            self.compared.remove(index);
+            self.set_status(format!("Stopped comparing PID {pid}"));
+        } else {
+            if self.compared.len() == 2 {
+                self.compared.remove(0);
+            }
+            self.compared.push(pid);
+            self.set_status(match self.compared.as_slice() {
+                [a, b] => format!("Comparing PIDs {a} and {b} on the Compare tab"),
+                _ => format!(
+                    "Comparing PID {pid}; pick another with '{}'",
+                    self.keys.hint(Action::Compare)
+                ),
+            });
+        }
+        self.set_view(self.current_view);
+    }
+
+    /// Switches to the tab showing `target` and puts it in focus, revealing
+    /// it first if the filter or the pseudo file system toggle hides it.
+    fn jump_to(&mut self, target: Target) {
+        match target {
+            Target::Process(pid) => {
+                self.set_view(DashboardView::Processes);
+                let state = lock_state(&self.system_state);
+                let hidden = state.snapshot.processes.iter().any(|process| {
+                    process.pid == pid && !name_matches(process, &self.process_filter)
+                });
+                let unstuck =
+                    state.snapshot.processes.iter().any(|process| {
+                        process.pid == pid && process.status != ProcessState::DiskWait
+                    });
+                let name = state
+                    .snapshot
+                    .processes
+                    .iter()
+                    .find(|process| process.pid == pid)
+                    .map(|process| process.name.clone());
+                drop(state);
+                if hidden {
+                    self.process_filter.clear();
+                }
+                self.stuck_only &= !unstuck;
+                if self.own_pid == Some(pid) {
+                    self.set_hide_self(false);
+                }
+                if let Some(name) = name {
+                    self.expanded_groups.insert(name);
+                }
+                self.selected_group = None;
+                self.selected_pid = Some(pid);
+            }
+            Target::Disk(mount_point) => {
+                self.set_view(DashboardView::Disk);
+                let state = lock_state(&self.system_state);
+                let pseudo = state
+                    .snapshot
+                    .disks
+                    .iter()
+                    .any(|disk| disk.mount_point == mount_point && disk.is_pseudo());
+                drop(state);
+                self.show_pseudo_disks |= pseudo;
+                self.focus = Some(mount_point);
+            }
+            Target::Interface(name) => {
+                self.set_view(DashboardView::Network);
+                self.focus = Some(name);
+            }
+        }
+    }
+
+    /// Moves `step` matches forward (wrapping) and puts the table cursor on
+    /// the resulting match.
+    fn step_search_match(&mut self, step: isize) {
+        let state = lock_state(&self.system_state);
+        let rows = self.visible_rows(&state.snapshot);
+        let matches = search_matches(&rows, &self.search_query);
+        if matches.is_empty() {
+            return;
+        }
+        let match_count = matches.len() as isize;
+        self.search_match = (self.search_match as isize + step).rem_euclid(match_count) as usize;
+        let (pid, group) = rows[matches[self.search_match]].key();
+        drop(state);
+        self.select_row(pid, group);
+    }
+
+    fn move_process_cursor(&mut self, down: bool) {
+        let state = lock_state(&self.system_state);
+        let rows = self.visible_rows(&state.snapshot);
+        let Some(last) = rows.len().checked_sub(1) else {
+            return;
+        };
+        let index = self
+            .selected_index(&rows)
+            .map_or(0, |index| step(index, last, down, self.wrap_lists));
+        let (pid, group) = rows[index].key();
+        drop(state);
+        self.select_row(pid, group);
+    }
+
+    /// Steps the disk I/O chart through "all devices" and then each device
+    /// in the order of the disk table.
+    fn move_device_selection(&mut self, forward: bool) {
+        let state = lock_state(&self.system_state);
+        let (disks, _) = self.sorted_disks(&state.snapshot);
+        let mut choices = vec![None];
+        for disk in disks {
+            let device = Some(disk.device.as_str());
+            if !disk.device.is_empty() && !choices.contains(&device) {
+                choices.push(device);
+            }
+        }
+        let index = choices
+            .iter()
+            .position(|&device| device == self.selected_device.as_deref())
+            .unwrap_or(0);
+        let index = step(index, choices.len() - 1, forward, self.wrap_lists);
+        let device = choices[index].map(str::to_string);
+        drop(state);
+        self.selected_device = device;
+    }
+
+    /// Disks in table order, and the pseudo file systems left out of it.
+    fn sorted_disks<'a>(
+        &self,
+        snapshot: &'a Snapshot,
+    ) -> (Vec<&'a DiskSnapshot>, Vec<&'a DiskSnapshot>) {
+        let (mut disks, pseudo): (Vec<_>, Vec<_>) = snapshot
+            .disks
+            .iter()
+            .partition(|disk| self.show_pseudo_disks || !disk.is_pseudo());
+        match self.disk_sort {
+            DiskSort::Usage => disks.sort_by(|a, b| {
+                let usage = |disk: &DiskSnapshot| disk.used() as f64 / disk.total.max(1) as f64;
+                usage(b).total_cmp(&usage(a))
+            }),
+            DiskSort::Available => disks.sort_by_key(|disk| disk.available),
+            DiskSort::Mount => disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point)),
+        }
+        (disks, pseudo)
+    }
+
+    fn select_row(&mut self, pid: u32, group: Option<String>) {
+        self.selected_pid = group.is_none().then_some(pid);
+        self.selected_group = group;
+    }
+
+    /// Index of the row under the cursor.
+    fn selected_index(&self, rows: &[ProcessRow<'_>]) -> Option<usize> {
+        rows.iter()
+            .position(|row| match (&row.members, &self.selected_group) {
+                (Some(_), Some(group)) => row.process.name == *group,
+                (None, None) => Some(row.process.pid) == self.selected_pid,
+                _ => false,
+            })
+    }
+
+    /// PIDs the cursor stands for: the selected process, or every member of
+    /// the selected group.
+    fn selected_pids(&self) -> Vec<u32> {
+        match &self.selected_group {
+            Some(group) => {
+                let state = lock_state(&self.system_state);
+                let mut pids: Vec<_> = self
+                    .filtered_processes(&state.snapshot)
+                    .into_iter()
+                    .filter(|process| process.name == *group)
+                    .map(|process| process.pid)
+                    .collect();
+                pids.sort_unstable();
+                pids
+            }
+            None => self.selected_pid.into_iter().collect(),
+        }
+    }
+
+    /// Expands the group under the cursor, or collapses the group the cursor
+    /// is in and moves the cursor to its summary row.
+    fn toggle_group(&mut self) {
+        if !self.group_processes {
+            return;
+        }
+        if let Some(group) = &self.selected_group {
+            if !self.expanded_groups.remove(group) {
+                self.expanded_groups.insert(group.clone());
+            }
+            return;
+        }
+        let state = lock_state(&self.system_state);
+        let name = self.selected_pid.and_then(|pid| {
+            state
+                .snapshot
+                .processes
+                .iter()
+                .find(|process| process.pid == pid)
+                .map(|process| process.name.clone())
+        });
+        drop(state);
+        if let Some(name) = name
+            && self.expanded_groups.remove(&name)
+        {
+            self.selected_pid = None;
+            self.selected_group = Some(name);
+        }
+    }
+
+    /// The processes the table lists: sorted, with the filter applied.
+    fn filtered_processes<'a>(&self, snapshot: &'a Snapshot) -> Vec<&'a ProcessSnapshot> {
+        let mut processes = sorted_processes(snapshot, self.process_sort, self.sort_descending);
+        if !self.process_filter.is_empty() {
+            processes.retain(|process| name_matches(process, &self.process_filter));
+        }
+        if self.stuck_only {
+            processes.retain(|process| process.status == ProcessState::DiskWait);
+        }
+        processes.retain(|process| !self.is_hidden_self(process));
+        processes
+    }
+
+    /// Hides or shows dashoxide's own process, in the session stats too.
+    fn set_hide_self(&mut self, hide: bool) {
+        self.hide_self = hide;
+        self.state().hide_self = hide;
+    }
+
+    fn is_hidden_self(&self, process: &ProcessSnapshot) -> bool {
+        self.hide_self && self.own_pid == Some(process.pid)
+    }
+
+    /// Processes the Overview picks its top consumers from.
+    fn overview_processes<'a>(
+        &self,
+        snapshot: &'a Snapshot,
+    ) -> impl Iterator<Item = &'a ProcessSnapshot> {
+        snapshot
+            .processes
+            .iter()
+            .filter(|process| !self.is_hidden_self(process))
+    }
+
+    /// The `overview_top` items with the largest `size`, biggest first, each
+    /// described by `describe`.
+    fn top_consumers<T>(
+        &self,
+        items: impl Iterator<Item = T>,
+        size: impl Fn(&T) -> f64,
+        describe: impl Fn(&T) -> String,
+    ) -> Vec<String> {
+        let mut items: Vec<_> = items.collect();
+        items.sort_by(|a, b| size(b).total_cmp(&size(a)));
+        items.iter().take(self.overview_top).map(describe).collect()
+    }
+
+    /// Rows of the process table, cut off at `max_processes`.
+    fn visible_rows<'a>(&self, snapshot: &'a Snapshot) -> Vec<ProcessRow<'a>> {
+        let mut rows = self.listed_rows(snapshot);
+        if let Some(max) = self.max_processes {
+            rows.truncate(max);
+        }
+        rows
+    }
+
+    /// Every row the process table lists. While grouping, processes sharing
+    /// a name are summed into one row, sorted among the others by the
+    /// summed values, and followed by their members if expanded.
+    fn listed_rows<'a>(&self, snapshot: &'a Snapshot) -> Vec<ProcessRow<'a>> {
+        let processes = self.filtered_processes(snapshot);
+        if !self.group_processes {
+            return processes
+                .into_iter()
+                .map(|process| ProcessRow {
+                    process: Cow::Borrowed(process),
+                    members: None,
+                    member: false,
+                })
+                .collect();
+        }
+
+        let mut groups: BTreeMap<&str, Vec<&ProcessSnapshot>> = BTreeMap::new();
+        for process in processes {
+            groups.entry(&process.name).or_default().push(process);
+        }
+        let mut heads: Vec<(Cow<'a, ProcessSnapshot>, Vec<&'a ProcessSnapshot>)> = groups
+            .into_values()
+            .map(|members| {
+                let head = match members.as_slice() {
+                    [single] => Cow::Borrowed(*single),
+                    _ => Cow::Owned(group_summary(&members)),
+                };
+                (head, members)
+            })
+            .collect();
+        heads.sort_by(|(a, _), (b, _)| {
+            let order = self.process_sort.compare(a, b).then(a.pid.cmp(&b.pid));
+            if self.sort_descending {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+
+        let mut rows = Vec::new();
+        for (head, members) in heads {
+            if members.len() == 1 {
+                rows.push(ProcessRow {
+                    process: head,
+                    members: None,
+                    member: false,
+                });
+                continue;
+            }
+            let expanded = self.expanded_groups.contains(&head.name);
+            rows.push(ProcessRow {
+                process: head,
+                members: Some(members.len()),
+                member: false,
+            });
+            if expanded {
+                rows.extend(members.into_iter().map(|process| ProcessRow {
+                    process: Cow::Borrowed(process),
+                    members: None,
+                    member: true,
+                }));
+            }
+        }
+        rows
+    }
+
+    /// Kills every PID and leaves a per-PID summary of any failures in the
+    /// status bar.
+    fn kill_processes(&mut self, pids: &[u32]) {
+        let result = self.state().kill(pids);
+        let Ok(failures) = result else {
+            self.retry_later(CollectorTask::Kill(pids.to_vec()));
+            return;
+        };
+        let failures: Vec<_> = failures
+            .into_iter()
+            .map(|(pid, e)| format!("{pid}: {e}"))
+            .collect();
+        self.marked.clear();
+
+        let killed = pids.len() - failures.len();
+        self.set_status(if failures.is_empty() {
+            format!("Killed {killed} process(es)")
+        } else if let [failure] = failures.as_slice()
+            && pids.len() == 1
+        {
+            format!("Kill failed: {failure}")
+        } else {
+            format!(
+                "Killed {killed} of {}; failed {}",
+                pids.len(),
+                failures.join(", ")
+            )
+        });
+    }
+
+    /// `name` as shown on screen: as is, or a stand-in in privacy mode. The
+    /// data itself keeps the real name, so filters and search still work.
+    fn process_name(&self, name: &str, pid: u32) -> String {
+        if self.privacy {
+            redacted_name(pid)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Moves the nice value of every PID by `step` from where it is now,
+    /// within the valid range, and reports failures like `kill_processes`.
+    fn renice_processes(&mut self, pids: &[u32], step: i32) {
+        let mut state = self.state();
+        let mut changes = Vec::new();
+        let mut failures = Vec::new();
+        for &pid in pids {
+            let current = state
+                .snapshot
+                .processes
+                .iter()
+                .find(|process| process.pid == pid)
+                .and_then(|process| process.nice);
+            match current {
+                Some(nice) => {
+                    let nice = (nice + step).clamp(*NICE_RANGE.start(), *NICE_RANGE.end());
+                    changes.push((pid, nice));
+                }
+                None => failures.push(format!("{pid}: nice value unknown")),
+            }
+        }
+        let result = state.renice(&changes);
+        drop(state);
+        let Ok(refused) = result else {
+            self.retry_later(CollectorTask::Renice(pids.to_vec(), step));
+            return;
+        };
+        failures.extend(refused.into_iter().map(|(pid, e)| format!("{pid}: {e}")));
+
+        let reniced = pids.len() - failures.len();
+        self.set_status(if failures.is_empty() {
+            format!("Reniced {reniced} process(es)")
+        } else if let [failure] = failures.as_slice()
+            && pids.len() == 1
+        {
+            format!("Renice failed: {failure}")
+        } else {
+            format!(
+                "Reniced {reniced} of {}; failed {}",
+                pids.len(),
+                failures.join(", ")
+            )
+        });
+    }
+
+    fn render_cpu<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let state = self.state();
+
+        let cpu_availability = state.cpu_availability();
+        if cpu_availability == DataAvailability::Unavailable {
+            Self::render_unavailable(f, area, "CPU Details", cpu_availability);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+            .split(area);
+        let cpu_usage = state.snapshot.cpu.usage;
+        let cpu_gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .title("Current CPU Usage")
+                    .borders(Borders::ALL),
+            )
+            .gauge_style(Style::default().fg(Color::Cyan))
+            .percent(self.gauge_value("cpu", f64::from(cpu_usage)) as u16)
+            .label(self.cpu_usage_text(&state.snapshot));
+
+        f.render_widget(cpu_gauge, chunks[0]);
+
+        let detail_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)].as_ref())
+            .split(chunks[1]);
+        let history_area = detail_chunks[0];
+        let page_cores = self.render_cpu_cores(f, detail_chunks[1], &state);
+
+        if cpu_availability == DataAvailability::Collecting {
+            Self::render_unavailable(f, history_area, "CPU History", cpu_availability);
+            return;
+        }
+
+        let cpu_history = &state.cpu_history;
+
+        let mut chart_data: Vec<(f64, f64)> = Vec::new();
+        for (i, &usage) in cpu_history.iter().enumerate() {
+            chart_data.push((i as f64, usage as f64));
+        }
+        let chart_data = self.chart_points(downsample(&chart_data, chart_resolution(history_area)));
+
+        // Degrees share the percent axis: 0-100°C covers every CPU short of
+        // a failing one, and throttling shows up as the two lines parting.
+        let temperature_data: Vec<(f64, f64)> = state
+            .cpu_temperature_history
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &temperature)| Some((i as f64, f64::from(temperature?).min(100.0))))
+            .collect();
+        let temperature_data = downsample(&temperature_data, chart_resolution(history_area));
+
+        // Datasets are drawn in order, so the cores go first and the average
+        // stays on top. Only the cores on the shown per-core page are
+        // overlaid; a hundred lines would just paint the chart over.
+        let overlay = self.show_core_overlay && self.rich_colors;
+        let core_data: Vec<(usize, Vec<(f64, f64)>)> = if overlay {
+            page_cores
+                .iter()
+                .filter_map(|&core| Some((core, state.core_history.get(core)?)))
+                .map(|(core, history)| {
+                    let data: Vec<_> = history
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &usage)| (i as f64, usage as f64))
+                        .collect();
+                    (core, downsample(&data, chart_resolution(history_area)))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let ghost = self.ghost.as_ref().map(|ghost| {
+            ghost_points(
+                ghost.cpu_history.iter().map(|&usage| f64::from(usage)),
+                history_area,
+            )
+        });
+        let mut datasets: Vec<_> = ghost
+            .iter()
+            .map(|data| ghost_dataset("Before", data))
+            .collect();
+        datasets.extend(core_data.iter().map(|(core, data)| {
+            Dataset::default()
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(CORE_COLORS[core % CORE_COLORS.len()]))
+                .data(data)
+        }));
+        datasets.push(
+            Dataset::default()
+                .name("CPU Usage")
+                .graph_type(GraphType::Line)
+                .style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .data(&chart_data),
+        );
+        if !temperature_data.is_empty() {
+            datasets.push(
+                Dataset::default()
+                    .name(format!("Temperature ({})", self.glyphs.celsius))
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(Color::LightRed))
+                    .data(&temperature_data),
+            );
+        }
+
+        let overlay_key = self.keys.hint(Action::ToggleCoreOverlay);
+        let mut title = if !self.rich_colors {
+            "CPU History".to_string()
+        } else if overlay {
+            format!("CPU History (cores overlaid, '{overlay_key}' to hide)")
+        } else {
+            format!("CPU History ('{overlay_key}' to overlay cores)")
+        };
+        let cursor = self
+            .cursor_index(cpu_history.len())
+            .map(|index| (index, cursor_line(index as f64, 100.0)));
+        if let Some((index, line)) = &cursor {
+            let mut readout = self.precision.percent(cpu_history[*index].into());
+            if let Some(Some(temperature)) = state.cpu_temperature_history.get(*index) {
+                readout.push_str(&format!(", {temperature:.0}{}", self.glyphs.celsius));
+            }
+            title = self.cursor_title(&title, *index, cpu_history.len(), readout);
+            datasets.push(cursor_dataset(line));
+        }
+        let y_title = if temperature_data.is_empty() {
+            "Usage (%)".to_string()
+        } else {
+            format!("Usage (%) / {}", self.glyphs.celsius)
+        };
+
+        let chart = Chart::new(datasets)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .x_axis(
+                Axis::default()
+                    .title(Span::styled("Time", Style::default().fg(Color::Red)))
+                    .style(Style::default().fg(Color::White))
+                    .bounds([0.0, cpu_history.len() as f64])
+                    .labels(
+                        time_axis_labels(
+                            cpu_history.len(),
+                            self.interval(),
+                            axis_ticks(history_area),
+                        )
+                        .into_iter()
+                        .map(|s| Span::styled(s, Style::default().fg(Color::White)))
+                        .collect(),
+                    ),
+            )
+            .y_axis(
+                Axis::default()
+                    .title(Span::styled(y_title, Style::default().fg(Color::Red)))
+                    .style(Style::default().fg(Color::White))
+                    .bounds([0.0, 100.0])
+                    .labels(
+                        ["0%", "50%", "100%"]
+                            .iter()
+                            .map(|s| Span::styled(*s, Style::default().fg(Color::White)))
+                            .collect(),
+                    ),
+            );
+        f.render_widget(chart, history_area);
+
+        let mut title = vec![Span::raw("CPU Details")];
+        if let Some(rates) = state.kernel_rates {
+            title.push(Span::raw(" - "));
+            title.extend(self.kernel_rate_spans(rates));
+        }
+        let cpu_block = Block::default()
+            .title(Spans::from(title))
+            .borders(Borders::ALL);
+        f.render_widget(cpu_block, area);
+    }
+
+    /// Context switch and interrupt rates, each with its label and the rate
+    /// that rings the bell.
+    fn kernel_rate_readings(&self, rates: (u64, u64)) -> [(&'static str, u64, Option<u64>); 2] {
+        [
+            ("Context switches", rates.0, self.kernel_rate_alerts[0]),
+            ("Interrupts", rates.1, self.kernel_rate_alerts[1]),
+        ]
+    }
+
+    /// The kernel rates as a line, those at their alert level in red.
+    fn kernel_rate_spans(&self, rates: (u64, u64)) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        for (label, rate, alert) in self.kernel_rate_readings(rates) {
+            if !spans.is_empty() {
+                spans.push(Span::raw(", "));
+            }
+            let text = format!("{label}: {}/s", abbreviate_count(rate));
+            spans.push(if alert.is_some_and(|alert| rate >= alert) {
+                Span::styled(text, Style::default().fg(Color::Red))
+            } else {
+                Span::raw(text)
+            });
+        }
+        spans
+    }
+
+    /// Label of the CPU gauge: usage in cores, then whatever temperature,
+    /// fan and thermal pressure readings there are.
+    fn cpu_usage_text(&self, snapshot: &Snapshot) -> String {
+        let cores = snapshot.cpu.cores.len() as f64;
+        let mut text = format!(
+            "CPU Usage: {}",
+            self.gauge_text(
+                f64::from(snapshot.cpu.usage) / 100.0 * cores,
+                cores,
+                "cores"
+            )
+        );
+        if let Some(temperature) = snapshot.cpu_temperature() {
+            text.push_str(&format!(" | {temperature:.0}{}", self.glyphs.celsius));
+        }
+        if let Some(temperature) = snapshot.gpu_temperature() {
+            text.push_str(&format!(" | GPU {temperature:.0}{}", self.glyphs.celsius));
+        }
+        if !snapshot.fans.is_empty() {
+            let speeds: Vec<_> = snapshot
+                .fans
+                .iter()
+                .map(|fan| format!("{:.0}", fan.rpm))
+                .collect();
+            text.push_str(&format!(" | fans {} rpm", speeds.join("/")));
+        }
+        if let Some(pressure) = snapshot.thermal_pressure {
+            text.push_str(&format!(" | thermal pressure {}", pressure.label()));
+        }
+        text
+    }
+
+    /// Draws one gauge per core, a page at a time when they don't all fit,
+    /// and returns the indices of the cores on the shown page.
+    fn render_cpu_cores<B: Backend>(
+        &self,
+        f: &mut Frame<'_, B>,
+        area: tui::layout::Rect,
+        state: &SystemState,
+    ) -> Vec<usize> {
+        let (shown, idle) = self.shown_cores(&state.snapshot.cpu.cores);
+
+        let idle_key = self.keys.hint(Action::ToggleIdleCores);
+        let title = if self.hide_idle_cores {
+            format!("Per-Core Usage (idle hidden, '{idle_key}' to show)")
+        } else {
+            format!("Per-Core Usage ('{idle_key}' to hide idle)")
+        };
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        // Rows left for gauges; when they run out, one more row goes to the
+        // page indicator.
+        let mut per_page = usize::from(inner.height).saturating_sub(usize::from(idle > 0));
+        if shown.len() > per_page {
+            per_page = per_page.saturating_sub(1);
+        }
+        let per_page = per_page.max(1);
+        let pages = shown.len().div_ceil(per_page).max(1);
+        self.core_pages.set(pages);
+        let page = self.core_page.min(pages - 1);
+        let shown =
+            &shown[(page * per_page).min(shown.len())..((page + 1) * per_page).min(shown.len())];
+
+        let mut constraints = vec![Constraint::Length(1); shown.len()];
+        if idle > 0 {
+            constraints.push(Constraint::Length(1));
+        }
+        if pages > 1 {
+            constraints.push(Constraint::Length(1));
+        }
+        constraints.push(Constraint::Min(0));
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(inner);
+
+        for (row, (index, usage)) in rows.iter().zip(shown) {
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(Color::Cyan))
+                .percent(
+                    self.gauge_value(&format!("cpu{index}"), f64::from(*usage))
+                        .clamp(0.0, 100.0) as u16,
+                )
+                .label(self.core_label(*index, *usage));
+            f.render_widget(gauge, *row);
+        }
+
+        let mut next_row = shown.len();
+        if idle > 0 {
+            let summary = Paragraph::new(self.idle_cores_text(idle))
+                .style(Style::default().fg(Color::DarkGray));
+            f.render_widget(summary, rows[next_row]);
+            next_row += 1;
+        }
+        if pages > 1 {
+            let indicator = Paragraph::new(format!(
+                "page {}/{pages}, {}/{} to scroll",
+                page + 1,
+                self.keys.hint(Action::CursorUp),
+                self.keys.hint(Action::CursorDown)
+            ))
+            .style(Style::default().fg(Color::Yellow));
+            f.render_widget(indicator, rows[next_row]);
+        }
+        shown.iter().map(|&(index, _)| index).collect()
+    }
+
+    fn render_memory<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let state = self.state();
+
+        let cgroup = state
+            .snapshot
+            .cgroup
+            .as_ref()
+            .and_then(|c| Some((c.version, c.memory()?)));
+        // A single node is the whole machine again, so the panel only
+        // shows up on NUMA systems.
+        let numa_nodes = &state.snapshot.numa_nodes;
+        let show_numa = numa_nodes.len() > 1;
+        let mut constraints = vec![
+            Constraint::Length(3), // RAM Gauge
+            Constraint::Length(3), // Swap Gauge
+            Constraint::Min(5),    // Memory History Chart
+        ];
+        if cgroup.is_some() {
+            constraints.insert(0, Constraint::Length(3)); // Container Gauge
+        }
+        if show_numa {
+            constraints.push(Constraint::Length(numa_nodes.len() as u16 + 3)); // NUMA Table
+        }
+        if self.show_kernel_memory {
+            constraints.push(Constraint::Length(5)); // Kernel Memory
+        }
+        let mut chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(area);
+
+        // --- Container ---
+        if let Some((version, (used, limit))) = cgroup {
+            let percent = used as f64 / limit as f64 * 100.0;
+            let container_gauge = Gauge::default()
+                .block(
+                    Block::default()
+                        .title(format!("Container Memory (cgroup v{version} limit)"))
+                        .borders(Borders::ALL),
+                )
+                .gauge_style(Style::default().fg(if percent >= 90.0 {
+                    Color::Red
+                } else {
+                    Color::Magenta
+                }))
+                .percent(
+                    self.gauge_value("container", percent)
+                        .clamp(0.0, 100.0)
+                        .round() as u16,
+                )
+                .label(self.gauge_text(
+                    used as f64 / (1_024 * 1_024 * 1_024) as f64,
+                    limit as f64 / (1_024 * 1_024 * 1_024) as f64,
+                    "GiB",
+                ));
+            f.render_widget(container_gauge, chunks.remove(0));
+        }
+
+        // --- RAM ---
+        let memory_availability = state.memory_availability();
+        let mem_total = state.snapshot.memory.total;
+        let mem_used = state.snapshot.memory.used;
+        let mem_percent = if mem_total > 0 {
+            mem_used as f64 / mem_total as f64 * 100.0
+        } else {
+            0.0
+        };
+        let mem_unit = 1_024 * 1_024 * 1_024; // GiB
+
+        let risk = oom_risk(&state.snapshot.memory, &state.memory_history);
+        let ram_title = Spans::from(vec![
+            Span::raw("RAM Usage - OOM risk: "),
+            Span::styled(risk.label(), Style::default().fg(risk_color(risk))),
+        ]);
+        let ram_gauge = Gauge::default()
+            .block(Block::default().title(ram_title).borders(Borders::ALL))
+            .gauge_style(Style::default().fg(Color::Magenta))
+            .percent(self.gauge_value("memory", mem_percent).round() as u16)
+            .label(self.gauge_text(
+                mem_used as f64 / mem_unit as f64,
+                mem_total as f64 / mem_unit as f64,
+                "GiB",
+            ));
+        if memory_availability == DataAvailability::Unavailable {
+            Self::render_unavailable(f, chunks[0], "RAM Usage", memory_availability);
+        } else {
+            f.render_widget(ram_gauge, chunks[0]);
+        }
+
+        // --- Swap ---
+        let swap_total = state.snapshot.memory.swap_total;
+        let swap_used = state.snapshot.memory.swap_used;
+        let swap_percent = if swap_total > 0 {
+            swap_used as f64 / swap_total as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let swap_unit = 1_024 * 1_024 * 1_024; // GiB
+
+        let swap_title = format!("{} Usage", state.snapshot.memory.swap_label());
+        let swap_gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .title(swap_title.as_str())
+                    .borders(Borders::ALL),
+            )
+            .gauge_style(Style::default().fg(Color::Yellow))
+            .percent(self.gauge_value("swap", swap_percent).round() as u16)
+            .label(self.gauge_text(
+                swap_used as f64 / swap_unit as f64,
+                swap_total as f64 / swap_unit as f64,
+                "GiB",
+            ));
+        // Only render swap if it exists
+        if state.swap_availability() == DataAvailability::Available {
+            f.render_widget(swap_gauge, chunks[1]);
+        } else {
+            let no_swap = Paragraph::new("No swap configured or not reported on this platform")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(Block::default().title(swap_title).borders(Borders::ALL))
+                .alignment(Alignment::Center);
+            f.render_widget(no_swap, chunks[1]);
+        }
+
+        // --- NUMA ---
+        if show_numa {
+            let header = Row::new(NUMA_HEADERS).style(Style::default().fg(Color::Yellow));
+            let rows = self
+                .numa_rows(&state.snapshot)
+                .into_iter()
+                .map(|(percent, cells)| {
+                    Row::new(cells).style(Style::default().fg(if percent >= 90.0 {
+                        Color::Red
+                    } else {
+                        Color::White
+                    }))
+                });
+            let table = Table::new(rows)
+                .header(header)
+                .block(
+                    Block::default()
+                        .title(format!("NUMA Nodes ({})", numa_nodes.len()))
+                        .borders(Borders::ALL),
+                )
+                .widths(&[
+                    Constraint::Length(8),
+                    Constraint::Length(22),
+                    Constraint::Length(11),
+                    Constraint::Length(8),
+                    Constraint::Length(5),
+                    Constraint::Length(7),
+                ]);
+            f.render_widget(table, chunks[3]);
+        }
+
+        // --- Kernel ---
+        if self.show_kernel_memory {
+            let area = chunks[chunks.len() - 1];
+            let title = format!(
+                "Kernel Memory ('{}' to hide)",
+                self.keys.hint(Action::ToggleKernelMemory)
+            );
+            let block = Block::default().title(title).borders(Borders::ALL);
+            match &state.snapshot.kernel_memory {
+                Some(kernel) => {
+                    let lines: Vec<_> = self
+                        .kernel_memory_lines(kernel)
+                        .into_iter()
+                        .map(|line| {
+                            let mut spans = Vec::new();
+                            for (i, (label, value)) in line.into_iter().enumerate() {
+                                if i > 0 {
+                                    spans.push(Span::raw("   "));
+                                }
+                                spans.push(Span::styled(
+                                    format!("{label}: "),
+                                    Style::default().fg(Color::Yellow),
+                                ));
+                                spans.push(Span::raw(value));
+                            }
+                            Spans::from(spans)
+                        })
+                        .collect();
+                    f.render_widget(Paragraph::new(lines).block(block), area);
+                }
+                None => {
+                    let message = Paragraph::new("Only reported on Linux")
+                        .style(Style::default().fg(Color::DarkGray))
+                        .block(block)
+                        .alignment(Alignment::Center);
+                    f.render_widget(message, area);
+                }
+            }
+        }
+
+        // --- History ---
+        if memory_availability != DataAvailability::Available {
+            return;
+        }
+        let memory_history = &state.memory_history;
+        if memory_history.is_empty() {
+            Self::render_unavailable(f, chunks[2], "Memory History", DataAvailability::Collecting);
+            return;
+        }
+        let percents: Vec<f64> = memory_history
+            .iter()
+            .map(|&(used, total)| {
+                if total > 0 {
+                    used as f64 / total as f64 * 100.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let chart_data: Vec<(f64, f64)> = percents
+            .iter()
+            .enumerate()
+            .map(|(i, &percent)| (i as f64, percent))
+            .collect();
+        let chart_data = self.chart_points(downsample(&chart_data, chart_resolution(chunks[2])));
+        let ghost = self.ghost.as_ref().map(|ghost| {
+            ghost_points(
+                ghost
+                    .memory_history
+                    .iter()
+                    .map(|&(used, total)| used as f64 / total.max(1) as f64 * 100.0),
+                chunks[2],
+            )
+        });
+
+        let mut datasets: Vec<_> = ghost
+            .iter()
+            .map(|data| ghost_dataset("Before", data))
+            .collect();
+        datasets.push(
+            Dataset::default()
+                .name("RAM Usage")
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Magenta))
+                .data(&chart_data),
+        );
+
+        let mut title = "Memory History".to_string();
+        let cursor = self
+            .cursor_index(percents.len())
+            .map(|index| (index, cursor_line(index as f64, 100.0)));
+        if let Some((index, line)) = &cursor {
+            let (used, _) = memory_history[*index];
+            title = self.cursor_title(
+                &title,
+                *index,
+                percents.len(),
+                format!(
+                    "{} ({})",
+                    self.precision.percent(percents[*index]),
+                    self.precision.memory(used as f64 / mem_unit as f64, "GiB")
+                ),
+            );
+            datasets.push(cursor_dataset(line));
+        }
+
+        let chart = Chart::new(datasets)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, percents.len() as f64])
+                    .labels(
+                        time_axis_labels(percents.len(), self.interval(), axis_ticks(chunks[2]))
+                            .into_iter()
+                            .map(|s| Span::styled(s, Style::default().fg(Color::Gray)))
+                            .collect(),
+                    ),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("Used (%)")
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, 100.0])
+                    .labels(vec![Span::raw("0%"), Span::raw("50%"), Span::raw("100%")]),
+            );
+        f.render_widget(chart, chunks[2]);
+    }
+
+    fn render_disk<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let state = self.state();
+
+        let disk_availability = state.disk_availability();
+        if disk_availability.is_missing() {
+            Self::render_unavailable(f, area, "Disk Details", disk_availability);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints(
+                [
+                    Constraint::Length(3),
+                    Constraint::Percentage(40),
+                    Constraint::Min(5),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        let (used_space, total_space) = state.snapshot.disk_space(self.disk_total_all);
+
+        let disk_usage_percent = if total_space > 0 {
+            self.gauge_value("disk", used_space as f64 / total_space as f64 * 100.0) as u16
+        } else {
+            0
+        };
+
+        let disk_guage = Gauge::default()
+            .block(
+                Block::default()
+                    .title("Total Disk Usage")
+                    .borders(Borders::ALL),
+            )
+            .gauge_style(Style::default().fg(Color::Blue))
+            .percent(disk_usage_percent)
+            .label(self.gauge_text(
+                used_space as f64 / 1_000_000_000.0,
+                total_space as f64 / 1_000_000_000.0,
+                "GB",
+            ));
+        f.render_widget(disk_guage, chunks[0]);
+
+        let (headers, mut texts) = self.disk_table(&state.snapshot);
+        let header_cells = headers.iter().map(|h| Cell::from(*h));
+        let header = Row::new(header_cells).style(Style::default().fg(Color::Yellow));
+        let (disks, pseudo) = self.sorted_disks(&state.snapshot);
+        let mut widths = fitted_widths(&headers, &texts);
+        // A mount point cut off at `MAX_COLUMN_WIDTH` can be ambiguous, so
+        // that column takes all it needs where the terminal has the room.
+        let longest_mount = texts.iter().map(|text| text[0].chars().count()).max();
+        widths[0] = Constraint::Min(longest_mount.unwrap_or(0) as u16);
+        let footer = Row::new(texts.pop().unwrap_or_default()).style(footer_style());
+        let rows = texts
+            .into_iter()
+            .zip(&disks)
+            .map(|(text, disk)| {
+                let row = Row::new(text);
+                if self.focus.as_deref() == Some(disk.mount_point.as_str())
+                    || self.selected_device.as_deref() == Some(disk.device.as_str())
+                {
+                    row.style(focus_style())
+                } else {
+                    row
+                }
+            })
+            .chain([footer]);
+        let table = Table::new(rows)
+            .header(header)
+            .block(
+                Block::default()
+                    .title(format!(
+                        "Disk Details (sorted by {}, '{}' to change, '{}' to {} type and flags, \
+                         '{}' to {})",
+                        self.disk_sort.label(),
+                        self.keys.hint(Action::Sort),
+                        self.keys.hint(Action::ToggleDiskDetails),
+                        if self.show_disk_details {
+                            "hide"
+                        } else {
+                            "show"
+                        },
+                        self.keys.hint(Action::TogglePseudoDisks),
+                        if self.show_pseudo_disks {
+                            "hide pseudo file systems".to_string()
+                        } else {
+                            format!("show {} pseudo file systems", pseudo.len())
+                        }
+                    ))
+                    .borders(Borders::ALL),
+            )
+            .widths(&widths)
+            .highlight_style(Style::default().bg(Color::DarkGray));
+        f.render_widget(table, chunks[2]);
+        self.render_disk_io(f, chunks[1], &state);
+        let disk_block = Block::default().title("Disk Details").borders(Borders::ALL);
+        f.render_widget(disk_block, area);
+    }
+
+    /// Header and cells of the disk table, the footer's totals last.
+    fn disk_table(&self, snapshot: &Snapshot) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        let (used_space, total_space) = snapshot.disk_space(self.disk_total_all);
+        let mut headers = vec!["Mount", "Total", "Used", "Available", "Usage %"];
+        if self.show_disk_details {
+            headers.extend(["Type", "Flags"]);
+        }
+        let disk_texts = |mount: &str, total: u64, available: u64| {
+            let used = total.saturating_sub(available);
+            let usage_percent = if total > 0 {
+                used as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            };
+            let gb = |bytes: u64| self.precision.memory(bytes as f64 / 1_000_000_000.0, "GB");
+            vec![
+                mount.to_string(),
+                gb(total),
+                gb(used),
+                gb(available),
+                self.precision.percent(usage_percent),
+            ]
+        };
+        let (disks, _) = self.sorted_disks(snapshot);
+        let mut texts: Vec<_> = disks
+            .iter()
+            .map(|disk| {
+                let mut text = disk_texts(&disk.mount_point, disk.total, disk.available);
+                if self.show_disk_details {
+                    let flags: Vec<_> = [(disk.read_only, "ro"), (disk.removable, "removable")]
+                        .into_iter()
+                        .filter_map(|(set, flag)| set.then_some(flag))
+                        .collect();
+                    text.push(disk.file_system.clone());
+                    text.push(if flags.is_empty() {
+                        "-".to_string()
+                    } else {
+                        flags.join(", ")
+                    });
+                }
+                text
+            })
+            .collect();
+        let mut total_text =
+            disk_texts("Total", total_space, total_space.saturating_sub(used_space));
+        total_text.resize(headers.len(), String::new());
+        texts.push(total_text);
+        (headers, texts)
+    }
+
+    /// Read and write rates of the selected device, or of all of them.
+    fn render_disk_io<B: Backend>(
+        &self,
+        f: &mut Frame<'_, B>,
+        area: tui::layout::Rect,
+        state: &SystemState,
+    ) {
+        let history = state.device_io(self.selected_device.as_deref());
+        let kb_per_second = |rate: u64| rate as f64 / 1024.0;
+        let read: Vec<_> = history
+            .iter()
+            .enumerate()
+            .map(|(i, &(read, _))| (i as f64, kb_per_second(read)))
+            .collect();
+        let written: Vec<_> = history
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, write))| (i as f64, kb_per_second(write)))
+            .collect();
+        let read = self.chart_points(downsample(&read, chart_resolution(area)));
+        let written = self.chart_points(downsample(&written, chart_resolution(area)));
+        let ghost = self.ghost.as_ref().map(|ghost| {
+            let history = ghost.device_io(self.selected_device.as_deref());
+            (
+                ghost_points(history.iter().map(|&(read, _)| kb_per_second(read)), area),
+                ghost_points(history.iter().map(|&(_, write)| kb_per_second(write)), area),
+            )
+        });
+        let max = read
+            .iter()
+            .chain(&written)
+            .chain(
+                ghost
+                    .iter()
+                    .flat_map(|(read, written)| read.iter().chain(written)),
+            )
+            .map(|&(_, v)| v)
+            .fold(0.0, f64::max);
+        let y_bound_top = (max * 1.1).max(10.0);
+
+        let mut datasets: Vec<_> = ghost
+            .iter()
+            .flat_map(|(read, written)| {
+                [
+                    ghost_dataset("Read before", read),
+                    ghost_dataset("Write before", written),
+                ]
+            })
+            .collect();
+        datasets.extend([
+            Dataset::default()
+                .name("Read (KB/s)")
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Green))
+                .data(&read),
+            Dataset::default()
+                .name("Write (KB/s)")
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Red))
+                .data(&written),
+        ]);
+        let (read_rate, write_rate) =
+            state.device_rate(self.selected_device.as_deref(), self.rate_window);
+        let title = format!(
+            "Disk I/O, {}: {} read, {} written{} ({}/{} to pick a device)",
+            self.selected_device.as_deref().unwrap_or("all devices"),
+            format_rate(read_rate, &self.precision),
+            format_rate(write_rate, &self.precision),
+            if self.rate_window > 1 {
+                format!(", mean of {} samples", self.rate_window)
+            } else {
+                String::new()
+            },
+            self.keys.hint(Action::CursorUp),
+            self.keys.hint(Action::CursorDown)
+        );
+        let chart = Chart::new(datasets)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, history.len() as f64])
+                    .labels(
+                        time_axis_labels(history.len(), self.interval(), axis_ticks(area))
+                            .into_iter()
+                            .map(|s| Span::styled(s, Style::default().fg(Color::Gray)))
+                            .collect(),
+                    ),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("KB/s")
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, y_bound_top])
+                    .labels(vec![
+                        Span::raw("0"),
+                        Span::raw(format!("{:.0}", y_bound_top / 2.0)),
+                        Span::raw(format!("{:.0}", y_bound_top)),
+                    ]),
+            );
+        f.render_widget(chart, area);
+    }
+
+    fn render_network<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let state = self.state();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(4),      // Current Rates Summary
+                    Constraint::Percentage(50), // Network History Chart
+                    Constraint::Min(5),         // Interface Details Table
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        let rate_area = chunks[0];
+        let chart_area = chunks[1];
+        let table_area = chunks[2];
+
+        let network_availability = state.network_availability();
+        if network_availability.is_missing() {
+            Self::render_unavailable(f, area, "Network", network_availability);
+            return;
+        }
+
+        let (rx_rate, tx_rate) = state.network_rate(self.rate_window);
+
+        if network_availability == DataAvailability::Collecting {
+            Self::render_unavailable(f, rate_area, "Current Traffic Rate", network_availability);
+        } else {
+            let (session_rx, session_tx) = state.session_totals;
+            let network_summary = Paragraph::new(vec![
+                Spans::from(vec![
+                    Span::styled("Down: ", Style::default().fg(Color::Green)),
+                    Span::raw(self.network_rate(rx_rate)),
+                    Span::raw("  "),
+                    Span::styled("Up: ", Style::default().fg(Color::Red)),
+                    Span::raw(self.network_rate(tx_rate)),
+                ]),
+                Spans::from(vec![Span::styled(
+                    format!(
+                        "Since start: {} down, {} up ('{}' to reset)",
+                        format_total_bytes(session_rx, &self.precision),
+                        format_total_bytes(session_tx, &self.precision),
+                        self.keys.hint(Action::ResetTotals)
+                    ),
+                    Style::default().fg(Color::Gray),
+                )]),
+            ])
+            .block(
+                Block::default()
+                    .title(if self.rate_window > 1 {
+                        format!(
+                            "Current Traffic Rate (mean of {} samples)",
+                            self.rate_window
+                        )
+                    } else {
+                        "Current Traffic Rate".to_string()
+                    })
+                    .borders(Borders::ALL),
+            )
+            .alignment(tui::layout::Alignment::Center);
+            f.render_widget(network_summary, rate_area);
+        }
+
+        let network_history = &state.network_history;
+
+        let (unit, per_unit) = if self.rates_in_bits {
+            ("Kb/s", 1000.0 / 8.0)
+        } else {
+            ("KB/s", 1024.0)
+        };
+        let mut rx_data: Vec<(f64, f64)> = Vec::new();
+        let mut tx_data: Vec<(f64, f64)> = Vec::new();
+
+        for (i, &(rx_rate_bps, tx_rate_bps)) in network_history.iter().enumerate() {
+            rx_data.push((i as f64, rx_rate_bps as f64 / per_unit));
+            tx_data.push((i as f64, tx_rate_bps as f64 / per_unit));
+        }
+        let rx_data = self.chart_points(downsample(&rx_data, chart_resolution(chart_area)));
+        let tx_data = self.chart_points(downsample(&tx_data, chart_resolution(chart_area)));
+        let ghost = self.ghost.as_ref().map(|ghost| {
+            let history = &ghost.network_history;
+            (
+                ghost_points(
+                    history.iter().map(|&(rx, _)| rx as f64 / per_unit),
+                    chart_area,
+                ),
+                ghost_points(
+                    history.iter().map(|&(_, tx)| tx as f64 / per_unit),
+                    chart_area,
+                ),
+            )
+        });
+
+        let mut datasets: Vec<_> = ghost
+            .iter()
+            .flat_map(|(rx, tx)| {
+                [
+                    ghost_dataset("Down before", rx),
+                    ghost_dataset("Up before", tx),
+                ]
+            })
+            .collect();
+        datasets.extend([
+            Dataset::default()
+                .name(format!("Download ({unit})"))
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Green))
+                .data(&rx_data),
+            Dataset::default()
+                .name(format!("Upload ({unit})"))
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Red))
+                .data(&tx_data),
+        ]);
+
+        let max_rate_kbps = rx_data
+            .iter()
+            .chain(tx_data.iter())
+            .chain(ghost.iter().flat_map(|(rx, tx)| rx.iter().chain(tx)))
+            .map(|&(_, v)| v)
+            .fold(0.0, |max, v| if v.is_finite() && v > max { v } else { max });
+
+        // Set a minimum top bound for the Y axis, e.g., 10 KB/s, and add headroom
+        let y_bound_top = (max_rate_kbps * 1.1).max(10.0);
+        let history_len = state.network_history.len() as f64;
+
+        let mut title = format!("Network History ({unit})");
+        let cursor = self
+            .cursor_index(network_history.len())
+            .map(|index| (index, cursor_line(index as f64, y_bound_top)));
+        if let Some((index, line)) = &cursor {
+            let (rx, tx) = network_history[*index];
+            title = self.cursor_title(
+                &title,
+                *index,
+                network_history.len(),
+                format!(
+                    "down {}, up {}",
+                    self.network_rate(rx),
+                    self.network_rate(tx)
+                ),
+            );
+            datasets.push(cursor_dataset(line));
+        }
+
+        let chart = Chart::new(datasets)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .x_axis(
+                Axis::default()
+                    // .title("Time") // Often redundant
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, history_len]) // X represents time steps
+                    .labels(
+                        time_axis_labels(
+                            state.network_history.len(),
+                            self.interval(),
+                            axis_ticks(chart_area),
+                        )
+                        .into_iter()
+                        .map(|s| Span::styled(s, Style::default().fg(Color::Gray)))
+                        .collect(),
+                    ),
+            )
+            .y_axis(
+                Axis::default()
+                    .title(unit)
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, y_bound_top]) // Dynamic upper bound
+                    .labels(
+                        // Generate labels dynamically based on the top bound
+                        vec![
+                            Span::raw("0"),
+                            Span::raw(format!("{:.0}", y_bound_top / 2.0)),
+                            Span::raw(format!("{:.0}", y_bound_top)),
+                        ],
+                    ),
+            );
+        if network_availability == DataAvailability::Collecting {
+            Self::render_unavailable(
+                f,
+                chart_area,
+                &format!("Network History ({unit})"),
+                network_availability,
+            );
+        } else {
+            f.render_widget(chart, chart_area);
+        }
+
+        let interfaces = self.listed_interfaces(&state);
+        let (headers, mut texts) = self.network_table(&state, &interfaces);
+        let header_cells = headers
+            .iter()
+            .map(|h| Cell::from(Span::styled(*h, Style::default().fg(Color::Yellow))));
+        let header = Row::new(header_cells)
+            .style(Style::default().bg(Color::DarkGray))
+            .height(1);
+        let widths = if self.show_link_details {
+            fitted_widths(&headers, &texts)
+        } else {
+            vec![
+                Constraint::Percentage(24),
+                Constraint::Percentage(16),
+                Constraint::Percentage(16),
+                Constraint::Percentage(22),
+                Constraint::Percentage(22),
+            ]
+        };
+        let footer = Row::new(texts.pop().unwrap_or_default()).style(footer_style());
+        let mut rows: Vec<_> = texts
+            .into_iter()
+            .zip(&interfaces)
+            .map(|(text, interface)| {
+                let mut row = Row::new(text);
+                if interface.up == Some(false) {
+                    row = row.style(Style::default().fg(Color::DarkGray));
+                }
+                if self.focus.as_deref() == Some(interface.name.as_str()) {
+                    row = row.style(focus_style());
+                }
+                row
+            })
+            .collect();
+        rows.push(footer);
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(
+                Block::default()
+                    .title(format!(
+                        "Network Interfaces{} (sorted by {}, '{}' to change, '{}' to {} link details)",
+                        if state.interface_filter.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" matching '{}'", state.interface_filter)
+                        },
+                        self.network_sort.label(),
+                        self.keys.hint(Action::Sort),
+                        self.keys.hint(Action::ToggleLinkDetails),
+                        if self.show_link_details {
+                            "hide"
+                        } else {
+                            "show"
+                        }
+                    ))
+                    .borders(Borders::ALL),
+            )
+            .widths(&widths)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        f.render_widget(table, table_area);
+    }
+
+    /// Interfaces the network table lists, in its order.
+    fn listed_interfaces<'a>(&self, state: &'a SystemState) -> Vec<&'a InterfaceSnapshot> {
+        let mut interfaces: Vec<_> = state
+            .snapshot
+            .networks
+            .iter()
+            .filter(|interface| interface.matches(&state.interface_filter))
+            .collect();
+        match self.network_sort {
+            NetworkSort::Name => interfaces.sort_by(|a, b| a.name.cmp(&b.name)),
+            NetworkSort::RxRate => interfaces.sort_by_key(|i| Reverse(i.rx_rate)),
+            NetworkSort::TxRate => interfaces.sort_by_key(|i| Reverse(i.tx_rate)),
+            NetworkSort::Total => {
+                interfaces.sort_by_key(|i| Reverse(i.total_received + i.total_transmitted))
+            }
+        }
+        interfaces
+    }
+
+    /// Header and cells of the network table for `interfaces`, the footer's
+    /// totals last.
+    fn network_table(
+        &self,
+        state: &SystemState,
+        interfaces: &[&InterfaceSnapshot],
+    ) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        let mut headers = vec![
+            "Interface Name",
+            "Rx Rate",
+            "Tx Rate",
+            "Total Recived",
+            "Total Transmitted",
+        ];
+        if self.show_link_details {
+            headers.extend(["State", "MTU", "MAC", "Addresses"]);
+        }
+        // The footer sums what the headline rates count, so loopback and
+        // container interfaces are listed but left out by default.
+        let mut totals = (0, 0, 0, 0);
+        let mut left_out = false;
+        let mut texts = Vec::new();
+        for interface in interfaces {
+            if state.counts_interface(interface) {
+                totals.0 += interface.rx_rate;
+                totals.1 += interface.tx_rate;
+                totals.2 += interface.total_received;
+                totals.3 += interface.total_transmitted;
+            } else {
+                left_out = true;
+            }
+            let mut text = vec![
+                interface.name.clone(),
+                self.network_rate(interface.rx_rate),
+                self.network_rate(interface.tx_rate),
+                format_total_bytes(interface.total_received, &self.precision),
+                format_total_bytes(interface.total_transmitted, &self.precision),
+            ];
+            if self.show_link_details {
+                text.extend([
+                    match interface.up {
+                        Some(true) => "up",
+                        Some(false) => "down",
+                        None => "?",
+                    }
+                    .to_string(),
+                    interface
+                        .mtu
+                        .map_or_else(|| "-".to_string(), |mtu| mtu.to_string()),
+                    match &interface.mac_address {
+                        Some(_) if self.privacy => REDACTED.to_string(),
+                        Some(mac) => mac.clone(),
+                        None => "-".to_string(),
+                    },
+                    if interface.addresses.is_empty() {
+                        "-".to_string()
+                    } else if self.privacy {
+                        REDACTED.to_string()
+                    } else {
+                        interface.addresses.join(", ")
+                    },
+                ]);
+            }
+            texts.push(text);
+        }
+        let mut total_text = vec![
+            if left_out {
+                "Total (excl. virtual)"
+            } else {
+                "Total"
+            }
+            .to_string(),
+            self.network_rate(totals.0),
+            self.network_rate(totals.1),
+            format_total_bytes(totals.2, &self.precision),
+            format_total_bytes(totals.3, &self.precision),
+        ];
+        total_text.resize(headers.len(), String::new());
+        texts.push(total_text);
+        (headers, texts)
+    }
+
+    /// Header of the process table; the first column holds the marks.
+    fn process_headers(&self) -> Vec<String> {
+        let arrow = if self.sort_descending {
+            self.glyphs.sort_descending
+        } else {
+            self.glyphs.sort_ascending
+        };
+        [String::new()]
+            .into_iter()
+            .chain(self.process_columns.iter().map(|&column| {
+                if column == self.process_sort {
+                    format!("{}{arrow}", column.header())
+                } else {
+                    column.header().to_string()
+                }
+            }))
+            .collect()
+    }
+
+    /// Cells of the process table's `rows`, as `process_headers` orders them.
+    fn process_texts(&self, state: &SystemState, rows: &[ProcessRow<'_>]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| {
+                let process = &row.process;
+                let mark = if row.members.is_none() && self.marked.contains(&process.pid) {
+                    self.glyphs.marked
+                } else {
+                    " "
+                };
+                let status = status_label(process.status).0;
+                let name = self.process_name(&process.name, process.pid);
+                [mark.to_string()]
+                    .into_iter()
+                    .chain(
+                        self.process_columns
+                            .iter()
+                            .map(|column| match (column, row.members) {
+                                (ProcessColumn::Pid, Some(_)) => String::new(),
+                                (ProcessColumn::Name, Some(members)) => {
+                                    let arrow = if self.expanded_groups.contains(&process.name) {
+                                        self.glyphs.expanded
+                                    } else {
+                                        self.glyphs.collapsed
+                                    };
+                                    format!("{arrow} {name} ({members})")
+                                }
+                                (ProcessColumn::Name, None) if row.member => {
+                                    format!("  {name}")
+                                }
+                                (ProcessColumn::Name, None) => name.clone(),
+                                _ => column.text(
+                                    process,
+                                    status,
+                                    &self.precision,
+                                    state.snapshot.timestamp,
+                                ),
+                            }),
+                    )
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn render_processes<B: Backend>(&self, f: &mut Frame<'_, B>, area: tui::layout::Rect) {
+        let state = self.state();
+
+        let watched = self.watched.and_then(|pid| state.process_history(pid));
+        // Sorted by start time, the table shows the oldest or newest few;
+        // the timeline shows whether many started together.
+        let show_timeline = matches!(
+            self.process_sort,
+            ProcessColumn::Started | ProcessColumn::Age
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Min(0),
+                    Constraint::Length(if watched.is_some() { 10 } else { 0 }),
+                    Constraint::Length(u16::from(show_timeline)),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+        if show_timeline {
+            let starts = start_timeline(&state.snapshot, START_TIMELINE_MINUTES);
+            let label = format!(
+                "Started per minute, last hour ({} in the last minute): ",
+                starts.last().copied().unwrap_or(0)
+            );
+            let parts = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Length(label.chars().count() as u16),
+                    Constraint::Min(0),
+                ])
+                .split(chunks[2]);
+            f.render_widget(Paragraph::new(label), parts[0]);
+            f.render_widget(
+                Sparkline::default()
+                    .bar_set(self.glyphs.bars.clone())
+                    .data(&starts)
+                    .style(Style::default().fg(Color::Cyan)),
+                parts[1],
+            );
+        }
+        if let Some(history) = watched {
+            self.render_process_history(
+                f,
+                chunks[1],
+                history,
+                Direction::Horizontal,
+                &format!(
+                    " - watching ('{}' to stop)",
+                    self.keys.hint(Action::Unwatch)
+                ),
+            );
+        }
+
+        let headers = self.process_headers();
+        let headers: Vec<_> = headers.iter().map(String::as_str).collect();
+        let header_cells = headers.iter().map(|h| Cell::from(*h));
+        let header = Row::new(header_cells).style(Style::default().fg(Color::Yellow));
+
+        let mut processes = self.listed_rows(&state.snapshot);
+        let listed = processes.len();
+        let listed_processes: usize = processes
+            .iter()
+            .map(|row| row.members.unwrap_or(usize::from(!row.member)))
+            .sum();
+        if let Some(max) = self.max_processes {
+            processes.truncate(max);
+        }
+        let matches = search_matches(&processes, &self.search_query);
+        let current_match = if matches.is_empty() {
+            None
+        } else {
+            Some(matches[self.search_match.min(matches.len() - 1)])
+        };
+
+        let texts = self.process_texts(&state, &processes);
+        // Fitting the PID column to the data matters most: PIDs go up to
+        // 4194304 on Linux and a truncated one points at the wrong process.
+        let mut constraints = fitted_widths(&headers, &texts);
+        if let Some(name) = self
+            .process_columns
+            .iter()
+            .position(|&column| column == ProcessColumn::Name)
+        {
+            constraints[name + 1] = Constraint::Percentage(40);
+        }
+
+        let relative_limit = self.highlight_memory_percent.map(|percent| {
+            (state.snapshot.memory.total as f64 * f64::from(percent) / 100.0) as u64
+        });
+        let memory_limit = match (self.highlight_memory, relative_limit) {
+            (Some(absolute), Some(relative)) => Some(absolute.min(relative)),
+            (absolute, relative) => absolute.or(relative),
+        };
+
+        let mut rows = Vec::new();
+        for (i, (entry, text)) in processes.iter().zip(texts).enumerate() {
+            let process = &entry.process;
+            let mut cells: Vec<_> = text.into_iter().map(Cell::from).collect();
+            for (cell, column) in cells[1..].iter_mut().zip(&self.process_columns) {
+                match column {
+                    ProcessColumn::Status => *cell = status_cell(process.status),
+                    ProcessColumn::OpenFiles if process.near_open_files_limit() => {
+                        *cell = std::mem::take(cell).style(Style::default().fg(Color::Red));
+                    }
+                    ProcessColumn::Memory
+                        if memory_limit.is_some_and(|limit| process.memory > limit) =>
+                    {
+                        *cell = std::mem::take(cell).style(Style::default().fg(Color::Red));
+                    }
+                    _ => {}
+                }
+            }
+            let mut row = Row::new(cells);
+            if entry.members.is_none() && state.is_new_process(process.pid) {
+                row = row.style(new_process_style());
+            }
+            if process.status == ProcessState::DiskWait {
+                row = row.style(stuck_process_style());
+            }
+            if matches.binary_search(&i).is_ok() {
+                row = row.style(Style::default().fg(Color::Yellow));
+            }
+            rows.push(row);
+        }
+
+        let format_count = |n: usize| {
+            if self.compact_numbers {
+                abbreviate_count(n as u64)
+            } else {
+                n.to_string()
+            }
+        };
+        let mut count = format_count(listed_processes);
+        let hides_self = state
+            .snapshot
+            .processes
+            .iter()
+            .any(|process| self.is_hidden_self(process));
+        if !self.process_filter.is_empty() || self.stuck_only || hides_self {
+            count = format!(
+                "{count} of {}",
+                format_count(state.snapshot.processes.len())
+            );
+        }
+        let mut marked = if self.marked.is_empty() {
+            String::new()
+        } else {
+            format!(", {} marked", self.marked.len())
+        };
+        if !self.process_filter.is_empty() {
+            marked.push_str(&format!(", filter '{}'", self.process_filter));
+        }
+        if hides_self {
+            marked.push_str(&format!(
+                ", dashoxide hidden, '{}' to show",
+                self.keys.hint(Action::ToggleSelf)
+            ));
+        }
+        if processes.len() < listed {
+            marked.push_str(&format!(", top {} rows shown", processes.len()));
+        }
+        let stuck = state
+            .snapshot
+            .processes
+            .iter()
+            .filter(|process| process.status == ProcessState::DiskWait)
+            .count();
+        if self.stuck_only {
+            marked.push_str(&format!(
+                ", disk wait only, '{}' to show all",
+                self.keys.hint(Action::ToggleStuck)
+            ));
+        } else if stuck > 0 {
+            marked.push_str(&format!(
+                ", {stuck} in disk wait, '{}' to list them",
+                self.keys.hint(Action::ToggleStuck)
+            ));
+        }
+        let title = if self.search_query.is_empty() {
+            format!("Processes Details ({count} processes{marked})")
+        } else if matches.is_empty() {
+            format!(
+                "Processes Details ({count} processes{marked}) - search '{}' (no matches)",
+                self.search_query
+            )
+        } else {
+            format!(
+                "Processes Details ({count} processes{marked}) - search '{}' ({}/{})",
+                self.search_query,
+                self.search_match.min(matches.len() - 1) + 1,
+                matches.len()
+            )
+        };
+
+        let selected = if self.mode == InputMode::Search {
+            current_match
+        } else {
+            self.selected_index(&processes).or(current_match)
+        };
+        // Inside the borders and header.
+        let height = usize::from(chunks[0].height.saturating_sub(3));
+        let offset = scroll_offset(self.process_offset.get(), selected, height, rows.len());
+        self.process_offset.set(offset);
+
+        let processes_block = Table::new(rows.into_iter().skip(offset).take(height))
+            .header(header)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .widths(&constraints)
+            .highlight_style(Style::default().bg(Color::DarkGray));
+
+        let mut table_state = TableState::default();
+        table_state.select(selected.map(|selected| selected - offset));
+        f.render_stateful_widget(processes_block, chunks[0], &mut table_state);
+        *self.swap_rows.borrow_mut() = if self.process_columns.contains(&ProcessColumn::Swap) {
+            processes[offset..]
+                .iter()
+                .take(height)
+                .flat_map(|row| {
+                    let process = &row.process;
+                    let members: Vec<_> = if row.members.is_some() {
+                        state
+                            .snapshot
+                            .processes
+                            .iter()
+                            .filter(|member| member.name == process.name)
+                            .map(|member| member.pid)
+                            .collect()
+                    } else {
+                        vec![process.pid]
+                    };
+                    members
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut legend = vec![Span::raw("Status: ")];
+        for status in [
+            ProcessState::Running,
+            ProcessState::Sleeping,
+            ProcessState::Idle,
+            ProcessState::Stopped,
+            ProcessState::DiskWait,
+            ProcessState::Zombie,
+        ] {
+            let (label, color) = status_label(status);
+            legend.push(Span::styled(
+                format!("{} {label}  ", self.glyphs.swatch),
+                Style::default().fg(color),
+            ));
+        }
+        legend.push(Span::styled(
+            format!("{} new process", self.glyphs.swatch),
+            new_process_style(),
+        ));
+        f.render_widget(Paragraph::new(Spans::from(legend)), chunks[3]);
+    }
+}
+
+/// The text of a line, without its styling.
+fn spans_text(spans: &Spans<'_>) -> String {
+    spans.0.iter().map(|span| span.content.as_ref()).collect()
+}
+
+/// A table as text: the header, then one line per row, each column padded
+/// to its widest cell.
+fn text_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<_> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<_> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:<width$}"))
+            .collect();
+        padded.join("  ").trim_end().to_string()
+    };
+    std::iter::once(line(headers.to_vec()))
+        .chain(
+            rows.iter()
+                .map(|row| line(row.iter().map(String::as_str).collect())),
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// One compact line naming an Overview box's top consumers, or nothing
+/// when there are none to name.
+fn overview_top_line(label: &str, top: Vec<String>) -> Option<Spans<'static>> {
+    (!top.is_empty()).then(|| {
+        Spans::from(vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::Cyan)),
+            Span::raw(top.join(", ")),
+        ])
+    })
+}
+
+const NUMA_HEADERS: [&str; 6] = ["Node", "Used", "Free", "Usage %", "CPUs", "CPU %"];
+
+const ALERT_HEADERS: [&str; 4] = ["Time (UTC)", "Metric", "Value", "Threshold"];
+
+/// Shown in place of addresses in privacy mode.
+const REDACTED: &str = "(hidden)";
+
+/// Stand-in for a process name in privacy mode.
+fn redacted_name(pid: u32) -> String {
+    format!("proc-{pid}")
+}
+
+/// Index one step forward or back from `index` in `0..=last`. At either
+/// end it stays put, or with `wrap` goes round to the other end.
+fn step(index: usize, last: usize, forward: bool, wrap: bool) -> usize {
+    match (forward, wrap) {
+        (true, _) if index < last => index + 1,
+        (true, true) => 0,
+        (true, false) => last,
+        (false, _) if index > 0 => index - 1,
+        (false, true) => last,
+        (false, false) => 0,
+    }
+}
+
+/// Minutes covered by the process start timeline.
+const START_TIMELINE_MINUTES: usize = 60;
+
+/// Processes started in each of the last `minutes` minutes before the
+/// snapshot, oldest minute first.
+fn start_timeline(snapshot: &Snapshot, minutes: usize) -> Vec<u64> {
+    let mut starts = vec![0; minutes];
+    for process in &snapshot.processes {
+        let minutes_ago = snapshot.timestamp.saturating_sub(process.start_time) / 60;
+        if let Some(count) = usize::try_from(minutes_ago)
+            .ok()
+            .and_then(|ago| minutes.checked_sub(ago + 1))
+            .and_then(|index| starts.get_mut(index))
+        {
+            *count += 1;
+        }
+    }
+    starts
+}
+
+fn format_rate(bytes_per_sec: u64, precision: &Precision) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = 1024.0 * KB;
+    let rate = bytes_per_sec as f64;
+    if rate < KB {
+        format!("{bytes_per_sec} B/s")
+    } else {
+        precision.rate(rate / MB, "MB/s")
+    }
+}
+
+/// A rate given in bytes as bits per second, in decimal units like link
+/// speeds ("1 Gb/s" is 10^9 bits).
+fn format_bit_rate(bytes_per_sec: u64, precision: &Precision) -> String {
+    let bits = bytes_per_sec as f64 * 8.0;
+    if bits < 1e3 {
+        format!("{bits:.0} b/s")
+    } else if bits < 1e6 {
+        precision.rate(bits / 1e3, "Kb/s")
+    } else if bits < 1e9 {
+        precision.rate(bits / 1e6, "Mb/s")
+    } else {
+        precision.rate(bits / 1e9, "Gb/s")
+    }
+}
+
+/// Number of points a chart drawn in `area` can actually resolve; anything
+/// beyond one sample per terminal column is just noise.
+fn chart_resolution(area: tui::layout::Rect) -> usize {
+    usize::from(area.width)
+}
+
+/// Points of a ghost line from its values, oldest first, thinned for
+/// `area` like the live lines.
+fn ghost_points(values: impl Iterator<Item = f64>, area: tui::layout::Rect) -> Vec<(f64, f64)> {
+    let data: Vec<_> = values.enumerate().map(|(i, v)| (i as f64, v)).collect();
+    downsample(&data, chart_resolution(area))
+}
+
+/// Columns the full tab bar takes: every title with a space on either side,
+/// a divider between them, and the borders.
+fn full_tab_bar_width() -> u16 {
+    let titles: usize = DashboardView::ALL
+        .iter()
+        .map(|view| view.title().len() + 2)
+        .sum();
+    (titles + DashboardView::ALL.len() - 1 + 2) as u16
+}
+
+/// Distance along the x axis, in samples, between the strokes of a filled
+/// chart; close enough that they merge at any usual chart width.
+const FILL_STEP: f64 = 0.25;
+
+/// Points tracing `data` with a vertical stroke down to the x axis every
+/// `FILL_STEP`, so a line dataset reads as a filled area. tui has no area
+/// graph type to do this for us.
+fn fill_under(data: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut points = Vec::new();
+    for pair in data.windows(2) {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        let steps = ((x1 - x0) / FILL_STEP).ceil().max(1.0) as usize;
+        for step in 0..steps {
+            let t = step as f64 / steps as f64;
+            let (x, y) = (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t);
+            points.extend([(x, y), (x, 0.0), (x, y)]);
+        }
+    }
+    if let Some(&(x, y)) = data.last() {
+        points.extend([(x, y), (x, 0.0), (x, y)]);
+    }
+    points
+}
+
+/// A ghost line, dimmed and drawn before the live lines so they stay on
+/// top.
+fn ghost_dataset<'a>(name: &'a str, data: &'a [(f64, f64)]) -> Dataset<'a> {
+    Dataset::default()
+        .name(name)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::DarkGray))
+        .data(data)
+}
+
+/// Style of the totals row closing a table.
+fn footer_style() -> Style {
+    Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// Muted 256-color palette entries for the per-core CPU overlay, dim enough
+/// to sit behind the cyan average.
+const CORE_COLORS: [Color; 8] = [
+    Color::Indexed(60),
+    Color::Indexed(65),
+    Color::Indexed(95),
+    Color::Indexed(101),
+    Color::Indexed(67),
+    Color::Indexed(72),
+    Color::Indexed(131),
+    Color::Indexed(137),
+];
+
+/// Whether `COLORTERM` or `TERM` advertise at least 256 colors.
+fn rich_colors() -> bool {
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    let term = std::env::var("TERM").unwrap_or_default();
+    matches!(colorterm.as_str(), "truecolor" | "24bit") || term.contains("256color")
+}
+
+/// First of `len` rows to show in `height` lines: `offset`, moved by as
+/// little as brings `selected` into view.
+fn scroll_offset(offset: usize, selected: Option<usize>, height: usize, len: usize) -> usize {
+    let offset = match selected {
+        Some(selected) if selected < offset => selected,
+        Some(selected) if selected >= offset + height => {
+            (selected + 1).saturating_sub(height.max(1))
+        }
+        _ => offset,
+    };
+    offset.min(len.saturating_sub(height))
+}
+
+/// Widest a fitted table column gets; longer values are cut off.
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// How long a status message replaces the key hints.
+const STATUS_MESSAGE_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// One `Length` per column, fitted to the longest of the header and the
+/// column's values and capped at `MAX_COLUMN_WIDTH`. Callers swap in a
+/// `Percentage` for a column that should take up the remaining space.
+fn fitted_widths(headers: &[&str], rows: &[Vec<String>]) -> Vec<Constraint> {
+    headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            let longest = rows
+                .iter()
+                .filter_map(|row| row.get(column))
+                .map(|value| value.chars().count())
+                .chain([header.chars().count()])
+                .max()
+                .unwrap_or(0);
+            Constraint::Length(longest.min(MAX_COLUMN_WIDTH) as u16)
+        })
+        .collect()
+}
+
+/// Two-point vertical line at `x`, drawn as the chart cursor.
+fn cursor_line(x: f64, top: f64) -> [(f64, f64); 2] {
+    [(x, 0.0), (x, top)]
+}
+
+fn cursor_dataset(line: &[(f64, f64)]) -> Dataset<'_> {
+    Dataset::default()
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::White))
+        .data(line)
+}
+
+/// How many x-axis labels fit under a chart without crowding each other.
+fn axis_ticks(area: tui::layout::Rect) -> usize {
+    usize::from(area.width / 16).clamp(2, 6)
+}
+
+fn risk_color(risk: OomRisk) -> Color {
+    match risk {
+        OomRisk::Low => Color::Green,
+        OomRisk::Elevated => Color::Yellow,
+        OomRisk::High => Color::Red,
+    }
+}
+
+/// Short label and color for a process status.
+fn status_label(status: ProcessState) -> (&'static str, Color) {
+    let color = match status {
+        ProcessState::Running | ProcessState::Waking => Color::Green,
+        ProcessState::Sleeping | ProcessState::Parked => Color::Gray,
+        ProcessState::Idle => Color::DarkGray,
+        ProcessState::Stopped | ProcessState::Traced | ProcessState::Wakekill => Color::Yellow,
+        ProcessState::DiskWait | ProcessState::LockWait => Color::Magenta,
+        ProcessState::Zombie | ProcessState::Dead => Color::Red,
+        ProcessState::Unknown => Color::White,
+    };
+    (status.label(), color)
+}
+
+/// The row the switcher jumped to, matching the process table's cursor.
+fn focus_style() -> Style {
+    Style::default().bg(Color::DarkGray)
+}
+
+/// Processes in uninterruptible sleep, usually waiting on storage or NFS.
+fn stuck_process_style() -> Style {
+    Style::default()
+        .fg(Color::Magenta)
+        .add_modifier(Modifier::BOLD)
+}
+
+fn new_process_style() -> Style {
+    Style::default()
+        .fg(Color::LightCyan)
+        .add_modifier(Modifier::BOLD)
+}
+
+fn status_cell(status: ProcessState) -> Cell<'static> {
+    let (label, color) = status_label(status);
+    Cell::from(label).style(Style::default().fg(color))
+}
+
+/// Processes in table order; ties fall back to PID so rows don't jump
+/// between refreshes.
+fn sorted_processes(
+    snapshot: &Snapshot,
+    column: ProcessColumn,
+    descending: bool,
+) -> Vec<&ProcessSnapshot> {
+    let mut processes: Vec<_> = snapshot.processes.iter().collect();
+    processes.sort_by(|a, b| {
+        let order = column.compare(a, b).then(a.pid.cmp(&b.pid));
+        if descending { order.reverse() } else { order }
+    });
+    processes
+}
+
+/// One row of the process table: a process, or the summary of a group of
+/// same-named processes.
+struct ProcessRow<'a> {
+    process: Cow<'a, ProcessSnapshot>,
+    /// Number of processes summed into a group's summary row.
+    members: Option<usize>,
+    /// Listed under an expanded group's summary row.
+    member: bool,
+}
+
+impl ProcessRow<'_> {
+    /// What the cursor remembers to find this row again: the PID, and the
+    /// group name for a summary row.
+    fn key(&self) -> (u32, Option<String>) {
+        let group = self.members.map(|_| self.process.name.clone());
+        (self.process.pid, group)
+    }
+}
+
+/// A process standing in for `members`, all of the same name: usage is
+/// summed, and the rest is taken from the member with the lowest PID.
+fn group_summary(members: &[&ProcessSnapshot]) -> ProcessSnapshot {
+    let leader = members
+        .iter()
+        .min_by_key(|process| process.pid)
+        .expect("groups have members");
+    let sum_optional = |value: fn(&ProcessSnapshot) -> Option<u64>| {
+        members
+            .iter()
+            .map(|process| value(process))
+            .sum::<Option<u64>>()
+    };
+    ProcessSnapshot {
+        cpu_usage: members.iter().map(|process| process.cpu_usage).sum(),
+        memory: members.iter().map(|process| process.memory).sum(),
+        disk_read: members.iter().map(|process| process.disk_read).sum(),
+        disk_written: members.iter().map(|process| process.disk_written).sum(),
+        disk_total_read: members.iter().map(|process| process.disk_total_read).sum(),
+        disk_total_written: members
+            .iter()
+            .map(|process| process.disk_total_written)
+            .sum(),
+        disk_read_rate: members.iter().map(|process| process.disk_read_rate).sum(),
+        disk_write_rate: members.iter().map(|process| process.disk_write_rate).sum(),
+        threads: sum_optional(|process| process.threads.map(|threads| threads as u64))
+            .map(|threads| threads as usize),
+        start_time: members
+            .iter()
+            .map(|process| process.start_time)
+            .min()
+            .unwrap_or(leader.start_time),
+        open_files: sum_optional(|process| process.open_files.map(u64::from))
+            .map(|files| files as u32),
+        open_files_limit: None,
+        swap: sum_optional(|process| process.swap),
+        nice: None,
+        ..(*leader).clone()
+    }
+}
+
+/// Row indices of processes whose name contains `query`, case-insensitively.
+fn search_matches(rows: &[ProcessRow<'_>], query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    rows.iter()
+        .enumerate()
+        .filter(|(_, row)| name_matches(&row.process, query))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn name_matches(process: &ProcessSnapshot, query: &str) -> bool {
+    process.name.to_lowercase().contains(&query.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use tui::{Terminal, backend::TestBackend, buffer::Buffer};
+
+    use super::*;
+    use crate::collector::{Collector, FileCollector};
+    use crate::util::{
+        CgroupSnapshot, CpuSnapshot, DiskSnapshot, FanSnapshot, InterfaceSnapshot,
+        KernelMemorySnapshot, MemorySnapshot, NumaNodeSnapshot, ProcessSnapshot, SensorSnapshot,
+        Snapshot, ThermalPressure,
+    };
+
+    /// Hands out the given snapshots in order, then repeats the last one.
+    /// Rates come from the fixtures so results don't depend on timing.
+    struct ScriptedCollector {
+        snapshots: Vec<Snapshot>,
+    }
+
+    impl Collector for ScriptedCollector {
+        fn collect(&mut self) -> Result<Snapshot, io::Error> {
+            if self.snapshots.len() > 1 {
+                Ok(self.snapshots.remove(0))
+            } else {
+                Ok(self.snapshots[0].clone())
+            }
+        }
+
+        fn provides_rates(&self) -> bool {
+            true
+        }
+
+        /// The fixture's firefox stands in for dashoxide.
+        fn own_pid(&self) -> Option<u32> {
+            Some(4242)
+        }
+
+        /// Only firefox's environment is readable, as if the others
+        /// belonged to another user.
+        fn environment(&mut self, pid: u32) -> Result<Vec<String>, io::Error> {
+            if pid == 4242 {
+                Ok(vec![
+                    "HOME=/home/user".to_string(),
+                    "API_TOKEN=abc123".to_string(),
+                ])
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "permission denied",
+                ))
+            }
+        }
+    }
+
+    fn process(pid: u32, name: &str, status: ProcessState) -> ProcessSnapshot {
+        ProcessSnapshot {
+            pid,
+            name: name.to_string(),
+            cpu_usage: 1.5,
+            memory: 64 * 1024 * 1024,
+            status,
+            threads: Some(3),
+            user: Some("alice".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn fixture() -> Snapshot {
+        Snapshot {
+            timestamp: 0,
+            cpu: CpuSnapshot {
+                usage: 42.0,
+                cores: vec![80.0, 2.0, 60.0, 1.0],
+            },
+            memory: MemorySnapshot {
+                used: 4_000_000_000,
+                total: 8_000_000_000,
+                swap_used: 0,
+                swap_total: 2_000_000_000,
+                swap_is_commit: false,
+            },
+            disks: vec![DiskSnapshot {
+                mount_point: "/data".to_string(),
+                device: "/dev/sda1".to_string(),
+                file_system: "ext4".to_string(),
+                total: 100_000_000_000,
+                available: 25_000_000_000,
+                ..Default::default()
+            }],
+            networks: vec![InterfaceSnapshot {
+                name: "eth0".to_string(),
+                rx_rate: 5 * 1024 * 1024,
+                tx_rate: 1024 * 1024,
+                total_received: 10_000_000,
+                total_transmitted: 2_000_000,
+                up: None,
+                mac_address: None,
+                mtu: None,
+                addresses: Vec::new(),
+            }],
+            processes: vec![
+                process(1, "init", ProcessState::Sleeping),
+                process(4242, "firefox", ProcessState::Running),
+                process(99, "defunct", ProcessState::Zombie),
+            ],
+            sensors: Vec::new(),
+            battery: None,
+            cgroup: None,
+            numa_nodes: Vec::new(),
+            kernel_memory: None,
+            kernel_counters: None,
+            fans: Vec::new(),
+            thermal_pressure: None,
+            processes_stale: false,
+            disks_stale: false,
+        }
+    }
+
+    /// A dashboard over `fixture()` with a few samples of history.
+    fn dashboard(view: DashboardView) -> Dashboard {
+        dashboard_with(Config {
+            tab: view,
+            ..Config::default()
+        })
+    }
+
+    fn dashboard_with(config: Config) -> Dashboard {
+        let collector = ScriptedCollector {
+            snapshots: vec![fixture()],
+        };
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        for _ in 0..3 {
+            state.update();
+        }
+        let interval = Arc::new(AtomicU64::new(1_000));
+        Dashboard::new(Arc::new(Mutex::new(state)), interval, &config)
+    }
+
+    /// Renders one frame and returns the screen as lines of text.
+    fn render(dashboard: &Dashboard) -> Vec<String> {
+        render_sized(dashboard, 120, 40)
+    }
+
+    fn render_sized(dashboard: &Dashboard, width: u16, height: u16) -> Vec<String> {
+        lines(&render_buffer_sized(dashboard, width, height))
+    }
+
+    /// Renders one frame and keeps the cells, for checking colors and
+    /// styles.
+    fn render_buffer(dashboard: &Dashboard) -> Buffer {
+        render_buffer_sized(dashboard, 120, 40)
+    }
+
+    fn render_buffer_sized(dashboard: &Dashboard, width: u16, height: u16) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| dashboard.draw(f)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn lines(buffer: &Buffer) -> Vec<String> {
+        buffer
+            .content
+            .chunks(usize::from(buffer.area.width))
+            .map(|row| row.iter().map(|cell| cell.symbol.as_str()).collect())
+            .collect()
+    }
+
+    /// The cell each occurrence of `text` starts at, top to bottom, at most
+    /// one per line.
+    fn cells_at<'a>(buffer: &'a Buffer, text: &str) -> Vec<&'a tui::buffer::Cell> {
+        lines(buffer)
+            .iter()
+            .enumerate()
+            .filter_map(|(y, line)| {
+                let x = line[..line.find(text)?].chars().count();
+                Some(buffer.get(x as u16, y as u16))
+            })
+            .collect()
+    }
+
+    fn contains(screen: &[String], text: &str) -> bool {
+        screen.iter().any(|line| line.contains(text))
+    }
+
+    #[test]
+    fn overview_shows_every_summary() {
+        let screen = render(&dashboard(DashboardView::Overview));
+        for title in [
+            "CPU Summary",
+            "Memory Summary",
+            "Disk Summary",
+            "Network Summary",
+        ] {
+            assert!(contains(&screen, title), "missing {title}");
+        }
+        assert!(contains(&screen, "CPU Usage: 42.0%"));
+        assert!(contains(&screen, "Cores: 4"));
+        assert!(contains(&screen, "Memory Usage: 50.0%"));
+        assert!(contains(&screen, "OOM risk: low"));
+        assert!(contains(&screen, "Usage: 75.0%"));
+        assert!(contains(&screen, "dashoxide itself: 1.5% CPU, 64.00 MB"));
+    }
+
+    #[test]
+    fn overview_boxes_name_their_top_consumers() {
+        let dashboard = dashboard(DashboardView::Overview);
+        {
+            let mut state = dashboard.state();
+            state.snapshot.processes[1].cpu_usage = 37.5;
+            state.snapshot.processes[2].memory = 512 * 1024 * 1024;
+        }
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Top: firefox 37.5%"));
+        assert!(contains(&screen, "Top: defunct 512.00 MB"));
+        assert!(contains(&screen, "Fullest: /data 75.0%"));
+        assert!(contains(&screen, "Busiest: eth0 6.0 MB/s"));
+
+        let dashboard = dashboard_with(Config {
+            overview_top: 2,
+            ..Config::default()
+        });
+        dashboard.state().snapshot.processes[1].cpu_usage = 37.5;
+        assert!(contains(
+            &render(&dashboard),
+            "Top: firefox 37.5%, init 1.5%"
+        ));
+
+        let dashboard = dashboard_with(Config {
+            overview_top: 0,
+            ..Config::default()
+        });
+        let screen = render(&dashboard);
+        assert!(!contains(&screen, "Top:"));
+        assert!(!contains(&screen, "Busiest:"));
+    }
+
+    #[test]
+    fn overview_summaries_fill_the_view() {
+        let screen = render(&dashboard(DashboardView::Overview));
+        let status = screen
+            .iter()
+            .position(|line| line.contains("Press 'q' to quit"))
+            .unwrap();
+        assert!(screen[status - 1].trim_start().starts_with('└'));
+    }
+
+    #[test]
+    fn overview_network_labels_match_directions() {
+        let screen = render(&dashboard(DashboardView::Overview));
+        let down = screen.iter().find(|line| line.contains("Down:")).unwrap();
+        let up = screen.iter().find(|line| line.contains("Up:")).unwrap();
+        assert!(down.contains("5.0 MB/s"), "{down}");
+        assert!(up.contains("1.0 MB/s"), "{up}");
+    }
+
+    #[test]
+    fn cpu_view_shows_usage_and_cores() {
+        let screen = render(&dashboard(DashboardView::Cpu));
+        assert!(contains(&screen, "cpu0 80.0%"));
+        assert!(contains(&screen, "cpu3 1.0%"));
+    }
+
+    #[test]
+    fn reset_clears_charts_until_new_samples_arrive() {
+        let mut dashboard = dashboard(DashboardView::Cpu);
+        dashboard.handle_input(KeyCode::Char('R'));
+        assert!(contains(&render(&dashboard), "Collecting data..."));
+
+        dashboard.system_state.lock().unwrap().update();
+        assert!(!contains(&render(&dashboard), "Collecting data..."));
+    }
+
+    #[test]
+    fn cpu_temperature_is_shown_and_charted_when_a_sensor_exists() {
+        let dashboard = dashboard(DashboardView::Cpu);
+        assert!(!contains(&render(&dashboard), "°C"));
+
+        {
+            let mut state = dashboard.state();
+            state.snapshot.sensors = vec![SensorSnapshot {
+                label: "coretemp Package id 0".to_string(),
+                temperature: Some(61.0),
+            }];
+            state.cpu_temperature_history = vec![Some(58.0), None, Some(61.0)];
+        }
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "CPU Usage: 1.7/4.0 cores (42.0%) | 61°C"));
+        assert!(contains(&screen, "Usage (%) / °C"));
+
+        let buffer = render_buffer(&dashboard);
+        assert!(buffer.content.iter().any(|cell| cell.fg == Color::LightRed));
+    }
+
+    #[test]
+    fn gpu_temperature_fans_and_thermal_pressure_join_the_cpu_text() {
+        let dashboard = dashboard(DashboardView::Cpu);
+        {
+            let mut state = dashboard.state();
+            state.snapshot.sensors = vec![SensorSnapshot {
+                label: "SMC GPU proximity".to_string(),
+                temperature: Some(47.0),
+            }];
+            state.snapshot.fans = vec![
+                FanSnapshot {
+                    label: "Fan 0".to_string(),
+                    rpm: 1800.0,
+                },
+                FanSnapshot {
+                    label: "Fan 1".to_string(),
+                    rpm: 2100.4,
+                },
+            ];
+            state.snapshot.thermal_pressure = Some(ThermalPressure::Moderate);
+        }
+        assert!(contains(
+            &render(&dashboard),
+            "(42.0%) | GPU 47°C | fans 1800/2100 rpm | thermal pressure moderate"
+        ));
+    }
+
+    #[test]
+    fn core_overlay_draws_each_core_behind_the_average() {
+        let mut dashboard = dashboard(DashboardView::Cpu);
+        dashboard.rich_colors = true;
+        dashboard.handle_input(KeyCode::Char('o'));
+        let buffer = render_buffer(&dashboard);
+        assert!(
+            buffer
+                .content
+                .iter()
+                .any(|cell| CORE_COLORS.contains(&cell.fg))
+        );
+        assert!(contains(&render(&dashboard), "cores overlaid"));
+    }
+
+    #[test]
+    fn core_overlay_needs_a_rich_palette() {
+        let mut dashboard = dashboard(DashboardView::Cpu);
+        dashboard.rich_colors = false;
+        dashboard.handle_input(KeyCode::Char('o'));
+        let screen = render(&dashboard);
+        assert!(!contains(&screen, "overlay"));
+        assert!(contains(&screen, "CPU History"));
+    }
+
+    #[test]
+    fn cpu_view_collapses_idle_cores() {
+        let mut dashboard = dashboard(DashboardView::Cpu);
+        dashboard.hide_idle_cores = true;
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "cpu2 60.0%"));
+        assert!(!contains(&screen, "cpu1"));
+        assert!(contains(&screen, "2 idle cores"));
+    }
+
+    #[test]
+    fn swap_is_labelled_by_the_data_not_the_build() {
+        let dashboard = dashboard(DashboardView::Memory);
+        assert!(contains(&render(&dashboard), "Swap Usage"));
+        dashboard.state().snapshot.memory.swap_is_commit = true;
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Commit Usage"));
+        assert!(!contains(&screen, "Swap Usage"));
+    }
+
+    #[test]
+    fn narrow_screens_get_a_one_line_tab_bar() {
+        let dashboard = dashboard(DashboardView::Cpu);
+        let screen = render_sized(&dashboard, 84, 30);
+        assert!(
+            screen[1].starts_with(" CPU [2/9] - System Monitor"),
+            "{}",
+            screen[1]
+        );
+        assert!(!contains(&screen, "Overview"));
+        assert!(screen[2].starts_with(" ┌CPU Details"), "{}", screen[2]);
+
+        let screen = render_sized(&dashboard, 85, 30);
+        assert!(contains(&screen, "│ Overview │ CPU │"));
+    }
+
+    #[test]
+    fn minimized_view_fills_the_screen() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Cpu,
+            minimized: true,
+            ..Config::default()
+        });
+        let screen = render(&dashboard);
+        assert!(screen[0].starts_with("┌CPU Details"), "{}", screen[0]);
+        assert!(!contains(&screen, "Overview"));
+        assert!(!contains(&screen, "to quit"));
+
+        dashboard.handle_input(KeyCode::Char('m'));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Overview"));
+        assert!(contains(&screen, "to quit"));
+    }
+
+    #[test]
+    fn debug_overlay_shows_timings() {
+        let mut dashboard = dashboard(DashboardView::Overview);
+        assert!(!contains(&render(&dashboard), "lock "));
+
+        dashboard.handle_input(KeyCode::F(12));
+        dashboard.timings.frame_drawn(Instant::now());
+        dashboard.state().update_duration = Duration::from_millis(12);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "frame "));
+        assert!(contains(&screen, "ms held"));
+        assert!(contains(&screen, "update  12.00 ms"));
+    }
+
+    #[test]
+    fn processes_sort_by_age_with_a_start_timeline() {
+        let dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            process_columns: vec![ProcessColumn::Pid, ProcessColumn::Name, ProcessColumn::Age],
+            process_sort: ProcessColumn::Age,
+            ..Config::default()
+        });
+        {
+            let mut state = dashboard.state();
+            state.snapshot.timestamp = 10_000;
+            for (process, start_time) in state.snapshot.processes.iter_mut().zip([0, 9_970, 9_000])
+            {
+                process.start_time = start_time;
+            }
+        }
+        let screen = render(&dashboard);
+        let row = |name| screen.iter().position(|line| line.contains(name)).unwrap();
+        assert!(row("firefox") < row("defunct") && row("defunct") < row("init"));
+        assert!(screen[row("firefox")].contains("30s ago"));
+        assert!(screen[row("init")].contains("2h ago"));
+        assert!(contains(
+            &screen,
+            "Started per minute, last hour (1 in the last minute)"
+        ));
+    }
+
+    #[test]
+    fn many_cores_are_paged() {
+        let mut dashboard = dashboard(DashboardView::Cpu);
+        dashboard.state().snapshot.cpu.cores = vec![10.0; 128];
+        let screen = render(&dashboard);
+        let pages = dashboard.core_pages.get();
+        assert!(pages > 1);
+        assert!(contains(
+            &screen,
+            &format!("page 1/{pages}, Up/Down to scroll")
+        ));
+        assert!(contains(&screen, "cpu0 10.0%"));
+        assert!(!contains(&screen, "cpu127 "));
+
+        for _ in 0..pages + 3 {
+            dashboard.apply(Action::CursorDown);
+        }
+        assert_eq!(dashboard.core_page, pages - 1, "stops at the last page");
+        let screen = render(&dashboard);
+        assert!(contains(&screen, &format!("page {pages}/{pages}")));
+        assert!(contains(&screen, "cpu127 10.0%"));
+        assert!(!contains(&screen, "cpu0 "));
+    }
+
+    #[test]
+    fn wrap_around_is_configurable() {
+        let mut dashboard = dashboard(DashboardView::Overview);
+        dashboard.apply(Action::PrevTab);
+        assert_eq!(dashboard.current_view, DashboardView::Compare);
+        dashboard.apply(Action::NextTab);
+        assert_eq!(dashboard.current_view, DashboardView::Overview);
+
+        dashboard.set_view(DashboardView::Processes);
+        dashboard.apply(Action::CursorDown);
+        dashboard.apply(Action::CursorUp);
+        assert_eq!(dashboard.selected_pid, Some(1), "stays on the first row");
+
+        let mut dashboard = dashboard_with(Config {
+            wrap_tabs: false,
+            wrap_lists: true,
+            ..Config::default()
+        });
+        dashboard.apply(Action::PrevTab);
+        assert_eq!(dashboard.current_view, DashboardView::Overview);
+        dashboard.set_view(DashboardView::Compare);
+        dashboard.apply(Action::NextTab);
+        assert_eq!(dashboard.current_view, DashboardView::Compare);
+
+        dashboard.set_view(DashboardView::Processes);
+        dashboard.apply(Action::CursorDown);
+        dashboard.apply(Action::CursorUp);
+        assert_eq!(dashboard.selected_pid, Some(4242), "wraps to the last row");
+    }
+
+    #[test]
+    fn ghost_keeps_the_charts_behind_the_live_lines() {
+        let mut dashboard = dashboard(DashboardView::Memory);
+        dashboard.state().memory_history = vec![(2, 8); 10];
+        assert!(!contains(&render(&dashboard), "Before"));
+
+        dashboard.handle_input(KeyCode::Char('G'));
+        dashboard.state().memory_history = vec![(6, 8); 10];
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Charts kept as a ghost"));
+        assert!(contains(&screen, "Before"));
+        let ghost = dashboard.ghost.as_ref().unwrap();
+        assert_eq!(ghost.memory_history[0], (2, 8), "a copy, not the live data");
+
+        dashboard.handle_input(KeyCode::Char('G'));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Ghost cleared"));
+        assert!(!contains(&screen, "Before"));
+    }
+
+    #[test]
+    fn memory_view_shows_ram_and_swap() {
+        let screen = render(&dashboard(DashboardView::Memory));
+        assert!(contains(&screen, "OOM risk: low"));
+        assert!(contains(&screen, "Memory History"));
+        assert!(contains(&screen, "Swap"));
+    }
+
+    #[test]
+    fn kernel_memory_panel_can_be_toggled() {
+        let mut dashboard = dashboard(DashboardView::Memory);
+        assert!(!contains(&render(&dashboard), "Kernel Memory"));
+
+        dashboard.handle_input(KeyCode::Char('K'));
+        assert!(contains(&render(&dashboard), "Only reported on Linux"));
+
+        dashboard.state().snapshot.kernel_memory = Some(KernelMemorySnapshot {
+            slab_reclaimable: 300 * 1_048_576,
+            slab_unreclaimable: 100 * 1_048_576,
+            page_tables: 40 * 1_048_576,
+            kernel_stack: 16 * 1_048_576,
+            dirty: 2 * 1_048_576,
+            writeback: 0,
+        });
+        let screen = render(&dashboard);
+        assert!(contains(
+            &screen,
+            "Slab: 400.00 MiB (300.00 MiB reclaimable, 100.00 MiB not)"
+        ));
+        assert!(contains(&screen, "Page tables: 40.00 MiB"));
+        assert!(contains(&screen, "Dirty: 2.00 MiB"));
+        let text = dashboard.view_text();
+        assert!(text.contains("Slab: 400.00 MiB (300.00 MiB reclaimable, 100.00 MiB not)\n"));
+        assert!(text.contains("Page tables: 40.00 MiB   Kernel stacks: "));
+        assert!(text.contains("Dirty: 2.00 MiB   Writeback: 0.00 MiB\n"));
+    }
+
+    #[test]
+    fn numa_nodes_are_listed_on_multi_node_machines() {
+        let dashboard = dashboard(DashboardView::Memory);
+        let node = |id, free, cpus: Vec<usize>| NumaNodeSnapshot {
+            id,
+            total: 4 * 1024 * 1024 * 1024,
+            free,
+            cpus,
+        };
+        dashboard.state().snapshot.numa_nodes = vec![node(0, 0, vec![0, 1])];
+        assert!(
+            !contains(&render(&dashboard), "NUMA"),
+            "one node is no NUMA"
+        );
+
+        dashboard
+            .state()
+            .snapshot
+            .numa_nodes
+            .push(node(1, 3 * 1024 * 1024 * 1024, vec![2, 3]));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "NUMA Nodes (2)"));
+        let row = |name| screen.iter().find(|line| line.contains(name)).unwrap();
+        assert!(row("node0").contains("4.00 GiB / 4.00 GiB") && row("node0").contains("41.0%"));
+        assert!(row("node1").contains("25.0%") && row("node1").contains("30.5%"));
+        let text = dashboard.view_text();
+        let line = |name| text.lines().find(|line| line.starts_with(name)).unwrap();
+        assert!(line("node0").contains("4.00 GiB / 4.00 GiB") && line("node0").contains("41.0%"));
+        assert!(line("node1").contains("25.0%") && line("node1").contains("30.5%"));
+    }
+
+    #[test]
+    fn container_limits_are_shown_next_to_host_totals() {
+        let dashboard = dashboard(DashboardView::Overview);
+        dashboard.system_state.lock().unwrap().snapshot.cgroup = Some(CgroupSnapshot {
+            version: 2,
+            memory_limit: Some(4_000_000_000),
+            memory_used: Some(3_800_000_000),
+            cpu_limit: Some(1.5),
+        });
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Memory Usage: 50.0%"));
+        assert!(contains(&screen, "Container: 95.0% of 4.00 GB limit"));
+        assert!(contains(&screen, "Container limit: 1.5 cores"));
+
+        let mut dashboard = dashboard;
+        dashboard.current_view = DashboardView::Memory;
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Container Memory (cgroup v2 limit)"));
+        assert!(contains(&screen, "(95.0%)"));
+        assert!(contains(&screen, "Swap"));
+    }
+
+    #[test]
+    fn unlimited_cgroups_fall_back_to_host_totals() {
+        let dashboard = dashboard(DashboardView::Memory);
+        dashboard.system_state.lock().unwrap().snapshot.cgroup = Some(CgroupSnapshot {
+            version: 1,
+            memory_limit: None,
+            memory_used: Some(3_800_000_000),
+            cpu_limit: Some(2.0),
+        });
+        let screen = render(&dashboard);
+        assert!(!contains(&screen, "Container Memory"));
+        assert!(contains(&screen, "OOM risk: low"));
+    }
+
+    #[test]
+    fn remapped_keys_drive_actions_and_hints() {
+        let mut keys = KeyMap::default();
+        keys.apply_config("[keys]\nquit = \"z\"\ntoggle_disk_details = \"d\"")
+            .unwrap();
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Disk,
+            keys,
+            ..Config::default()
+        });
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Press 'z' to quit"));
+        assert!(contains(&screen, "'d' to hide type and flags"));
+
+        dashboard.handle_input(KeyCode::Char('t'));
+        assert!(dashboard.show_disk_details);
+        dashboard.handle_input(KeyCode::Char('d'));
+        assert!(!dashboard.show_disk_details);
+        dashboard.handle_input(KeyCode::Char('q'));
+        assert!(!dashboard.should_quit);
+        dashboard.handle_input(KeyCode::Char('z'));
+        assert!(dashboard.should_quit);
+    }
+
+    #[test]
+    fn keys_mean_different_actions_in_each_mode() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        assert_eq!(dashboard.action_for(KeyCode::Char('q')), Some(Action::Quit));
+        assert_eq!(dashboard.action_for(KeyCode::Left), Some(Action::PrevTab));
+
+        dashboard.apply(Action::Search);
+        assert_eq!(
+            dashboard.action_for(KeyCode::Char('q')),
+            Some(Action::InsertChar('q'))
+        );
+        assert_eq!(dashboard.action_for(KeyCode::Enter), Some(Action::Submit));
+        dashboard.apply(Action::Cancel);
+
+        dashboard.mode = InputMode::ConfirmKill(vec![1]);
+        assert_eq!(
+            dashboard.action_for(KeyCode::Char('y')),
+            Some(Action::Confirm)
+        );
+        assert_eq!(
+            dashboard.action_for(KeyCode::Char('q')),
+            Some(Action::Cancel)
+        );
+        dashboard.apply(Action::Cancel);
+
+        dashboard.set_view(DashboardView::Cpu);
+        dashboard.apply(Action::ChartCursor);
+        assert_eq!(
+            dashboard.action_for(KeyCode::Left),
+            Some(Action::CursorBack)
+        );
+        assert_eq!(dashboard.action_for(KeyCode::Char('q')), Some(Action::Quit));
+    }
+
+    #[test]
+    fn every_mode_returns_to_normal_on_escape() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        for key in ['/', 'f', ':', '?'] {
+            dashboard.handle_input(KeyCode::Char(key));
+            assert_ne!(dashboard.mode, InputMode::Normal, "{key}");
+            dashboard.handle_input(KeyCode::Char('x'));
+            dashboard.handle_input(KeyCode::Esc);
+            assert_eq!(dashboard.mode, InputMode::Normal, "{key}");
+        }
+        assert!(dashboard.process_filter.is_empty());
+        assert!(dashboard.search_query.is_empty());
+        assert!(!dashboard.should_quit);
+    }
+
+    #[test]
+    fn filter_hides_processes_that_do_not_match() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        for key in "/FIRE".chars() {
+            dashboard.handle_input(KeyCode::Char(key));
+        }
+        assert!(contains(&render(&dashboard), "Filter: FIRE_"));
+        dashboard.handle_input(KeyCode::Enter);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "(1 of 3 processes, filter 'FIRE')"));
+        assert!(!contains(&screen, "init"));
+    }
+
+    #[test]
+    fn commands_switch_tabs_and_report_mistakes() {
+        let mut dashboard = dashboard(DashboardView::Overview);
+        let run = |dashboard: &mut Dashboard, command: &str| {
+            dashboard.handle_input(KeyCode::Char(':'));
+            for c in command.chars() {
+                dashboard.handle_input(KeyCode::Char(c));
+            }
+            dashboard.handle_input(KeyCode::Enter);
+        };
+        run(&mut dashboard, "tab processes");
+        assert_eq!(dashboard.current_view, DashboardView::Processes);
+        run(&mut dashboard, "sort memory");
+        assert_eq!(dashboard.process_sort, ProcessColumn::Memory);
+        run(&mut dashboard, "fly");
+        assert!(contains(&render(&dashboard), ":fly: unknown command 'fly'"));
+        run(&mut dashboard, "q");
+        assert!(dashboard.should_quit);
+    }
+
+    #[test]
+    fn help_lists_the_current_bindings() {
+        let mut keys = KeyMap::default();
+        keys.apply_config("[keys]\nquit = \"z\"").unwrap();
+        let mut dashboard = dashboard_with(Config {
+            keys,
+            ..Config::default()
+        });
+        dashboard.handle_input(KeyCode::Char('?'));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "z  quit"));
+        assert!(contains(&screen, "/  filter"));
+        dashboard.handle_input(KeyCode::Char('z'));
+        assert!(!dashboard.should_quit);
+        assert_eq!(dashboard.mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn switcher_jumps_to_the_chosen_item() {
+        use crossterm::event::{KeyEvent, KeyModifiers};
+
+        let mut dashboard = dashboard(DashboardView::Overview);
+        let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        let go_to = |dashboard: &mut Dashboard, query: &str| {
+            dashboard.handle_input(ctrl_p);
+            for c in query.chars() {
+                dashboard.handle_input(KeyCode::Char(c));
+            }
+            let screen = render(dashboard);
+            dashboard.handle_input(KeyCode::Enter);
+            screen
+        };
+
+        let screen = go_to(&mut dashboard, "frfx");
+        assert!(contains(&screen, "> frfx_"));
+        assert!(contains(&screen, "proc firefox (4242)"));
+        assert_eq!(dashboard.current_view, DashboardView::Processes);
+        assert_eq!(dashboard.selected_pid, Some(4242));
+
+        go_to(&mut dashboard, "eth");
+        assert_eq!(dashboard.current_view, DashboardView::Network);
+        let buffer = render_buffer(&dashboard);
+        let table_row = cells_at(&buffer, "eth0").pop().unwrap();
+        assert_eq!(table_row.bg, Color::DarkGray);
+
+        dashboard.handle_input(KeyCode::Char('p'));
+        assert_eq!(dashboard.mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn actions_apply_without_a_terminal() {
+        let mut dashboard = dashboard(DashboardView::Cpu);
+        dashboard.apply(Action::ChartCursor);
+        dashboard.apply(Action::CursorBack);
+        dashboard.apply(Action::CursorBack);
+        dashboard.apply(Action::CursorForward);
+        assert_eq!(dashboard.chart_cursor, Some(1));
+        dashboard.apply(Action::ChartCursor);
+        assert_eq!(dashboard.chart_cursor, None);
+
+        dashboard.set_view(DashboardView::Processes);
+        dashboard.apply(Action::Search);
+        for c in "cargx".chars() {
+            dashboard.apply(Action::InsertChar(c));
+        }
+        dashboard.apply(Action::DeleteChar);
+        dashboard.apply(Action::Submit);
+        assert_eq!(dashboard.search_query, "carg");
+        assert_eq!(dashboard.mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn interval_keys_scale_the_shared_interval_within_bounds() {
+        let mut dashboard = dashboard(DashboardView::Cpu);
+        dashboard.handle_input(KeyCode::Char('<'));
+        assert_eq!(dashboard.interval.load(Ordering::Relaxed), 2_000);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Sampling every 2000 ms"));
+        let span = 2 * dashboard.state().cpu_history.len();
+        assert!(
+            contains(&screen, &format!("{span}s ago")),
+            "axis should span the samples taken, at 2s each"
+        );
+
+        for _ in 0..10 {
+            dashboard.handle_input(KeyCode::Char('>'));
+        }
+        assert_eq!(
+            dashboard.interval.load(Ordering::Relaxed),
+            MIN_INTERVAL.as_millis() as u64
+        );
+    }
+
+    #[test]
+    fn status_bar_shows_sample_age_and_clock_on_the_right() {
+        let screen = render(&dashboard(DashboardView::Overview));
+        let status = screen[screen.len() - 2].trim_end();
+        assert!(status.starts_with(" Press 'q' to quit"), "{status}");
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let zone = format_local_time(now.as_secs()).split_off(9);
+        assert!(status.ends_with(&format!(" {zone}")), "{status}");
+        assert!(status.contains(" updated "), "{status}");
+    }
+
+    #[test]
+    fn stale_data_fades_until_paused() {
+        let mut dashboard = dashboard(DashboardView::Overview);
+        let buffer = render_buffer(&dashboard);
+        assert!(
+            !buffer
+                .content
+                .iter()
+                .any(|cell| cell.modifier.contains(Modifier::DIM))
+        );
+
+        // At 1 ms samples, a sample 50 ms old is a long stall.
+        dashboard.interval.store(1, Ordering::Relaxed);
+        dashboard.state().last_sample_at -= Duration::from_millis(50);
+        let buffer = render_buffer(&dashboard);
+        let title = buffer.get(2, 1);
+        assert_eq!(title.fg, Color::DarkGray);
+        assert!(title.modifier.contains(Modifier::DIM));
+        let screen = render(&dashboard);
+        assert!(screen[screen.len() - 2].contains("STALE, updated"));
+
+        dashboard.apply(Action::Pause);
+        let screen = render(&dashboard);
+        assert!(!screen[screen.len() - 2].contains("STALE"));
+    }
+
+    #[test]
+    fn gauge_labels_follow_the_configured_style() {
+        let screen = render(&dashboard_with(Config {
+            tab: DashboardView::Memory,
+            gauge_label: GaugeLabel::Percent,
+            gauge_precision: Some(0),
+            precision: "percent=3".parse().unwrap(),
+            ..Config::default()
+        }));
+        assert!(
+            screen
+                .iter()
+                .any(|line| line.trim_matches(['│', ' ']) == "50%")
+        );
+        let screen = render(&dashboard_with(Config {
+            tab: DashboardView::Memory,
+            gauge_label: GaugeLabel::Percent,
+            precision: "percent=3".parse().unwrap(),
+            ..Config::default()
+        }));
+        assert!(
+            screen
+                .iter()
+                .any(|line| line.trim_matches(['│', ' ']) == "50.000%")
+        );
+        assert!(!contains(&screen, "GiB"));
+
+        let screen = render(&dashboard_with(Config {
+            tab: DashboardView::Disk,
+            gauge_label: GaugeLabel::Absolute,
+            ..Config::default()
+        }));
+        assert!(contains(&screen, "75.0/100.0 GB"));
+    }
+
+    #[test]
+    fn crashed_collector_leaves_the_last_data_on_screen() {
+        let dashboard = dashboard(DashboardView::Cpu);
+        let state = Arc::clone(&dashboard.system_state);
+        let _ = std::thread::spawn(move || {
+            let _guard = state.lock().unwrap();
+            panic!("collector crashed");
+        })
+        .join();
+        assert!(dashboard.system_state.is_poisoned());
+
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "CPU Usage: 1.7/4.0 cores (42.0%)"));
+        assert!(contains(&screen, "Collector crashed, data is frozen"));
+    }
+
+    #[test]
+    fn missing_disks_and_interfaces_are_called_out() {
+        let mut dashboard = dashboard(DashboardView::Overview);
+        {
+            let mut state = dashboard.state();
+            state.snapshot.disks.clear();
+            state.snapshot.networks.clear();
+        }
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "No disks detected"));
+        assert!(contains(&screen, "No network interfaces detected"));
+
+        dashboard.set_view(DashboardView::Disk);
+        assert!(contains(&render(&dashboard), "No disks detected"));
+        dashboard.set_view(DashboardView::Network);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "No network interfaces detected"));
+        assert!(!contains(&screen, "Not supported"));
+    }
+
+    #[test]
+    fn disk_view_lists_mounts() {
+        let screen = render(&dashboard(DashboardView::Disk));
+        let row = screen.iter().find(|line| line.contains("/data")).unwrap();
+        assert!(row.contains("75.0%"), "{row}");
+    }
+
+    #[test]
+    fn fullest_disks_come_first() {
+        let mut dashboard = dashboard(DashboardView::Disk);
+        let disk = |mount_point: &str, available| DiskSnapshot {
+            mount_point: mount_point.to_string(),
+            file_system: "ext4".to_string(),
+            total: 100_000_000_000,
+            available,
+            ..Default::default()
+        };
+        dashboard.state().snapshot.disks = vec![
+            disk("/boot", 90_000_000_000),
+            disk("/var", 2_000_000_000),
+            disk("/home", 50_000_000_000),
+        ];
+        let order = |screen: &[String]| -> Vec<usize> {
+            ["/boot", "/var", "/home"]
+                .iter()
+                .map(|mount| screen.iter().position(|l| l.contains(mount)).unwrap())
+                .collect()
+        };
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "sorted by usage"));
+        let rows = order(&screen);
+        assert!(rows[1] < rows[2] && rows[2] < rows[0], "{rows:?}");
+
+        dashboard.handle_input(KeyCode::Char('s'));
+        dashboard.handle_input(KeyCode::Char('s'));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "sorted by mount"));
+        let rows = order(&screen);
+        assert!(rows[0] < rows[2] && rows[2] < rows[1], "{rows:?}");
+    }
+
+    #[test]
+    fn disk_io_chart_picks_a_device() {
+        let mut dashboard = dashboard(DashboardView::Disk);
+        let backup = DiskSnapshot {
+            mount_point: "/backup".to_string(),
+            device: "/dev/sdb1".to_string(),
+            available: 90_000_000_000,
+            ..dashboard.state().snapshot.disks[0].clone()
+        };
+        dashboard.state().snapshot.disks.push(backup);
+        assert!(contains(&render(&dashboard), "Disk I/O, all devices"));
+
+        dashboard.handle_input(KeyCode::Down);
+        assert!(contains(&render(&dashboard), "Disk I/O, /dev/sda1"));
+        dashboard.handle_input(KeyCode::Down);
+        assert!(contains(&render(&dashboard), "Disk I/O, /dev/sdb1"));
+        dashboard.handle_input(KeyCode::Down);
+        assert!(
+            contains(&render(&dashboard), "Disk I/O, /dev/sdb1"),
+            "stops at the last device"
+        );
+    }
+
+    #[test]
+    fn disk_type_and_flags_can_be_toggled() {
+        let mut dashboard = dashboard(DashboardView::Disk);
+        dashboard.state().snapshot.disks.push(DiskSnapshot {
+            mount_point: "/media/usb".to_string(),
+            device: "/dev/sr0".to_string(),
+            file_system: "iso9660".to_string(),
+            total: 1_000_000_000,
+            available: 0,
+            removable: true,
+            read_only: true,
+            ..Default::default()
+        });
+        let screen = render(&dashboard);
+        let usb = screen.iter().find(|l| l.contains("/media/usb")).unwrap();
+        assert!(
+            usb.contains("iso9660") && usb.contains("ro, removable"),
+            "{usb}"
+        );
+        let data = screen.iter().find(|l| l.contains("/data")).unwrap();
+        assert!(data.contains("ext4"), "{data}");
+
+        dashboard.handle_input(KeyCode::Char('t'));
+        let screen = render(&dashboard);
+        assert!(!contains(&screen, "iso9660"));
+        assert!(contains(&screen, "'t' to show type and flags"));
+    }
+
+    #[test]
+    fn pseudo_file_systems_are_hidden_until_toggled() {
+        let mut dashboard = dashboard(DashboardView::Disk);
+        dashboard.state().snapshot.disks.extend(
+            [("/dev/shm", "tmpfs"), ("/snap/core/1", "squashfs")].map(
+                |(mount_point, file_system)| DiskSnapshot {
+                    mount_point: mount_point.to_string(),
+                    file_system: file_system.to_string(),
+                    total: 1_000_000,
+                    ..Default::default()
+                },
+            ),
+        );
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "/data"));
+        assert!(!contains(&screen, "/dev/shm") && !contains(&screen, "/snap/core"));
+        assert!(contains(&screen, "'v' to show 2 pseudo file systems"));
+
+        dashboard.handle_input(KeyCode::Char('v'));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "/dev/shm") && contains(&screen, "/snap/core"));
+        assert!(contains(&screen, "'v' to hide pseudo file systems"));
+    }
+
+    #[test]
+    fn disk_table_ends_with_totals() {
+        let dashboard = dashboard(DashboardView::Disk);
+        dashboard
+            .system_state
+            .lock()
+            .unwrap()
+            .snapshot
+            .disks
+            .push(DiskSnapshot {
+                mount_point: "/boot".to_string(),
+                device: "/dev/sdb1".to_string(),
+                file_system: "vfat".to_string(),
+                total: 1_000_000_000,
+                available: 1_000_000_000,
+                ..Default::default()
+            });
+        let screen = render(&dashboard);
+        let boot = screen.iter().position(|l| l.contains("/boot")).unwrap();
+        let total = &screen[boot + 1];
+        assert!(total.contains("Total"), "{total}");
+        assert!(total.contains("101.00 GB"), "{total}");
+        assert!(total.contains("74.3%"), "{total}");
+    }
+
+    #[test]
+    fn disk_totals_skip_pseudo_file_systems_and_repeated_devices() {
+        let with_mounts = |disk_total_all| {
+            let dashboard = dashboard_with(Config {
+                tab: DashboardView::Disk,
+                disk_total_all,
+                ..Config::default()
+            });
+            let mut state = dashboard.state();
+            let data = state.snapshot.disks[0].clone();
+            state.snapshot.disks.extend([
+                DiskSnapshot {
+                    mount_point: "/srv/data".to_string(),
+                    ..data.clone()
+                },
+                DiskSnapshot {
+                    mount_point: "/run".to_string(),
+                    device: "tmpfs".to_string(),
+                    file_system: "tmpfs".to_string(),
+                    ..data
+                },
+            ]);
+            drop(state);
+            dashboard
+        };
+
+        let screen = render(&with_mounts(false));
+        assert!(contains(&screen, "/srv/data"));
+        let total_row = |screen: &[String]| {
+            screen
+                .iter()
+                .find(|l| l.contains("Total") && l.contains("GB"))
+                .unwrap()
+                .clone()
+        };
+        let total = total_row(&screen);
+        assert!(total.contains("100.00 GB"), "{total}");
+
+        let screen = render(&with_mounts(true));
+        let total = total_row(&screen);
+        assert!(total.contains("300.00 GB"), "{total}");
+    }
+
+    #[test]
+    fn network_view_shows_rates_per_interface() {
+        let screen = render(&dashboard(DashboardView::Network));
+        let row = screen.iter().find(|line| line.contains("eth0")).unwrap();
+        let rx = row.find("5.0 MB/s").unwrap();
+        let tx = row.find("1.0 MB/s").unwrap();
+        assert!(rx < tx, "{row}");
+    }
+
+    #[test]
+    fn link_details_show_state_and_addresses() {
+        let mut dashboard = dashboard(DashboardView::Network);
+        {
+            let mut state = dashboard.state();
+            let eth0 = &mut state.snapshot.networks[0];
+            eth0.up = Some(false);
+            eth0.mtu = Some(1500);
+            eth0.mac_address = Some("52:54:00:12:34:56".to_string());
+            eth0.addresses = vec!["10.0.0.2/24".to_string()];
+        }
+        assert!(!contains(&render(&dashboard), "10.0.0.2/24"));
+
+        dashboard.apply(Action::ToggleLinkDetails);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "'l' to hide link details"));
+        let row = screen.iter().find(|line| line.contains("eth0")).unwrap();
+        for text in ["down", "1500", "52:54:00:12:34:56", "10.0.0.2/24"] {
+            assert!(row.contains(text), "{text} in {row}");
+        }
+    }
+
+    #[test]
+    fn privacy_mode_hides_names_and_addresses_on_screen_only() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.handle_input(KeyCode::Char('P'));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "System Monitor [private]"));
+        assert!(contains(&screen, "proc-4242"));
+        assert!(!contains(&screen, "firefox"));
+        assert_eq!(dashboard.state().snapshot.processes[1].name, "firefox");
+
+        dashboard.set_view(DashboardView::Network);
+        dashboard.apply(Action::ToggleLinkDetails);
+        dashboard.state().snapshot.networks[0].addresses = vec!["10.0.0.2/24".to_string()];
+        let screen = render(&dashboard);
+        assert!(!contains(&screen, "10.0.0.2"));
+        assert!(contains(&screen, "(hidden)"));
+
+        dashboard.handle_input(KeyCode::Char('P'));
+        assert!(contains(&render(&dashboard), "10.0.0.2/24"));
+    }
+
+    #[test]
+    fn interface_filter_limits_the_network_table() {
+        let mut dashboard = dashboard(DashboardView::Network);
+        let mut veth = dashboard.state().snapshot.networks[0].clone();
+        veth.name = "veth1a2b".to_string();
+        dashboard.state().snapshot.networks.push(veth);
+        assert!(contains(&render(&dashboard), "veth1a2b"));
+
+        dashboard.run_command("net eth*");
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Network Interfaces matching 'eth*'"));
+        assert!(contains(&screen, "eth0"));
+        assert!(!contains(&screen, "veth1a2b"));
+        assert_eq!(dashboard.state().interface_filter, "eth*");
+
+        dashboard.run_command("net");
+        assert!(contains(&render(&dashboard), "veth1a2b"));
+    }
+
+    #[test]
+    fn loopback_is_listed_but_left_out_of_the_total() {
+        let dashboard = dashboard(DashboardView::Network);
+        let mut lo = dashboard.state().snapshot.networks[0].clone();
+        lo.name = "lo".to_string();
+        lo.rx_rate = 50 * 1024 * 1024;
+        dashboard.state().snapshot.networks.push(lo);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "50.0 MB/s"), "lo keeps its row");
+        let total = screen
+            .iter()
+            .find(|line| line.contains("Total (excl. virtual)"))
+            .unwrap();
+        assert!(total.contains("5.0 MB/s"), "{total}");
+
+        dashboard.state().network_total_all = true;
+        let screen = render(&dashboard);
+        let total = screen
+            .iter()
+            .find(|line| line.contains("Total") && line.contains("MB/s"))
+            .unwrap();
+        assert!(total.contains("55.0 MB/s"), "{total}");
+    }
+
+    #[test]
+    fn network_rates_can_be_shown_in_bits() {
+        let mut dashboard = dashboard(DashboardView::Network);
+        dashboard.apply(Action::ToggleBits);
+        let screen = render(&dashboard);
+        let row = screen.iter().find(|line| line.contains("eth0")).unwrap();
+        assert!(
+            row.contains("41.9 Mb/s") && row.contains("8.4 Mb/s"),
+            "{row}"
+        );
+        assert!(contains(&screen, "Network History (Kb/s)"));
+        assert!(!contains(&screen, "MB/s"));
+
+        assert_eq!(format_bit_rate(0, &Precision::default()), "0 b/s");
+        assert_eq!(
+            format_bit_rate(125_000_000, &Precision::default()),
+            "1.0 Gb/s"
+        );
+    }
+
+    #[test]
+    fn processes_in_disk_wait_are_counted_and_can_be_listed_alone() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.state().snapshot.processes[1].status = ProcessState::DiskWait;
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "1 in disk wait, 'D' to list them"));
+
+        dashboard.apply(Action::ToggleStuck);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "(1 of 3 processes, disk wait only"));
+        assert!(contains(&screen, "firefox"));
+        assert!(!contains(&screen, "defunct"));
+
+        dashboard.jump_to(Target::Process(1));
+        assert!(
+            !dashboard.stuck_only,
+            "jumping to a running process shows all"
+        );
+    }
+
+    #[test]
+    fn own_process_can_be_hidden() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            hide_self: true,
+            ..Config::default()
+        });
+        let screen = render(&dashboard);
+        assert!(contains(
+            &screen,
+            "(2 of 3 processes, dashoxide hidden, 'H' to show)"
+        ));
+        assert!(!contains(&screen, "firefox"));
+
+        dashboard.jump_to(Target::Process(4242));
+        assert!(!dashboard.hide_self, "jumping to it shows it again");
+        assert!(contains(&render(&dashboard), "firefox"));
+    }
+
+    #[test]
+    fn process_rows_are_capped() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            max_processes: Some(2),
+            ..Config::default()
+        });
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "(3 processes, top 2 rows shown)"));
+        assert!(contains(&screen, "defunct"));
+        assert!(!contains(&screen, "firefox"));
+
+        for _ in 0..5 {
+            dashboard.apply(Action::CursorDown);
+        }
+        assert_eq!(
+            dashboard.selected_pid,
+            Some(99),
+            "the cursor stays within the cap"
+        );
+    }
+
+    #[test]
+    fn same_named_processes_group_into_one_expandable_row() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.state().snapshot.processes.extend([
+            process(4243, "firefox", ProcessState::Sleeping),
+            process(4244, "firefox", ProcessState::Sleeping),
+        ]);
+        dashboard.apply(Action::GroupByName);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "(5 processes)"));
+        let group = screen
+            .iter()
+            .find(|line| line.contains("▸ firefox (3)"))
+            .expect("summary row");
+        assert!(group.contains("4.5"), "CPU is summed: {group}");
+        assert!(!contains(&screen, "4243"));
+
+        dashboard.selected_pid = Some(4242);
+        dashboard.apply(Action::ExpandGroup);
+        assert_eq!(dashboard.selected_group, None, "only summary rows expand");
+        dashboard.selected_pid = None;
+        dashboard.selected_group = Some("firefox".to_string());
+        assert_eq!(dashboard.selected_pids(), vec![4242, 4243, 4244]);
+        dashboard.apply(Action::ExpandGroup);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "▾ firefox (3)"));
+        assert!(contains(&screen, "4243") && contains(&screen, "4244"));
+
+        dashboard.apply(Action::CursorDown);
+        assert_eq!(dashboard.selected_pid, Some(4242));
+        dashboard.apply(Action::ExpandGroup);
+        assert_eq!(dashboard.selected_group.as_deref(), Some("firefox"));
+        assert!(!contains(&render(&dashboard), "4243"));
+    }
+
+    #[test]
+    fn a_snapshot_file_drives_the_dashboard() {
+        let path =
+            std::env::temp_dir().join(format!("dashoxide-snapshot-{}.json", std::process::id()));
+        fs::write(&path, serde_json::to_vec(&fixture()).unwrap()).unwrap();
+        let state = SystemState::new(Box::new(FileCollector::new(path.clone()))).unwrap();
+        fs::remove_file(&path).unwrap();
+        let dashboard = Dashboard::new(
+            Arc::new(Mutex::new(state)),
+            Arc::new(AtomicU64::new(1_000)),
+            &Config {
+                tab: DashboardView::Processes,
+                ..Config::default()
+            },
+        );
+        let screen = render(&dashboard);
+        let row = screen.iter().find(|line| line.contains("defunct")).unwrap();
+        assert!(row.contains("99") && row.contains("zombie"), "{row}");
+    }
+
+    #[test]
+    fn processes_are_listed_by_pid_with_status_labels() {
+        let screen = render(&dashboard(DashboardView::Processes));
+        let init = screen.iter().position(|l| l.contains("init")).unwrap();
+        let defunct = screen.iter().position(|l| l.contains("defunct")).unwrap();
+        let firefox = screen.iter().position(|l| l.contains("firefox")).unwrap();
+        assert!(init < defunct && defunct < firefox);
+        assert!(screen[defunct].contains("zombie"));
+        assert!(screen[firefox].contains("running"));
+    }
+
+    #[test]
+    fn launch_options_preset_tab_sort_and_filter() {
+        let dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            process_sort: ProcessColumn::Cpu,
+            sort_descending: true,
+            process_filter: "fire".to_string(),
+            ..Config::default()
+        });
+        for (process, cpu) in dashboard
+            .state()
+            .snapshot
+            .processes
+            .iter_mut()
+            .zip([5.0, 90.0, 0.0])
+        {
+            process.cpu_usage = cpu;
+        }
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "CPU%▼"));
+        assert!(contains(&screen, "(1 of 3 processes, filter 'fire')"));
+        assert!(contains(&screen, "firefox"));
+        assert!(!contains(&screen, "defunct"));
+    }
+
+    #[test]
+    fn sort_key_cycles_through_shown_columns() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.handle_input(KeyCode::Char('s'));
+        assert_eq!(dashboard.process_sort, ProcessColumn::Name);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Name▲"));
+        let row = |name| screen.iter().position(|l| l.contains(name)).unwrap();
+        assert!(row("defunct") < row("firefox") && row("firefox") < row("init"));
+
+        dashboard.handle_input(KeyCode::Char('S'));
+        let screen = render(&dashboard);
+        let row = |name| screen.iter().position(|l| l.contains(name)).unwrap();
+        assert!(row("init") < row("firefox"));
+    }
+
+    #[test]
+    fn strip_view_shows_one_line_per_metric() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.handle_input(KeyCode::Right);
+        assert_eq!(dashboard.current_view, DashboardView::Strip);
+        let screen = render(&dashboard);
+        for line in [
+            "CPU      42.0%  4 cores",
+            "Memory   50.0%  4.00/8.00 GB",
+            "Swap      0.0%  0.00/2.00 GB",
+            "Disk     75.0%  75.00/100.00 GB",
+            "Network down 5.0 MB/s, up 1.0 MB/s",
+            "Top       1.5%  defunct (99)",
+        ] {
+            assert!(contains(&screen, line), "{line}");
+        }
+        assert!(contains(&screen, "▃▃▃"));
+    }
+
+    #[test]
+    fn copied_views_follow_the_filter_and_sort_on_screen() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            process_filter: "i".to_string(),
+            process_sort: ProcessColumn::Name,
+            ..Config::default()
+        });
+        let text = dashboard.view_text();
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines[0].starts_with("Processes at 1970-01-01"), "{text}");
+        assert_eq!(lines[1], "Filter 'i'");
+        assert!(
+            lines[2].contains("PID") && lines[2].contains("Name▲"),
+            "{text}"
+        );
+        let firefox = lines.iter().position(|line| line.contains("firefox"));
+        let init = lines.iter().position(|line| line.contains("init"));
+        assert!(firefox.unwrap() < init.unwrap(), "{text}");
+        assert!(!text.contains("defunct"));
+
+        dashboard.apply(Action::TogglePrivacy);
+        assert!(!dashboard.view_text().contains("firefox"));
+
+        dashboard.set_view(DashboardView::Overview);
+        let text = dashboard.view_text();
+        assert!(text.contains("\nCPU Summary\nCPU Usage: 42.0%\n"), "{text}");
+        assert!(text.contains("Fullest: /data 75.0%"), "{text}");
+
+        let path = std::env::temp_dir().join(format!("dashoxide-view-{}.txt", std::process::id()));
+        dashboard.set_view(DashboardView::Disk);
+        dashboard.run_command(&format!("copy {}", path.display()));
+        let copied = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(
+            copied.contains("Total Disk Usage: 75.0/100.0 GB (75.0%)"),
+            "{copied}"
+        );
+        let row = copied
+            .lines()
+            .find(|line| line.starts_with("/data"))
+            .unwrap();
+        assert_eq!(
+            row,
+            "/data  100.00 GB  75.00 GB  25.00 GB   75.0%    ext4  -"
+        );
+        assert!(contains(&render(&dashboard), "Wrote the Disk view to"));
+    }
 
-        let cpu_block = Block::default().title("CPU Details").borders(Borders::ALL);
-        f.render_widget(cpu_block, area);
+    #[test]
+    fn text_tables_pad_columns_to_the_widest_cell() {
+        let rows = vec![
+            vec!["eth0".to_string(), "5.0 MB/s".to_string()],
+            vec!["Total".to_string(), String::new()],
+        ];
+        assert_eq!(
+            text_table(&["Interface", "Rx"], &rows),
+            "Interface  Rx\neth0       5.0 MB/s\nTotal"
+        );
     }
 
-    fn render_memory(
-        &self,
-        f: &mut tui::Frame<'_, CrosstermBackend<io::Stdout>>,
-        area: tui::layout::Rect,
-    ) {
-        let state_guard = self.system_state.lock();
-        let state = match state_guard {
-            Ok(ref state) => state,
-            Err(_) => {
-                f.render_widget(Paragraph::new("Error locking state"), area);
-                return;
-            }
-        };
+    #[test]
+    fn fired_alerts_are_logged_and_exported() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Alerts,
+            cpu_alert: Some(40.0),
+            alert_after: 1,
+            ..Config::default()
+        });
+        assert!(contains(&render(&dashboard), "No alerts fired yet"));
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Length(3), // RAM Gauge
-                    Constraint::Length(3), // Swap Gauge
-                    Constraint::Min(5),    // Potentially top memory consuming processes
-                ]
-                .as_ref(),
-            )
-            .split(area);
+        let mut bell = Vec::new();
+        dashboard.check_alerts(&mut bell).unwrap();
+        assert_eq!(bell, b"\x07");
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Alerts (1 logged"));
+        assert!(
+            screen.iter().any(|line| line.contains("CPU")
+                && line.contains("42.0%")
+                && line.contains("40.0%"))
+        );
 
-        // --- RAM ---
-        let mem_total = state.system.total_memory();
-        let mem_used = state.system.used_memory();
-        let mem_percent = if mem_total > 0 {
-            mem_used as f64 / mem_total as f64 * 100.0
-        } else {
-            0.0
-        };
-        let mem_unit = 1_024 * 1_024 * 1_024; // GiB
+        let path =
+            std::env::temp_dir().join(format!("dashoxide-alerts-{}.csv", std::process::id()));
+        dashboard.run_command(&format!("export {}", path.display()));
+        let csv = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.lines().nth(1).unwrap().ends_with(",CPU,42.0,40.0"));
+        assert!(contains(&render(&dashboard), "Exported 1 alerts to"));
 
-        let ram_gauge = Gauge::default()
-            .block(Block::default().title("RAM Usage").borders(Borders::ALL))
-            .gauge_style(Style::default().fg(Color::Magenta))
-            .percent(mem_percent.round() as u16)
-            .label(format!(
-                "{:.1}/{:.1} GiB ({:.1}%)",
-                mem_used as f64 / mem_unit as f64,
-                mem_total as f64 / mem_unit as f64,
-                mem_percent
-            ));
-        f.render_widget(ram_gauge, chunks[0]);
+        dashboard.apply(Action::ClearAlerts);
+        assert!(dashboard.alert_log.is_empty());
+    }
 
-        // --- Swap ---
-        let swap_total = state.system.total_swap();
-        let swap_used = state.system.used_swap();
-        let swap_percent = if swap_total > 0 {
-            swap_used as f64 / swap_total as f64 * 100.0
-        } else {
-            0.0
+    #[test]
+    fn filled_charts_stroke_down_to_the_axis() {
+        assert_eq!(
+            fill_under(&[(0.0, 40.0), (0.5, 20.0)]),
+            [
+                (0.0, 40.0),
+                (0.0, 0.0),
+                (0.0, 40.0),
+                (0.25, 30.0),
+                (0.25, 0.0),
+                (0.25, 30.0),
+                (0.5, 20.0),
+                (0.5, 0.0),
+                (0.5, 20.0),
+            ]
+        );
+        assert!(fill_under(&[]).is_empty());
+
+        let mut dashboard = dashboard(DashboardView::Cpu);
+        for _ in 0..3 {
+            dashboard.state().update();
+        }
+        let drawn = |screen: Vec<String>| {
+            screen
+                .iter()
+                .flat_map(|line| line.chars())
+                .filter(|&c| c == '•')
+                .count()
         };
+        let lines = drawn(render(&dashboard));
+        dashboard.apply(Action::ToggleFill);
+        assert_eq!(
+            dashboard.status_message(),
+            Some("Charts drawn as filled areas")
+        );
+        assert!(drawn(render(&dashboard)) > lines);
+    }
 
-        let swap_unit = 1_024 * 1_024; // MiB
+    #[test]
+    fn kernel_rate_spikes_ring_the_bell() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Cpu,
+            context_switch_alert: Some(100_000),
+            alert_after: 1,
+            ..Config::default()
+        });
+        assert!(!contains(&render(&dashboard), "Context switches"));
 
-        let swap_gauge = Gauge::default()
-            .block(Block::default().title("Swap Usage").borders(Borders::ALL))
-            .gauge_style(Style::default().fg(Color::Yellow))
-            .percent(swap_percent.round() as u16)
-            .label(format!(
-                "{:.0}/{:.0} MiB ({:.1}%)",
-                swap_used as f64 / swap_unit as f64,
-                swap_total as f64 / swap_unit as f64,
-                swap_percent
-            ));
-        // Only render swap if it exists
-        if swap_total > 0 {
-            f.render_widget(swap_gauge, chunks[1]);
-        } else {
-            let no_swap = Paragraph::new("No swap configured")
-                .block(Block::default().title("Swap Usage").borders(Borders::ALL))
-                .alignment(Alignment::Center);
-            f.render_widget(no_swap, chunks[1]);
+        dashboard.state().kernel_rates = Some((120_000, 3_400));
+        assert!(contains(
+            &render(&dashboard),
+            "CPU Details - Context switches: 120k/s, Interrupts: 3.4k/s"
+        ));
+        let mut bell = Vec::new();
+        dashboard.check_alerts(&mut bell).unwrap();
+        assert_eq!(bell, b"\x07");
+        assert_eq!(
+            dashboard.status_message(),
+            Some("Spike: Context switches at 120k/s (>= 100k/s)")
+        );
+        assert!(dashboard.alert_log.is_empty());
+    }
+
+    #[test]
+    fn alerts_can_freeze_the_data() {
+        let mut dashboard = dashboard_with(Config {
+            cpu_alert: Some(40.0),
+            alert_after: 1,
+            freeze_on_alert: true,
+            ..Config::default()
+        });
+        dashboard.check_alerts(&mut Vec::new()).unwrap();
+        let sample = dashboard.state().sample_count;
+        dashboard.state().update();
+        assert_eq!(
+            dashboard.state().sample_count,
+            sample,
+            "no samples while frozen"
+        );
+        let timestamp = dashboard.state().snapshot.timestamp;
+        assert!(contains(
+            &render(&dashboard),
+            &format!(
+                "Frozen on alert at {} UTC - press 'p' to resume",
+                &format_timestamp(timestamp)[11..]
+            )
+        ));
+
+        dashboard.handle_input(KeyCode::Char('p'));
+        dashboard.state().update();
+        assert_eq!(dashboard.state().sample_count, sample + 1);
+        assert!(!contains(&render(&dashboard), "Frozen on alert"));
+    }
+
+    #[test]
+    fn compared_processes_are_shown_side_by_side() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        for pid in [4242, 1, 99] {
+            dashboard.selected_pid = Some(pid);
+            dashboard.apply(Action::Compare);
         }
+        assert_eq!(dashboard.compared, [1, 99], "the oldest pick is replaced");
+        assert!(contains(&render(&dashboard), "Comparing PIDs 1 and 99"));
+
+        dashboard.apply(Action::Compare);
+        dashboard.toggle_compared(31337);
+        dashboard.set_view(DashboardView::Compare);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "init (1)"));
+        assert!(contains(&screen, "? (31337) - exited, last data kept"));
+        assert!(contains(&screen, "Memory 64.00 MB"));
+
+        dashboard.apply(Action::Compare);
+        assert!(dashboard.compared.is_empty());
+        assert!(dashboard.state().tracked_processes.is_empty());
+        assert!(contains(&render(&dashboard), "press 'C' to compare it"));
     }
 
-    fn render_disk(
-        &self,
-        f: &mut tui::Frame<'_, CrosstermBackend<io::Stdout>>,
-        area: tui::layout::Rect,
-    ) {
-        let state = match self.system_state.lock() {
-            Ok(guard) => guard,
-            Err(_) => return,
-        };
+    #[test]
+    fn watched_process_gets_a_panel_under_the_table() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.selected_pid = Some(4242);
+        dashboard.apply(Action::Watch);
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "firefox (4242) - watching ('W' to stop)"));
+        assert!(contains(&screen, "CPU 1.5%"));
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(1)
-            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
-            .split(area);
+        dashboard.selected_pid = Some(31337);
+        dashboard.apply(Action::Watch);
+        assert!(contains(&render(&dashboard), "- exited, last data kept"));
+        assert_eq!(dashboard.state().tracked_processes.len(), 1);
 
-        let mut total_space = 0;
-        let mut used_space = 0;
+        dashboard.apply(Action::Unwatch);
+        assert!(!contains(&render(&dashboard), "watching"));
+        assert!(dashboard.state().tracked_processes.is_empty());
+    }
 
-        for disk in state.disks.list() {
-            total_space += disk.total_space();
-            used_space += disk.total_space() - disk.available_space();
+    #[test]
+    fn a_narrow_chart_keeps_a_one_sample_spike() {
+        let dashboard = dashboard(DashboardView::Cpu);
+        {
+            let mut state = dashboard.state();
+            state.cpu_history = vec![0.0; HISTORY_LEN];
+            state.cpu_history[HISTORY_LEN / 2] = 100.0;
         }
+        // Sixty samples on a chart a third as wide: thinning them for the
+        // width must not average the spike away.
+        let screen = render_sized(&dashboard, 40, 40);
+        let top = screen
+            .iter()
+            .position(|line| line.contains("100%"))
+            .unwrap();
+        assert!(screen[top + 1].contains('•'), "{}", screen[top + 1]);
+    }
 
-        let disk_usage_percent = if total_space > 0 {
-            (used_space as f64 / total_space as f64 * 100.0) as u16
-        } else {
-            0
+    #[test]
+    fn history_axes_span_the_samples_taken_so_far() {
+        for view in [DashboardView::Cpu, DashboardView::Memory] {
+            let dashboard = dashboard(view);
+            let samples = dashboard.state().cpu_history.len();
+            let screen = render(&dashboard);
+            assert!(contains(&screen, &format!("{samples}s ago")), "{view:?}");
+            assert!(!contains(&screen, "60s ago"), "{view:?}");
+        }
+    }
+
+    #[test]
+    fn ascii_mode_leaves_no_unicode_on_screen() {
+        let screen = render(&dashboard_with(Config {
+            tab: DashboardView::Cpu,
+            ascii: true,
+            ..Config::default()
+        }));
+        assert!(screen.iter().all(|line| line.is_ascii()));
+        assert!(screen[1].starts_with(" +System Monitor---"));
+
+        let screen = render(&dashboard_with(Config {
+            tab: DashboardView::Processes,
+            process_sort: ProcessColumn::Cpu,
+            sort_descending: true,
+            ascii: true,
+            ..Config::default()
+        }));
+        assert!(screen.iter().all(|line| line.is_ascii()));
+        assert!(contains(&screen, "CPU%v"));
+        assert!(contains(&screen, "# new process"));
+    }
+
+    #[test]
+    fn tab_names_parse() {
+        assert_eq!("processes".parse(), Ok(DashboardView::Processes));
+        let error = "procs".parse::<DashboardView>().unwrap_err();
+        assert!(error.contains("overview, cpu"), "{error}");
+    }
+
+    #[test]
+    fn processes_that_just_appeared_are_highlighted() {
+        let dashboard = dashboard(DashboardView::Processes);
+        let mut forked = fixture();
+        forked
+            .processes
+            .push(process(5000, "worker", ProcessState::Running));
+        let collector = ScriptedCollector {
+            snapshots: vec![fixture(), forked],
         };
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.update();
+        *dashboard.state() = state;
 
-        let disk_guage = Gauge::default()
-            .block(
-                Block::default()
-                    .title("Total Disk Usage")
-                    .borders(Borders::ALL),
-            )
-            .gauge_style(Style::default().fg(Color::Blue))
-            .percent(disk_usage_percent);
-        f.render_widget(disk_guage, chunks[0]);
+        let buffer = render_buffer(&dashboard);
+        let color_of = |name: &str| cells_at(&buffer, name)[0].fg;
+        assert_eq!(color_of("worker"), Color::LightCyan);
+        assert_ne!(color_of("firefox"), Color::LightCyan);
+    }
 
-        let headers = ["Mount", "Total", "Used", "Available", "Usage %"];
-        let header_cells = headers.iter().map(|h| Cell::from(*h));
-        let header = Row::new(header_cells).style(Style::default().fg(Color::Yellow));
+    #[test]
+    fn process_search_reports_match_position() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.search_query = "FIRE".to_string();
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "search 'FIRE' (1/1)"));
+    }
 
-        let mut rows = Vec::new();
-        for disk in state.disks.list() {
-            let mount_point = disk.mount_point().to_string_lossy();
-            let total = disk.total_space();
-            let available = disk.available_space();
-            let used = total - available;
-            let usage_percent = if total > 0 {
-                (used as f64 / total as f64 * 100.0) as u64
-            } else {
-                0
-            };
+    #[test]
+    fn marked_processes_are_flagged_and_counted() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.handle_input(KeyCode::Down);
+        dashboard.handle_input(KeyCode::Down);
+        dashboard.handle_input(KeyCode::Char(' '));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "1 marked"));
+        let row = screen.iter().find(|line| line.contains("defunct")).unwrap();
+        assert!(row.contains('✓'), "{row}");
+    }
 
-            let row = Row::new(vec![
-                Cell::from(mount_point.to_string()),
-                Cell::from(format!("{:.1} GB", total as f64 / 1_000_000_000.0)),
-                Cell::from(format!("{:.1} GB", used as f64 / 1_000_000_000.0)),
-                Cell::from(format!("{:.1} GB", available as f64 / 1_000_000_000.0)),
-                Cell::from(format!("{}%", usage_percent)),
-            ]);
-            rows.push(row);
+    #[test]
+    fn marks_and_groups_of_vanished_processes_are_dropped() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.marked.extend([1, 4242]);
+        dashboard.expanded_groups.insert("firefox".to_string());
+        {
+            let mut state = dashboard.state();
+            state
+                .snapshot
+                .processes
+                .retain(|process| process.pid != 4242);
+            state.sample_count += 1;
         }
-        let table = Table::new(rows)
-            .header(header)
-            .block(Block::default().title("Disk Details").borders(Borders::ALL))
-            .widths(&[
-                Constraint::Percentage(20),
-                Constraint::Percentage(20),
-                Constraint::Percentage(20),
-                Constraint::Percentage(20),
-                Constraint::Percentage(20),
-            ])
-            .highlight_style(Style::default().bg(Color::DarkGray));
-        f.render_widget(table, chunks[1]);
-        let disk_block = Block::default().title("Disk Details").borders(Borders::ALL);
-        f.render_widget(disk_block, area);
+        dashboard.prune_stale();
+        assert_eq!(dashboard.marked, BTreeSet::from([1]));
+        assert!(dashboard.expanded_groups.is_empty());
     }
 
-    fn render_network(
-        &self,
-        f: &mut tui::Frame<'_, CrosstermBackend<io::Stdout>>,
-        area: tui::layout::Rect,
-    ) {
-        let state_guard = self.system_state.lock();
-        let state = match state_guard {
-            Ok(ref state) => state,
-            Err(_) => {
-                let error_msg = Paragraph::new("Error: Could not access system state.")
-                    .style(Style::default().fg(Color::Red));
-                f.render_widget(error_msg, area);
-                return;
-            }
-        };
+    #[test]
+    fn batch_kill_asks_first_and_reports_each_failure() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.marked.extend([1, 4242]);
+        dashboard.handle_input(KeyCode::Char('k'));
+        assert!(contains(
+            &render(&dashboard),
+            "Kill 2 process(es) (1, 4242)?"
+        ));
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Length(3),      // Current Rates Summary
-                    Constraint::Percentage(50), // Network History Chart
-                    Constraint::Min(5),         // Interface Details Table
-                ]
-                .as_ref(),
-            )
-            .split(area);
+        dashboard.handle_input(KeyCode::Char('y'));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "Killed 0 of 2"));
+        assert!(contains(&screen, "4242: not supported for this source"));
+        assert!(dashboard.marked.is_empty());
+    }
 
-        let rate_area = chunks[0];
-        let chart_area = chunks[1];
-        let table_area = chunks[2];
+    #[test]
+    fn single_kill_failure_is_reported_until_it_expires() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.selected_pid = Some(4242);
+        dashboard.handle_input(KeyCode::Char('k'));
+        dashboard.handle_input(KeyCode::Char('y'));
+        // Later key presses leave the message up.
+        dashboard.handle_input(KeyCode::Down);
+        assert!(contains(
+            &render(&dashboard),
+            "Kill failed: 4242: not supported for this source"
+        ));
 
-        let (rx_rate, tx_rate) = if state.network_history.len() >= 2 {
-            let current = state.network_history.iter().nth_back(0).unwrap();
-            let previous = state.network_history.iter().nth_back(1).unwrap();
-            (
-                current.0.saturating_sub(previous.0),
-                current.1.saturating_sub(previous.1),
-            )
-        } else {
-            (0, 0)
-        };
+        let (_, at) = dashboard.status_message.as_mut().unwrap();
+        *at -= STATUS_MESSAGE_TIMEOUT;
+        let screen = render(&dashboard);
+        assert!(!contains(&screen, "Kill failed"));
+        assert!(contains(&screen, "Press 'q' to quit"));
+    }
 
-        fn format_rate(bytes_per_sec: u64) -> String {
-            const KB: f64 = 1024.0;
-            const MB: f64 = 1024.0 * KB;
-            if bytes_per_sec == 0 {
-                return "0 B/s".to_string();
-            }
-            let rate = bytes_per_sec as f64;
-            if rate < KB {
-                format!("{} B/s", rate / KB)
-            } else {
-                format!("{:.1} MB/s", rate / MB)
-            }
-        }
+    #[test]
+    fn kill_waits_for_a_busy_collector_without_blocking() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.selected_pid = Some(4242);
+        let collector = dashboard.state().begin_sample().unwrap();
+        let sampling = collector.lock().unwrap();
+        dashboard.handle_input(KeyCode::Char('k'));
+        dashboard.handle_input(KeyCode::Char('y'));
+        assert!(contains(&render(&dashboard), "Collector busy, retrying"));
 
-        let network_summary = Paragraph::new(vec![Spans::from(vec![
-            Span::styled("Down: ", Style::default().fg(Color::Green)),
-            Span::raw(format_rate(tx_rate)),
-        ])])
-        .block(
-            Block::default()
-                .title("Current Traffic Rate")
-                .borders(Borders::ALL),
-        )
-        .alignment(tui::layout::Alignment::Center);
-        f.render_widget(network_summary, rate_area);
+        dashboard.retry_collector_task();
+        assert!(contains(&render(&dashboard), "Collector busy, retrying"));
+        drop(sampling);
+        dashboard.retry_collector_task();
+        assert!(contains(
+            &render(&dashboard),
+            "Kill failed: 4242: not supported for this source"
+        ));
+        assert!(dashboard.retry.is_none());
+    }
 
-        let network_history = &state.network_history;
+    #[test]
+    fn renice_waits_for_a_busy_collector_without_blocking() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.state().snapshot.processes[1].nice = Some(5);
+        dashboard.selected_pid = Some(4242);
+        let collector = dashboard.state().begin_sample().unwrap();
+        let sampling = collector.lock().unwrap();
+        dashboard.handle_input(KeyCode::Char('+'));
+        dashboard.handle_input(KeyCode::Char('y'));
+        assert!(contains(&render(&dashboard), "Collector busy, retrying"));
 
-        let mut rx_data: Vec<(f64, f64)> = Vec::new();
-        let mut tx_data: Vec<(f64, f64)> = Vec::new();
+        drop(sampling);
+        dashboard.retry_collector_task();
+        assert!(contains(
+            &render(&dashboard),
+            "Renice failed: 4242: not supported for this source"
+        ));
+    }
 
-        for i in 1..network_history.len() {
-            let current = network_history[i];
-            let prev = network_history[i - 1];
+    #[test]
+    fn renice_asks_first_and_reports_failures() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            process_columns: vec![ProcessColumn::Name, ProcessColumn::Nice],
+            ..Config::default()
+        });
+        dashboard.state().snapshot.processes[1].nice = Some(5);
+        dashboard.selected_pid = Some(4242);
+        dashboard.handle_input(KeyCode::Char('+'));
+        let screen = render(&dashboard);
+        assert!(contains(
+            &screen,
+            "Change the nice value of 1 process(es) (4242) by +1?"
+        ));
+        assert!(
+            screen
+                .iter()
+                .any(|l| l.contains("firefox") && l.contains('5'))
+        );
 
-            let rx_rate_bps = current.0.saturating_sub(prev.0);
-            let tx_rate_bps = current.1.saturating_sub(prev.1);
+        dashboard.handle_input(KeyCode::Char('y'));
+        assert!(contains(
+            &render(&dashboard),
+            "Renice failed: 4242: not supported for this source"
+        ));
 
-            rx_data.push((i as f64, rx_rate_bps as f64 / 1024.0));
-            tx_data.push((i as f64, tx_rate_bps as f64 / 1024.0));
-        }
+        dashboard.selected_pid = Some(1);
+        dashboard.handle_input(KeyCode::Char('-'));
+        dashboard.handle_input(KeyCode::Char('y'));
+        assert!(contains(
+            &render(&dashboard),
+            "Renice failed: 1: nice value unknown"
+        ));
+    }
 
-        let datasets = vec![
-            Dataset::default()
-                .name("Download (KB/s)")
-                .graph_type(GraphType::Line)
-                .style(Style::default().fg(Color::Green))
-                .data(&rx_data),
-            Dataset::default()
-                .name("Upload (KB/s)")
-                .graph_type(GraphType::Line)
-                .style(Style::default().fg(Color::Red))
-                .data(&tx_data),
-        ];
+    #[test]
+    fn environment_viewer_hides_secrets_and_explains_failures() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.selected_pid = Some(4242);
+        dashboard.handle_input(KeyCode::Char('E'));
+        let screen = render(&dashboard);
+        assert!(contains(
+            &screen,
+            "Environment of firefox (4242) (2 variables, secrets hidden)"
+        ));
+        assert!(contains(&screen, "HOME=/home/user"));
+        assert!(contains(&screen, "API_TOKEN=********"));
 
-        let max_rate_kbps = rx_data
-            .iter()
-            .chain(tx_data.iter())
-            .map(|&(_, v)| v)
-            .fold(0.0, |max, v| if v.is_finite() && v > max { v } else { max });
+        dashboard.handle_input(KeyCode::Tab);
+        assert!(contains(&render(&dashboard), "API_TOKEN=abc123"));
+        dashboard.handle_input(KeyCode::Char('h'));
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "(1 variables, secrets shown)"));
+        assert!(!contains(&screen, "API_TOKEN"));
 
-        // Set a minimum top bound for the Y axis, e.g., 10 KB/s, and add headroom
-        let y_bound_top = (max_rate_kbps * 1.1).max(10.0);
-        let history_len = state.network_history.len() as f64;
+        dashboard.handle_input(KeyCode::Esc);
+        assert_eq!(dashboard.mode, InputMode::Normal);
+        dashboard.selected_pid = Some(1);
+        dashboard.handle_input(KeyCode::Char('E'));
+        assert!(contains(
+            &render(&dashboard),
+            "Can't read the environment: permission denied"
+        ));
+    }
 
-        let chart = Chart::new(datasets)
-            .block(
-                Block::default()
-                    .title("Network History (KB/s)")
-                    .borders(Borders::ALL),
-            )
-            .x_axis(
-                Axis::default()
-                    // .title("Time") // Often redundant
-                    .style(Style::default().fg(Color::Gray))
-                    .bounds([0.0, history_len]) // X represents time steps
-                    .labels(vec![
-                        Span::styled(
-                            format!("{}s", history_len.round()),
-                            Style::default().fg(Color::Gray),
-                        ), // Start label (oldest)
-                        Span::styled("0s", Style::default().fg(Color::Gray)), // End label (now)
-                    ]),
-            )
-            .y_axis(
-                Axis::default()
-                    .title("KB/s")
-                    .style(Style::default().fg(Color::Gray))
-                    .bounds([0.0, y_bound_top]) // Dynamic upper bound
-                    .labels(
-                        // Generate labels dynamically based on the top bound
-                        vec![
-                            Span::raw("0"),
-                            Span::raw(format!("{:.0}", y_bound_top / 2.0)),
-                            Span::raw(format!("{:.0}", y_bound_top)),
-                        ],
-                    ),
-            );
-        f.render_widget(chart, chart_area);
+    #[test]
+    fn environment_opens_once_a_busy_collector_is_free() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.selected_pid = Some(4242);
+        let collector = dashboard.state().begin_sample().unwrap();
+        let sampling = collector.lock().unwrap();
+        dashboard.handle_input(KeyCode::Char('E'));
+        assert_eq!(dashboard.mode, InputMode::Normal);
+        assert!(contains(&render(&dashboard), "Collector busy, retrying"));
 
-        let headers = ["Interface Name", "Total Recived", "Total Transmitted"];
-        let header_cells = headers
-            .iter()
-            .map(|h| Cell::from(Span::styled(*h, Style::default().fg(Color::Yellow))));
-        let header = Row::new(header_cells)
-            .style(Style::default().bg(Color::DarkGray))
-            .height(1);
+        drop(sampling);
+        dashboard.retry_collector_task();
+        assert!(contains(
+            &render(&dashboard),
+            "Environment of firefox (4242)"
+        ));
+    }
 
-        fn format_total_bytes(bytes: u64) -> String {
-            const MB: f64 = 1_000_000.0;
-            const GB: f64 = 1_000.0 * MB;
-            if bytes == 0 {
-                return "0 B".to_string();
-            }
-            let b = bytes as f64;
-            if b < MB {
-                format!("{:.1} KB", b / 1000.0)
-            } else if b < GB {
-                format!("{:.2} MB", b / MB)
-            } else {
-                format!("{:.2} GB", b / GB)
-            }
+    #[test]
+    fn batch_kill_can_be_cancelled() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.marked.insert(1);
+        dashboard.handle_input(KeyCode::Char('k'));
+        dashboard.handle_input(KeyCode::Char('n'));
+        assert_eq!(dashboard.mode, InputMode::Normal);
+        assert_eq!(dashboard.marked.len(), 1);
+    }
+
+    #[test]
+    fn process_columns_follow_the_configuration() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.process_columns = vec![
+            ProcessColumn::Name,
+            ProcessColumn::User,
+            ProcessColumn::Threads,
+        ];
+        let screen = render(&dashboard);
+        let header = screen.iter().find(|line| line.contains("Name")).unwrap();
+        assert!(header.contains("User") && header.contains("Threads"));
+        assert!(!header.contains("PID") && !header.contains("Status"));
+        let row = screen.iter().find(|line| line.contains("firefox")).unwrap();
+        assert!(row.contains("alice") && row.contains('3'), "{row}");
+    }
+
+    #[test]
+    fn open_files_column_flags_processes_near_their_limit() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.process_columns = vec![ProcessColumn::Name, ProcessColumn::OpenFiles];
+        {
+            let mut state = dashboard.state();
+            let processes = &mut state.snapshot.processes;
+            processes[1].open_files = Some(1_000);
+            processes[1].open_files_limit = Some(1_024);
+            processes[2].open_files = Some(12);
+            processes[2].open_files_limit = Some(1_024);
         }
+        let buffer = render_buffer(&dashboard);
+        let screen = lines(&buffer);
 
-        let mut rows = Vec::new();
-        for (interface_name, data) in state.networks.list() {
-            let row = Row::new(vec![
-                Cell::from(interface_name.clone()),
-                Cell::from(format_total_bytes(data.total_received())),
-                Cell::from(format_total_bytes(data.total_transmitted())),
-            ]);
-            rows.push(row);
+        let fds_column = screen
+            .iter()
+            .find_map(|line| Some(line[..line.find("FDs")?].chars().count()))
+            .unwrap() as u16;
+        let color_of = |name: &str| {
+            let row = screen.iter().position(|line| line.contains(name)).unwrap();
+            buffer.get(fds_column, row as u16).fg
+        };
+        assert_eq!(color_of("firefox"), Color::Red);
+        assert_eq!(color_of("defunct"), Color::Reset);
+        let init = screen.iter().find(|line| line.contains("init")).unwrap();
+        assert!(init.contains('?'), "{init}");
+    }
+
+    #[test]
+    fn processes_sort_by_swap() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            process_columns: vec![ProcessColumn::Name, ProcessColumn::Swap],
+            sort_descending: true,
+            ..Config::default()
+        });
+        dashboard.run_command("sort swap");
+        {
+            let mut state = dashboard.state();
+            let processes = &mut state.snapshot.processes;
+            processes[0].swap = Some(512_000);
+            processes[2].swap = Some(300_000_000);
         }
+        let screen = render(&dashboard);
+        let row_of = |name: &str| screen.iter().position(|line| line.contains(name)).unwrap();
+        assert!(row_of("defunct") < row_of("init"));
+        assert!(row_of("init") < row_of("firefox"));
+        assert!(screen[row_of("defunct")].contains("300.00 MB"));
+        assert!(screen[row_of("init")].contains("512.00 KB"));
+        assert!(screen[row_of("firefox")].contains('-'));
+    }
 
-        let table = Table::new(rows)
-            .header(header)
-            .block(
-                Block::default()
-                    .title("Network Interfaces (Total Data)")
-                    .borders(Borders::ALL),
-            )
-            .widths(&[
-                Constraint::Percentage(40),
-                Constraint::Percentage(30),
-                Constraint::Percentage(30),
-            ])
-            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
-            .highlight_symbol(">> ");
-        f.render_widget(table, table_area);
+    #[test]
+    fn process_table_scrolls_only_as_far_as_the_selection_needs() {
+        assert_eq!(scroll_offset(0, Some(12), 10, 50), 3, "down past the end");
+        assert_eq!(scroll_offset(3, Some(5), 10, 50), 3, "stays while in view");
+        assert_eq!(scroll_offset(3, Some(1), 10, 50), 1, "up past the top");
+        assert_eq!(scroll_offset(30, None, 10, 20), 10, "list got shorter");
+        assert_eq!(scroll_offset(0, Some(4), 0, 20), 4);
     }
 
-    fn render_processes(
-        &self,
-        f: &mut tui::Frame<'_, CrosstermBackend<io::Stdout>>,
-        area: tui::layout::Rect,
-    ) {
-        let state = match self.system_state.lock() {
-            Ok(guard) => guard,
-            Err(_) => return,
-        };
+    #[test]
+    fn threads_are_demanded_only_while_shown_or_sorted() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            process_columns: vec![ProcessColumn::Name],
+            ..Config::default()
+        });
+        assert!(!dashboard.refresh_demand().threads);
+        dashboard.run_command("sort threads");
+        assert!(dashboard.refresh_demand().threads);
 
-        let headers = ["PID", "Name", "CPU%", "Memory", "Status"];
-        let header_cells = headers.iter().map(|h| Cell::from(*h));
-        let header = Row::new(header_cells).style(Style::default().fg(Color::Yellow));
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            process_columns: vec![ProcessColumn::Name, ProcessColumn::Threads],
+            ..Config::default()
+        });
+        assert!(dashboard.refresh_demand().threads);
+        dashboard.set_view(DashboardView::Cpu);
+        assert!(!dashboard.refresh_demand().threads);
+    }
 
-        let mut rows = Vec::new();
-        for (pid, process) in state.system.processes() {
-            let row = Row::new(vec![
-                Cell::from(pid.to_string()),
-                Cell::from(process.name().to_string_lossy()),
-                Cell::from(format!("{:.1}%", process.cpu_usage())),
-                Cell::from(format!("{} MB", process.memory() / 1024 / 1024)),
-                Cell::from(format!("{:?}", process.status())),
-            ]);
-            rows.push(row);
+    #[test]
+    fn swap_is_demanded_only_for_rows_on_screen() {
+        let mut dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            process_columns: vec![ProcessColumn::Name, ProcessColumn::Swap],
+            ..Config::default()
+        });
+        assert!(dashboard.refresh_demand().swap_pids.is_empty());
+        render(&dashboard);
+        let mut on_screen = dashboard.refresh_demand().swap_pids;
+        on_screen.sort_unstable();
+        assert_eq!(on_screen, [1, 99, 4242]);
+        assert!(!dashboard.refresh_demand().swap_all);
+
+        dashboard.run_command("sort swap");
+        assert!(dashboard.refresh_demand().swap_all);
+
+        dashboard.process_columns = vec![ProcessColumn::Name];
+        render(&dashboard);
+        assert!(dashboard.refresh_demand().swap_pids.is_empty());
+    }
+
+    #[test]
+    fn memory_over_either_threshold_is_red() {
+        let dashboard = dashboard_with(Config {
+            tab: DashboardView::Processes,
+            highlight_memory: Some(1_000_000_000),
+            highlight_memory_percent: Some(10.0),
+            ..Config::default()
+        });
+        {
+            let mut state = dashboard.state();
+            let processes = &mut state.snapshot.processes;
+            // Over 10% of the 8 GB fixture but under the absolute limit.
+            processes[0].memory = 900_000_000;
+            processes[1].memory = 700_000_000;
         }
+        let buffer = render_buffer(&dashboard);
+        let color_of = |text: &str| cells_at(&buffer, text)[0].fg;
+        assert_eq!(color_of("858 MB"), Color::Red);
+        assert_ne!(color_of("667 MB"), Color::Red);
+    }
 
-        let constraints = [
-            Constraint::Length(7),
-            Constraint::Percentage(40),
-            Constraint::Length(8),
-            Constraint::Length(10),
-            Constraint::Length(10),
-        ];
+    #[test]
+    fn pid_column_fits_the_widest_pid() {
+        let dashboard = dashboard(DashboardView::Processes);
+        dashboard.system_state.lock().unwrap().snapshot.processes =
+            vec![process(4_194_304, "kworker", ProcessState::Idle)];
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "4194304 kworker"));
+    }
 
-        let processes_block = Table::new(rows)
-            .header(header)
-            .block(
-                Block::default()
-                    .title("Processes Details")
-                    .borders(Borders::ALL),
-            )
-            .widths(&constraints)
-            .highlight_style(Style::default().bg(Color::DarkGray));
-        f.render_widget(processes_block, area);
+    #[test]
+    fn disk_mount_column_fits_long_paths() {
+        let dashboard = dashboard(DashboardView::Disk);
+        dashboard.system_state.lock().unwrap().snapshot.disks[0].mount_point =
+            "/var/lib/containers/storage".to_string();
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "/var/lib/containers/storage 100.00 GB"));
+
+        let long = "/var/lib/containers/storage/overlay/3f2a9c1d/merged";
+        dashboard.system_state.lock().unwrap().snapshot.disks[0].mount_point = long.to_string();
+        let screen = render_sized(&dashboard, 160, 40);
+        assert!(contains(&screen, &format!("{long} 100.00 GB")));
+    }
+
+    #[test]
+    fn process_count_can_be_abbreviated() {
+        let mut dashboard = dashboard(DashboardView::Processes);
+        dashboard.compact_numbers = true;
+        dashboard.system_state.lock().unwrap().snapshot.processes = (0..1_234)
+            .map(|pid| process(pid, "worker", ProcessState::Sleeping))
+            .collect();
+        let screen = render(&dashboard);
+        assert!(contains(&screen, "(1.2k processes)"));
     }
 }
@@ -0,0 +1,155 @@
+//! The environment viewer: one process's variables in a popup, searchable,
+//! with the values of likely secrets hidden until revealed.
+
+use std::io;
+
+/// Keys containing any of these, ignoring case, have their values hidden.
+const SENSITIVE_KEY_PARTS: [&str; 7] = [
+    "PASSWORD",
+    "PASSWD",
+    "TOKEN",
+    "SECRET",
+    "CREDENTIAL",
+    "API_KEY",
+    "PRIVATE_KEY",
+];
+
+/// Shown in place of a hidden value.
+pub const HIDDEN_VALUE: &str = "********";
+
+/// Whether the value of `key` is hidden until revealed.
+pub fn is_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    SENSITIVE_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+/// An open viewer. The variables are read once, when it opens.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvironmentView {
+    pub pid: u32,
+    /// Key and value of each variable, sorted by key, or why they couldn't
+    /// be read.
+    variables: Result<Vec<(String, String)>, String>,
+    pub query: String,
+    /// Index into `rows()` of the first row shown.
+    pub scroll: usize,
+    /// Show the values of sensitive variables too.
+    pub reveal: bool,
+}
+
+impl EnvironmentView {
+    /// `environment` holds "KEY=value" entries, as the OS keeps them.
+    pub fn new(pid: u32, environment: Result<Vec<String>, io::Error>) -> Self {
+        let variables = environment
+            .map(|entries| {
+                let mut variables: Vec<_> = entries
+                    .into_iter()
+                    .map(|entry| match entry.split_once('=') {
+                        Some((key, value)) => (key.to_string(), value.to_string()),
+                        None => (entry, String::new()),
+                    })
+                    .collect();
+                variables.sort();
+                variables
+            })
+            .map_err(|e| e.to_string());
+        Self {
+            pid,
+            variables,
+            query: String::new(),
+            scroll: 0,
+            reveal: false,
+        }
+    }
+
+    /// Variables whose key or shown value contains the query, ignoring
+    /// case, with sensitive values hidden unless revealed. A hidden value
+    /// isn't searched, so the results can't give it away.
+    pub fn rows(&self) -> Result<Vec<(&str, &str)>, &str> {
+        let variables = self.variables.as_ref().map_err(String::as_str)?;
+        let query = self.query.to_lowercase();
+        Ok(variables
+            .iter()
+            .map(|(key, value)| {
+                if self.reveal || !is_sensitive(key) {
+                    (key.as_str(), value.as_str())
+                } else {
+                    (key.as_str(), HIDDEN_VALUE)
+                }
+            })
+            .filter(|(key, value)| {
+                key.to_lowercase().contains(&query)
+                    || (*value != HIDDEN_VALUE && value.to_lowercase().contains(&query))
+            })
+            .collect())
+    }
+
+    /// Scrolls one row up or down, stopping at either end.
+    pub fn scroll_by(&mut self, down: bool) {
+        let last = self.rows().map_or(0, |rows| rows.len().saturating_sub(1));
+        self.scroll = if down {
+            (self.scroll + 1).min(last)
+        } else {
+            self.scroll.saturating_sub(1)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view() -> EnvironmentView {
+        let environment = [
+            "PATH=/usr/bin",
+            "DB_PASSWORD=hunter2",
+            "HOME=/root",
+            "EMPTY",
+        ]
+        .map(String::from)
+        .to_vec();
+        EnvironmentView::new(4242, Ok(environment))
+    }
+
+    #[test]
+    fn secrets_are_hidden_and_not_searched() {
+        let mut view = view();
+        assert_eq!(
+            view.rows().unwrap(),
+            [
+                ("DB_PASSWORD", HIDDEN_VALUE),
+                ("EMPTY", ""),
+                ("HOME", "/root"),
+                ("PATH", "/usr/bin")
+            ]
+        );
+
+        view.query = "hunter".to_string();
+        assert!(view.rows().unwrap().is_empty());
+        view.reveal = true;
+        assert_eq!(view.rows().unwrap(), [("DB_PASSWORD", "hunter2")]);
+
+        view.query = "ROOT".to_string();
+        assert_eq!(view.rows().unwrap(), [("HOME", "/root")]);
+    }
+
+    #[test]
+    fn unreadable_environments_keep_the_reason() {
+        let error = io::Error::new(io::ErrorKind::PermissionDenied, "permission denied");
+        let mut view = EnvironmentView::new(1, Err(error));
+        assert_eq!(view.rows(), Err("permission denied"));
+        view.scroll_by(true);
+        assert_eq!(view.scroll, 0);
+    }
+
+    #[test]
+    fn scrolling_stops_at_the_last_row() {
+        let mut view = view();
+        for _ in 0..10 {
+            view.scroll_by(true);
+        }
+        assert_eq!(view.scroll, 3);
+        view.scroll_by(false);
+        assert_eq!(view.scroll, 2);
+    }
+}
@@ -0,0 +1,92 @@
+//! Fading of stale data: the longer collection goes without a sample, the
+//! dimmer the frame gets, so frozen numbers don't pass for live ones.
+
+use std::time::Duration;
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+/// How old the data on screen is, in sampling intervals rather than
+/// seconds so a long interval doesn't count as stale by itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Staleness {
+    Fresh,
+    /// Over 3 intervals without a sample: dimmed.
+    Late,
+    /// Over 10: grayed out.
+    Stale,
+    /// Over 30: grayed out and dimmed.
+    Frozen,
+}
+
+impl Staleness {
+    pub fn of(age: Duration, interval: Duration) -> Self {
+        let intervals = age.as_secs_f64() / interval.as_secs_f64().max(f64::EPSILON);
+        if intervals > 30.0 {
+            Staleness::Frozen
+        } else if intervals > 10.0 {
+            Staleness::Stale
+        } else if intervals > 3.0 {
+            Staleness::Late
+        } else {
+            Staleness::Fresh
+        }
+    }
+
+    /// Style laid over every cell, or `None` for fresh data.
+    fn style(self) -> Option<Style> {
+        let gray = Style::default().fg(Color::DarkGray);
+        match self {
+            Staleness::Fresh => None,
+            Staleness::Late => Some(Style::default().add_modifier(Modifier::DIM)),
+            Staleness::Stale => Some(gray),
+            Staleness::Frozen => Some(gray.add_modifier(Modifier::DIM)),
+        }
+    }
+}
+
+/// Widget that fades everything already drawn in its area; render it over
+/// the views after they're drawn.
+pub struct StaleFade(pub Staleness);
+
+impl Widget for StaleFade {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if let Some(style) = self.0.style() {
+            buf.set_style(area, style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staleness_is_counted_in_intervals() {
+        let second = Duration::from_secs(1);
+        assert_eq!(
+            Staleness::of(Duration::from_secs(2), second),
+            Staleness::Fresh
+        );
+        assert_eq!(
+            Staleness::of(Duration::from_secs(5), second),
+            Staleness::Late
+        );
+        assert_eq!(
+            Staleness::of(Duration::from_secs(15), second),
+            Staleness::Stale
+        );
+        assert_eq!(
+            Staleness::of(Duration::from_secs(31), second),
+            Staleness::Frozen
+        );
+        assert_eq!(
+            Staleness::of(Duration::from_secs(31), Duration::from_secs(10)),
+            Staleness::Late
+        );
+    }
+}
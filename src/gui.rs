@@ -0,0 +1,213 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use eframe::egui;
+
+use crate::collector::RefreshDemand;
+use crate::config::Config;
+use crate::util::{DataAvailability, SystemState, lock_state};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Panel {
+    Overview,
+    Cpu,
+    Processes,
+}
+
+impl Panel {
+    const ALL: [Panel; 3] = [Panel::Overview, Panel::Cpu, Panel::Processes];
+
+    fn title(self) -> &'static str {
+        match self {
+            Panel::Overview => "Overview",
+            Panel::Cpu => "CPU",
+            Panel::Processes => "Processes",
+        }
+    }
+}
+
+/// The native-window front end. It reads the same `SystemState` the TUI
+/// does; only the drawing differs.
+struct GuiApp {
+    system_state: Arc<Mutex<SystemState>>,
+    interval: Duration,
+    panel: Panel,
+    /// Panel the collector's refresh demand was last set for.
+    demanded: Option<Panel>,
+}
+
+/// Opens the window and blocks until it is closed.
+pub fn run(system_state: Arc<Mutex<SystemState>>, config: &Config) -> Result<(), io::Error> {
+    let app = GuiApp {
+        system_state,
+        interval: config.interval,
+        panel: Panel::Overview,
+        demanded: None,
+    };
+    eframe::run_native(
+        "dashoxide",
+        eframe::NativeOptions::default(),
+        Box::new(|_| Ok(Box::new(app))),
+    )
+    .map_err(|e| io::Error::other(e.to_string()))
+}
+
+impl eframe::App for GuiApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::TopBottomPanel::top("panels").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for panel in Panel::ALL {
+                    ui.selectable_value(&mut self.panel, panel, panel.title());
+                }
+                if self.system_state.is_poisoned() {
+                    ui.colored_label(egui::Color32::RED, "Collector crashed, data is frozen");
+                }
+            });
+        });
+
+        let mut state = lock_state(&self.system_state);
+        if self.demanded != Some(self.panel) {
+            state.set_demand(RefreshDemand {
+                processes: self.panel == Panel::Processes,
+                disks: self.panel == Panel::Overview,
+                ..RefreshDemand::default()
+            });
+            self.demanded = Some(self.panel);
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| match self.panel {
+            Panel::Overview => overview(ui, &state),
+            Panel::Cpu => cpu(ui, &state),
+            Panel::Processes => processes(ui, &state),
+        });
+        drop(state);
+
+        // Nothing changes between samples, so there's no point redrawing
+        // more often than they arrive.
+        ctx.request_repaint_after(self.interval);
+    }
+}
+
+fn overview(ui: &mut egui::Ui, state: &SystemState) {
+    let snapshot = &state.snapshot;
+
+    ui.heading("CPU");
+    ui.label(format!(
+        "Usage: {:.1}% across {} cores",
+        snapshot.cpu.usage,
+        snapshot.cpu.cores.len()
+    ));
+
+    ui.heading("Memory");
+    let memory = &snapshot.memory;
+    if state.memory_availability() == DataAvailability::Unavailable {
+        ui.label(DataAvailability::Unavailable.message());
+    } else {
+        let fraction = memory.used as f32 / memory.total as f32;
+        ui.add(egui::ProgressBar::new(fraction).text(format!(
+            "{:.2} / {:.2} GB",
+            memory.used as f64 / 1_000_000_000.0,
+            memory.total as f64 / 1_000_000_000.0
+        )));
+        if let Some((used, limit)) = snapshot.cgroup.as_ref().and_then(|c| c.memory()) {
+            ui.add(
+                egui::ProgressBar::new(used as f32 / limit as f32).text(format!(
+                    "Container: {:.2} / {:.2} GB limit",
+                    used as f64 / 1_000_000_000.0,
+                    limit as f64 / 1_000_000_000.0
+                )),
+            );
+        }
+    }
+
+    ui.heading("Disks");
+    for disk in &snapshot.disks {
+        let used = disk.used();
+        let fraction = if disk.total > 0 {
+            used as f32 / disk.total as f32
+        } else {
+            0.0
+        };
+        ui.add(egui::ProgressBar::new(fraction).text(format!(
+            "{} {:.1} / {:.1} GB",
+            disk.mount_point,
+            used as f64 / 1_000_000_000.0,
+            disk.total as f64 / 1_000_000_000.0
+        )));
+    }
+
+    ui.heading("Network");
+    let (rx, tx) = state.network_history.last().copied().unwrap_or((0, 0));
+    ui.label(format!(
+        "Down: {:.1} KB/s  Up: {:.1} KB/s",
+        rx as f64 / 1024.0,
+        tx as f64 / 1024.0
+    ));
+}
+
+fn cpu(ui: &mut egui::Ui, state: &SystemState) {
+    let usage = state.snapshot.cpu.usage;
+    ui.add(egui::ProgressBar::new(usage / 100.0).text(format!("Total {usage:.1}%")));
+
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), 120.0),
+        egui::Sense::hover(),
+    );
+    let history = &state.cpu_history;
+    if history.len() >= 2 {
+        let step = rect.width() / (history.len() - 1) as f32;
+        let points = history
+            .iter()
+            .enumerate()
+            .map(|(i, usage)| {
+                egui::pos2(
+                    rect.left() + step * i as f32,
+                    rect.bottom() - rect.height() * usage.clamp(0.0, 100.0) / 100.0,
+                )
+            })
+            .collect();
+        ui.painter().add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.5, egui::Color32::LIGHT_BLUE),
+        ));
+    } else {
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            DataAvailability::Collecting.message(),
+            egui::FontId::default(),
+            ui.visuals().weak_text_color(),
+        );
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for (index, usage) in state.snapshot.cpu.cores.iter().enumerate() {
+            ui.add(egui::ProgressBar::new(usage / 100.0).text(format!("cpu{index} {usage:.1}%")));
+        }
+    });
+}
+
+fn processes(ui: &mut egui::Ui, state: &SystemState) {
+    let mut processes: Vec<_> = state.snapshot.processes.iter().collect();
+    processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("processes").striped(true).show(ui, |ui| {
+            for header in ["PID", "Name", "CPU%", "Memory", "Status"] {
+                ui.strong(header);
+            }
+            ui.end_row();
+            for process in processes {
+                ui.label(process.pid.to_string());
+                ui.label(&process.name);
+                ui.label(format!("{:.1}%", process.cpu_usage));
+                ui.label(format!("{} MB", process.memory / 1024 / 1024));
+                ui.label(process.status.label());
+                ui.end_row();
+            }
+        });
+    });
+}
@@ -0,0 +1,573 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Something the user can ask the dashboard to do from the keyboard. Which
+/// key triggers which action is decided by a `KeyMap`, except for the
+/// actions at the end, which the dashboard produces itself while a prompt
+/// or the chart cursor has the keyboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Help,
+    Command,
+    /// Open the quick switcher over processes, disks and interfaces.
+    Switcher,
+    NextTab,
+    PrevTab,
+    Search,
+    Filter,
+    NextMatch,
+    PrevMatch,
+    ClearSearch,
+    ChartCursor,
+    CursorUp,
+    CursorDown,
+    Mark,
+    Kill,
+    /// Raise the nice value of the selected or marked processes by one,
+    /// giving them less CPU time.
+    NiceUp,
+    /// Lower the nice value by one, giving them more; needs root.
+    NiceDown,
+    /// Collapse processes sharing a name into one summed row.
+    GroupByName,
+    /// Expand the group under the cursor, or collapse it again.
+    ExpandGroup,
+    /// List only processes stuck in uninterruptible (disk) sleep.
+    ToggleStuck,
+    /// Hide dashoxide's own process from the process lists.
+    ToggleSelf,
+    /// Add the selected process to the Compare tab, or take it off.
+    Compare,
+    /// Follow the selected process in a panel under the process table.
+    Watch,
+    /// Show the environment variables of the selected process.
+    Environment,
+    Unwatch,
+    ToggleIdleCores,
+    ToggleCoreOverlay,
+    ToggleDiskDetails,
+    /// Show link state, MTU, MAC and addresses in the interface table.
+    ToggleLinkDetails,
+    /// Show the kernel's slab, page table, stack and dirty page figures on
+    /// the Memory tab.
+    ToggleKernelMemory,
+    /// Show the current view alone, without the tab bar, or go back.
+    ToggleMinimized,
+    /// Show frame, lock and update timings, for profiling the dashboard.
+    ToggleDebug,
+    /// Hide process names and network addresses, for screenshots.
+    TogglePrivacy,
+    TogglePseudoDisks,
+    ResetTotals,
+    /// Switch network rates between bytes and bits per second.
+    ToggleBits,
+    /// Draw the history charts filled down to the axis, or as lines again.
+    ToggleFill,
+    Sort,
+    ReverseSort,
+    ResetCharts,
+    /// Keep the charts as they are now as dimmed lines behind the live
+    /// ones, or clear them.
+    Ghost,
+    /// Copy the current view as plain text to the clipboard, or to a file
+    /// when there's no clipboard.
+    CopyView,
+    /// Write the alert log to the `--alert-log` file.
+    ExportAlerts,
+    ClearAlerts,
+    /// Stop taking samples, or pause a replay; again to resume.
+    Pause,
+    ReplayForward,
+    ReplayBack,
+    Slower,
+    Faster,
+    /// Accept a prompt, e.g. 'y' to a kill or renice confirmation.
+    Confirm,
+    /// Leave the active prompt or the chart cursor without acting.
+    Cancel,
+    /// Finish typing into a prompt and act on it.
+    Submit,
+    InsertChar(char),
+    DeleteChar,
+    /// Move the chart cursor one sample older or newer.
+    CursorBack,
+    CursorForward,
+    /// Show or hide secret values in the environment viewer.
+    RevealSecrets,
+}
+
+impl Action {
+    pub const ALL: [Action; 50] = [
+        Action::Quit,
+        Action::Help,
+        Action::Command,
+        Action::Switcher,
+        Action::NextTab,
+        Action::PrevTab,
+        Action::Search,
+        Action::Filter,
+        Action::NextMatch,
+        Action::PrevMatch,
+        Action::ClearSearch,
+        Action::ChartCursor,
+        Action::CursorUp,
+        Action::CursorDown,
+        Action::Mark,
+        Action::Kill,
+        Action::NiceUp,
+        Action::NiceDown,
+        Action::GroupByName,
+        Action::ExpandGroup,
+        Action::ToggleStuck,
+        Action::ToggleSelf,
+        Action::Compare,
+        Action::Watch,
+        Action::Environment,
+        Action::Unwatch,
+        Action::ToggleIdleCores,
+        Action::ToggleCoreOverlay,
+        Action::ToggleDiskDetails,
+        Action::ToggleLinkDetails,
+        Action::ToggleKernelMemory,
+        Action::ToggleMinimized,
+        Action::ToggleDebug,
+        Action::TogglePrivacy,
+        Action::TogglePseudoDisks,
+        Action::ResetTotals,
+        Action::ToggleBits,
+        Action::ToggleFill,
+        Action::Sort,
+        Action::ReverseSort,
+        Action::ResetCharts,
+        Action::Ghost,
+        Action::CopyView,
+        Action::ExportAlerts,
+        Action::ClearAlerts,
+        Action::Pause,
+        Action::ReplayForward,
+        Action::ReplayBack,
+        Action::Slower,
+        Action::Faster,
+    ];
+
+    /// Name used in the `[keys]` table of the config file. Only the actions
+    /// in `ALL` can be bound.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::Command => "command",
+            Action::Switcher => "switcher",
+            Action::NextTab => "next_tab",
+            Action::PrevTab => "prev_tab",
+            Action::Search => "search",
+            Action::Filter => "filter",
+            Action::NextMatch => "next_match",
+            Action::PrevMatch => "prev_match",
+            Action::ClearSearch => "clear_search",
+            Action::ChartCursor => "chart_cursor",
+            Action::CursorUp => "cursor_up",
+            Action::CursorDown => "cursor_down",
+            Action::Mark => "mark",
+            Action::Kill => "kill",
+            Action::NiceUp => "nice_up",
+            Action::NiceDown => "nice_down",
+            Action::GroupByName => "group_by_name",
+            Action::ExpandGroup => "expand_group",
+            Action::ToggleStuck => "toggle_stuck",
+            Action::ToggleSelf => "toggle_self",
+            Action::Compare => "compare",
+            Action::Watch => "watch",
+            Action::Environment => "environment",
+            Action::Unwatch => "unwatch",
+            Action::ToggleIdleCores => "toggle_idle_cores",
+            Action::ToggleCoreOverlay => "toggle_core_overlay",
+            Action::ToggleDiskDetails => "toggle_disk_details",
+            Action::ToggleLinkDetails => "toggle_link_details",
+            Action::ToggleKernelMemory => "toggle_kernel_memory",
+            Action::ToggleMinimized => "toggle_minimized",
+            Action::ToggleDebug => "toggle_debug",
+            Action::TogglePrivacy => "toggle_privacy",
+            Action::TogglePseudoDisks => "toggle_pseudo_disks",
+            Action::ResetTotals => "reset_totals",
+            Action::ToggleBits => "toggle_bits",
+            Action::ToggleFill => "toggle_fill",
+            Action::Sort => "sort",
+            Action::ReverseSort => "reverse_sort",
+            Action::ResetCharts => "reset_charts",
+            Action::Ghost => "ghost",
+            Action::CopyView => "copy_view",
+            Action::ExportAlerts => "export_alerts",
+            Action::ClearAlerts => "clear_alerts",
+            Action::Pause => "pause",
+            Action::ReplayForward => "replay_forward",
+            Action::ReplayBack => "replay_back",
+            Action::Slower => "slower",
+            Action::Faster => "faster",
+            Action::Confirm => "confirm",
+            Action::Cancel => "cancel",
+            Action::Submit => "submit",
+            Action::InsertChar(_) => "insert_char",
+            Action::DeleteChar => "delete_char",
+            Action::CursorBack => "cursor_back",
+            Action::CursorForward => "cursor_forward",
+            Action::RevealSecrets => "reveal_secrets",
+        }
+    }
+
+    fn default_key(self) -> Option<Key> {
+        let code = match self {
+            Action::Switcher => {
+                return Some(Key {
+                    code: KeyCode::Char('p'),
+                    ctrl: true,
+                });
+            }
+            Action::Quit => KeyCode::Char('q'),
+            Action::Help => KeyCode::Char('?'),
+            Action::Command => KeyCode::Char(':'),
+            Action::NextTab => KeyCode::Right,
+            Action::PrevTab => KeyCode::Left,
+            Action::Search => KeyCode::Char('f'),
+            Action::Filter => KeyCode::Char('/'),
+            Action::NextMatch => KeyCode::Char('n'),
+            Action::PrevMatch => KeyCode::Char('N'),
+            Action::ClearSearch => KeyCode::Esc,
+            Action::ChartCursor => KeyCode::Char('c'),
+            Action::CursorUp => KeyCode::Up,
+            Action::CursorDown => KeyCode::Down,
+            Action::Mark => KeyCode::Char(' '),
+            Action::Kill => KeyCode::Char('k'),
+            Action::NiceUp => KeyCode::Char('+'),
+            Action::NiceDown => KeyCode::Char('-'),
+            Action::GroupByName => KeyCode::Char('g'),
+            Action::ExpandGroup => KeyCode::Enter,
+            Action::ToggleStuck => KeyCode::Char('D'),
+            Action::ToggleSelf => KeyCode::Char('H'),
+            Action::Compare => KeyCode::Char('C'),
+            Action::Watch => KeyCode::Char('w'),
+            Action::Environment => KeyCode::Char('E'),
+            Action::Unwatch => KeyCode::Char('W'),
+            Action::ToggleIdleCores => KeyCode::Char('i'),
+            Action::ToggleCoreOverlay => KeyCode::Char('o'),
+            Action::ToggleDiskDetails => KeyCode::Char('t'),
+            Action::ToggleLinkDetails => KeyCode::Char('l'),
+            Action::ToggleKernelMemory => KeyCode::Char('K'),
+            Action::ToggleMinimized => KeyCode::Char('m'),
+            Action::ToggleDebug => KeyCode::F(12),
+            Action::TogglePrivacy => KeyCode::Char('P'),
+            Action::TogglePseudoDisks => KeyCode::Char('v'),
+            Action::ResetTotals => KeyCode::Char('r'),
+            Action::ToggleBits => KeyCode::Char('b'),
+            Action::ToggleFill => KeyCode::Char('F'),
+            Action::Sort => KeyCode::Char('s'),
+            Action::ReverseSort => KeyCode::Char('S'),
+            Action::ResetCharts => KeyCode::Char('R'),
+            Action::Ghost => KeyCode::Char('G'),
+            Action::CopyView => KeyCode::Char('y'),
+            Action::ExportAlerts => KeyCode::Char('e'),
+            Action::ClearAlerts => KeyCode::Char('x'),
+            Action::Pause => KeyCode::Char('p'),
+            Action::ReplayForward => KeyCode::Char('.'),
+            Action::ReplayBack => KeyCode::Char(','),
+            Action::Slower => KeyCode::Char('<'),
+            Action::Faster => KeyCode::Char('>'),
+            Action::Confirm
+            | Action::Cancel
+            | Action::Submit
+            | Action::InsertChar(_)
+            | Action::DeleteChar
+            | Action::CursorBack
+            | Action::CursorForward
+            | Action::RevealSecrets => return None,
+        };
+        Some(code.into())
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "replay_pause" is the name from before live data could be paused.
+        let s = if s == "replay_pause" { "pause" } else { s };
+        Action::ALL
+            .into_iter()
+            .find(|action| action.name() == s)
+            .ok_or_else(|| format!("unknown action '{s}'"))
+    }
+}
+
+/// A key as the key map sees it. Shift is already part of the character
+/// ('N' vs 'n'), so only Ctrl is tracked separately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub ctrl: bool,
+}
+
+impl From<KeyCode> for Key {
+    fn from(code: KeyCode) -> Self {
+        Self { code, ctrl: false }
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+        }
+    }
+}
+
+/// Named keys accepted in key descriptors, besides single characters.
+const NAMED_KEYS: [(&str, KeyCode); 12] = [
+    ("space", KeyCode::Char(' ')),
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("delete", KeyCode::Delete),
+];
+
+/// Parses a key descriptor: a single character ("q", "<"), a key name
+/// ("space", "left", "esc", ...) or a function key ("f5"), optionally
+/// prefixed with "ctrl-" ("ctrl-p"). Names are case-insensitive; single
+/// characters are not.
+pub fn parse_key(descriptor: &str) -> Result<Key, String> {
+    if let Some(rest) = descriptor
+        .get(..5)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("ctrl-"))
+        .and_then(|_| descriptor.get(5..))
+        .filter(|rest| !rest.is_empty())
+    {
+        let key = parse_key(rest)?;
+        return Ok(Key { ctrl: true, ..key });
+    }
+    let mut chars = descriptor.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c).into());
+    }
+    let lower = descriptor.to_lowercase();
+    if let Some((_, key)) = NAMED_KEYS.iter().find(|(name, _)| *name == lower) {
+        return Ok((*key).into());
+    }
+    if let Some(number) = lower.strip_prefix('f')
+        && let Ok(number @ 1..=12) = number.parse()
+    {
+        return Ok(KeyCode::F(number).into());
+    }
+    Err(format!("unknown key '{descriptor}'"))
+}
+
+/// How a key is shown in hints, e.g. "q", "space", "Left" or "Ctrl-p".
+pub fn key_name(key: Key) -> String {
+    let name = match key.code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(number) => format!("F{number}"),
+        other => format!("{other:?}"),
+    };
+    if key.ctrl {
+        format!("Ctrl-{name}")
+    } else {
+        name
+    }
+}
+
+/// Which action each key triggers. Every action has exactly one key, so
+/// rebinding an action frees its default key.
+#[derive(Clone)]
+pub struct KeyMap {
+    bindings: HashMap<Key, Action>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .into_iter()
+                .filter_map(|action| Some((action.default_key()?, action)))
+                .collect(),
+        }
+    }
+}
+
+impl KeyMap {
+    pub fn action(&self, key: impl Into<Key>) -> Option<Action> {
+        self.bindings.get(&key.into()).copied()
+    }
+
+    /// Binds `key` to `action`, taking the key from any action that had it.
+    /// Returns that action, if it was a different one.
+    pub fn bind(&mut self, action: Action, key: Key) -> Option<Action> {
+        self.bindings.retain(|_, bound| *bound != action);
+        self.bindings
+            .insert(key, action)
+            .filter(|previous| *previous != action)
+    }
+
+    /// Name of the key bound to `action`, for hints in titles and the status
+    /// bar.
+    pub fn hint(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .find(|(_, bound)| **bound == action)
+            .map_or_else(|| "unbound".to_string(), |(key, _)| key_name(*key))
+    }
+
+    /// Applies the `[keys]` table of a config file, which maps action names
+    /// to key descriptors:
+    ///
+    /// ```toml
+    /// [keys]
+    /// quit = "x"
+    /// next_tab = "l"
+    /// ```
+    ///
+    /// Only this subset of TOML is understood; other tables are skipped. A
+    /// key taken from another action is an error unless the file gives that
+    /// action a new key too.
+    pub fn apply_config(&mut self, contents: &str) -> Result<(), String> {
+        let mut in_keys = false;
+        let mut taken = Vec::new();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                in_keys = line == "[keys]";
+                continue;
+            }
+            if !in_keys {
+                continue;
+            }
+            let binding = line
+                .split_once('=')
+                .ok_or_else(|| "expected 'action = \"key\"'".to_string())
+                .and_then(|(action, key)| {
+                    let action: Action = action.trim().parse()?;
+                    let key = parse_key(&unquote(key.trim())?)?;
+                    Ok((action, key))
+                });
+            match binding {
+                Ok((action, key)) => {
+                    if let Some(previous) = self.bind(action, key) {
+                        taken.push((number, key, previous));
+                    }
+                }
+                Err(e) => return Err(format!("line {}: {e}", number + 1)),
+            }
+        }
+        match taken
+            .into_iter()
+            .find(|(_, _, previous)| !self.bindings.values().any(|bound| bound == previous))
+        {
+            Some((number, key, previous)) => Err(format!(
+                "line {}: '{}' is already bound to {}; give {} another key",
+                number + 1,
+                key_name(key),
+                previous.name(),
+                previous.name()
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The contents of a `"..."` or `'...'` string, ignoring a trailing comment.
+fn unquote(value: &str) -> Result<String, String> {
+    let mut chars = value.chars();
+    let quote = chars
+        .next()
+        .filter(|c| *c == '"' || *c == '\'')
+        .ok_or_else(|| format!("expected a quoted key, found {value}"))?;
+    let rest = chars.as_str();
+    let end = rest
+        .find(quote)
+        .ok_or_else(|| format!("unterminated string {value}"))?;
+    let trailing = rest[end + 1..].trim();
+    if !trailing.is_empty() && !trailing.starts_with('#') {
+        return Err(format!("unexpected '{trailing}' after the key"));
+    }
+    Ok(rest[..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_characters_names_and_function_keys() {
+        assert_eq!(parse_key("q"), Ok(KeyCode::Char('q').into()));
+        assert_eq!(parse_key("Q"), Ok(KeyCode::Char('Q').into()));
+        assert_eq!(parse_key("Space"), Ok(KeyCode::Char(' ').into()));
+        assert_eq!(parse_key("left"), Ok(KeyCode::Left.into()));
+        assert_eq!(parse_key("f5"), Ok(KeyCode::F(5).into()));
+        assert!(parse_key("f13").is_err());
+        let ctrl_x = Key {
+            code: KeyCode::Char('x'),
+            ctrl: true,
+        };
+        assert_eq!(parse_key("Ctrl-x"), Ok(ctrl_x));
+        assert_eq!(key_name(ctrl_x), "Ctrl-x");
+        assert!(parse_key("ctrl-").is_err());
+        assert!(parse_key("alt-x").is_err());
+    }
+
+    #[test]
+    fn config_rebinds_and_frees_the_old_key() {
+        let mut keys = KeyMap::default();
+        keys.apply_config(
+            "# dvorak\n[display]\nquit = \"u\"\n\n[keys]\nquit = \"z\" # not q\nnext_tab = 'j'\n",
+        )
+        .unwrap();
+        assert_eq!(keys.action(KeyCode::Char('z')), Some(Action::Quit));
+        assert_eq!(keys.action(KeyCode::Char('q')), None);
+        assert_eq!(keys.action(KeyCode::Char('u')), None);
+        assert_eq!(keys.action(KeyCode::Char('j')), Some(Action::NextTab));
+        assert_eq!(keys.hint(Action::Quit), "z");
+    }
+
+    #[test]
+    fn config_reports_keys_taken_from_other_actions() {
+        let mut keys = KeyMap::default();
+        let error = keys
+            .apply_config("[keys]\nhelp = \"h\"\nquit = \"x\"")
+            .unwrap_err();
+        assert_eq!(
+            error,
+            "line 3: 'x' is already bound to clear_alerts; give clear_alerts another key"
+        );
+
+        let mut keys = KeyMap::default();
+        keys.apply_config("[keys]\nquit = \"x\"\nclear_alerts = \"q\"")
+            .unwrap();
+        assert_eq!(keys.action(KeyCode::Char('x')), Some(Action::Quit));
+        assert_eq!(keys.action(KeyCode::Char('q')), Some(Action::ClearAlerts));
+    }
+
+    #[test]
+    fn config_errors_name_the_line() {
+        let mut keys = KeyMap::default();
+        let error = keys
+            .apply_config("[keys]\nquit = \"z\"\nfly = \"f\"")
+            .unwrap_err();
+        assert_eq!(error, "line 3: unknown action 'fly'");
+        let error = keys.apply_config("[keys]\nquit = z").unwrap_err();
+        assert!(
+            error.starts_with("line 2: expected a quoted key"),
+            "{error}"
+        );
+    }
+}
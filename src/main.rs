@@ -1,57 +1,244 @@
+mod alert;
+mod animation;
+mod api;
+mod ascii;
+mod clipboard;
+mod collector;
+mod columns;
+mod config;
 mod dashboard;
+mod environment;
+mod fade;
+#[cfg(feature = "gui")]
+mod gui;
+mod keys;
+mod plain;
+mod record;
+mod switcher;
 mod util;
+mod view;
 
+use std::io::{self, IsTerminal};
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use std::{sync::Arc, thread};
-use sysinfo::{Components, Cpu, Disks, Networks, System};
 
+use tokio::runtime::Builder;
+use tokio::task;
+use tokio::time::{self, Instant, MissedTickBehavior};
+
+use collector::{Collector, FileCollector, ReplayCollector, SshCollector, SysinfoCollector};
+use config::Config;
 use dashboard::Dashboard;
-use tokio::io;
-use util::SystemState;
+use record::Recorder;
+use util::{DetectionSummary, SystemState, lock_state};
 
 fn main() -> Result<(), io::Error> {
-    let system_state = Arc::new(Mutex::new(SystemState::new()));
-    let state_clone = Arc::clone(&system_state);
-    thread::spawn(move || {
-        loop {
-            {
-                let mut state = state_clone.lock().unwrap();
-                state.update();
+    let config = Config::from_args()?;
+
+    let collector: Box<dyn Collector> = if let Some(path) = &config.replay {
+        Box::new(ReplayCollector::open(path, config.replay_speed)?)
+    } else if let Some(path) = &config.snapshot_file {
+        Box::new(FileCollector::new(path.clone()))
+    } else if let Some(destination) = &config.remote {
+        Box::new(SshCollector::new(
+            destination.clone(),
+            config.remote_command.clone(),
+        ))
+    } else {
+        Box::new(SysinfoCollector::new(
+            config.process_interval,
+            config.refresh_all,
+        ))
+    };
+    let mut initial_state = match SystemState::new(collector) {
+        Ok(state) => state,
+        Err(e) if config.check => check_unknown(&e.to_string()),
+        Err(e) => return Err(e),
+    };
+    initial_state
+        .interface_filter
+        .clone_from(&config.net_filter);
+    initial_state.network_total_all = config.net_total_all;
+
+    if config.report {
+        // CPU usage and network rates are both differences between two
+        // samples, so take a second one before reporting.
+        thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        initial_state.update();
+        if config.check {
+            if let Some(error) = &initial_state.last_error {
+                check_unknown(error);
+            }
+            let (status, summary) =
+                alert::check(&initial_state.snapshot, |kind| config.thresholds(kind));
+            println!("{summary}");
+            std::process::exit(status.exit_code());
+        }
+        if let Some(error) = initial_state.last_error {
+            return Err(io::Error::other(error));
+        }
+        let report = serde_json::to_string(&initial_state.snapshot).map_err(io::Error::other)?;
+        println!("{report}");
+        return Ok(());
+    }
+
+    if config.show_splash {
+        let summary = DetectionSummary::from_state(&initial_state);
+        if config.verbose {
+            println!("{}", summary.details());
+        } else {
+            println!("{summary}");
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    // Built by hand rather than through `#[tokio::main]`, which would need
+    // the macros crate. The UI keeps the main thread; collection and the
+    // API run on the workers.
+    let runtime = Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()?;
+
+    let system_state = Arc::new(Mutex::new(initial_state));
+    if let Some(addr) = config.api_addr
+        && let Err(e) = api::spawn(runtime.handle(), addr, Arc::clone(&system_state))
+    {
+        eprintln!("dashoxide: could not start HTTP API on {addr}: {e}");
+    }
+
+    let recorder = config
+        .record
+        .as_deref()
+        .map(|path| Recorder::create(path, config.record_rotation))
+        .transpose()?;
+    // Shared with the dashboard, which can change it while running.
+    let interval = Arc::new(AtomicU64::new(config.interval.as_millis() as u64));
+    runtime.spawn(collect(
+        Arc::clone(&system_state),
+        recorder,
+        Arc::clone(&interval),
+    ));
+
+    let mut dashboard = Dashboard::new(Arc::clone(&system_state), interval, &config);
+    let result = if config.gui {
+        run_gui(Arc::clone(&system_state), &config)
+    } else if config.plain || !tui_capable() {
+        plain::run(Arc::clone(&system_state), config.interval, config.precision)
+    } else {
+        match dashboard.run() {
+            Err(e) if e.kind() == io::ErrorKind::Unsupported => {
+                eprintln!("dashoxide: {e}");
+                eprintln!("dashoxide: falling back to plain output (use --plain to skip the TUI)");
+                plain::run(Arc::clone(&system_state), config.interval, config.precision)
             }
-            thread::sleep(Duration::from_secs(1));
+            result => result,
         }
-    });
-
-    let mut dashboard = Dashboard::new(Arc::clone(&system_state));
-    //let mut sys = System::new_all();
-    //let disk_list = Disks::new_with_refreshed_list();
-    //let network_list = Networks::new_with_refreshed_list();
-
-    //    loop {
-    //        sys.refresh_all();
-    //        for (index, cpu) in sys.cpus().iter().enumerate() {
-    //            println!("CPU {} Usage: {}%", index, cpu.cpu_usage());
-    //        }
-
-    //        println!(
-    //            "Memory: {:.2} GB used / s{:.2} GB total",
-    //            sys.used_memory() as f64 / 1_000_000_000.0,
-    //            sys.total_memory() as f64 / 1_000_000_000.0
-    //        );
-
-    //        for disk in disk_list.list() {
-    //            println!("Disk: {disk:?}");
-    //        }
-
-    //        for network in network_list.list() {
-    //            println!("Network: {network:?}");
-    //        }
-
-    //        thread::sleep(Duration::from_secs(1));
-
-    //        print!("{}[2J", 27 as char);
-    //    }
-    dashboard.run()?;
+    };
+    // Dropping the runtime would wait for a sample still being collected,
+    // which over a hung ssh connection could be forever.
+    runtime.shutdown_background();
+    result?;
+
+    if config.exit_summary {
+        println!("{}", lock_state(&system_state).session);
+    }
     Ok(())
 }
+
+/// How often a wait for the next sample checks whether the interval changed.
+const INTERVAL_POLL: Duration = Duration::from_millis(100);
+
+/// Samples the collector on every tick of `interval`, recording each new
+/// snapshot. The collectors block, so each sample runs on the blocking pool.
+async fn collect(
+    system_state: Arc<Mutex<SystemState>>,
+    mut recorder: Option<Recorder>,
+    interval: Arc<AtomicU64>,
+) {
+    let load = || Duration::from_millis(interval.load(Ordering::Relaxed));
+    let mut period = load();
+    let mut ticker = time::interval(period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut last_tick = Instant::now();
+    let mut last_recorded = 0;
+    loop {
+        // Wake up now and then while waiting so a changed interval applies
+        // now rather than after the old one has run out.
+        last_tick = loop {
+            if let Ok(tick) = time::timeout(INTERVAL_POLL, ticker.tick()).await {
+                break tick;
+            }
+            if load() != period {
+                period = load();
+                ticker = time::interval_at(last_tick + period, period);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            }
+        };
+
+        let state = Arc::clone(&system_state);
+        let sampled = task::spawn_blocking(move || {
+            sample(&state, recorder.as_mut(), &mut last_recorded);
+            (recorder, last_recorded)
+        })
+        .await;
+        match sampled {
+            Ok(kept) => (recorder, last_recorded) = kept,
+            // The collector panicked; stop sampling as a crashed thread would.
+            Err(_) => return,
+        }
+    }
+}
+
+/// Takes one sample and appends it to the recording if it is new. The
+/// state is only locked to start and to apply the sample, so the UI can
+/// draw while a slow collector works.
+fn sample(
+    system_state: &Mutex<SystemState>,
+    recorder: Option<&mut Recorder>,
+    last_recorded: &mut u64,
+) {
+    let Some(collector) = lock_state(system_state).begin_sample() else {
+        return;
+    };
+    let started = Instant::now();
+    let result = util::collect(&collector);
+    let mut state = lock_state(system_state);
+    state.finish_sample(result, started.elapsed());
+    if let Some(recorder) = recorder
+        && state.sample_count != *last_recorded
+    {
+        *last_recorded = state.sample_count;
+        if let Err(e) = recorder.append(&state.snapshot) {
+            state.last_error = Some(format!("recording failed: {e}"));
+        }
+    }
+}
+
+/// Ends a `--check` that couldn't measure anything, which monitoring
+/// reads as neither healthy nor failing.
+fn check_unknown(error: &str) -> ! {
+    let status = alert::Status::Unknown;
+    println!("{} - {error}", status.label());
+    std::process::exit(status.exit_code());
+}
+
+/// A pipe or `TERM=dumb` can't show the TUI even where raw mode works.
+fn tui_capable() -> bool {
+    std::io::stdout().is_terminal() && std::env::var("TERM").is_ok_and(|term| term != "dumb")
+}
+
+#[cfg(feature = "gui")]
+fn run_gui(system_state: Arc<Mutex<SystemState>>, config: &Config) -> Result<(), io::Error> {
+    gui::run(system_state, config)
+}
+
+#[cfg(not(feature = "gui"))]
+fn run_gui(_system_state: Arc<Mutex<SystemState>>, _config: &Config) -> Result<(), io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "--gui needs dashoxide built with the `gui` feature",
+    ))
+}
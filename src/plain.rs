@@ -0,0 +1,173 @@
+//! Line-based output for terminals the TUI can't drive, such as CI logs,
+//! `TERM=dumb` or a pipe.
+
+use std::{
+    fmt::Write as _,
+    io::{self, IsTerminal, Write},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crate::util::{Precision, Snapshot, SystemState, format_timestamp, lock_state};
+
+/// Processes listed in each block.
+const TOP_PROCESSES: usize = 5;
+
+/// Prints a block of plain text for every new sample until interrupted.
+/// On a capable terminal each block replaces the previous one; anywhere
+/// else blocks are appended so logs keep the whole history.
+pub fn run(
+    system_state: Arc<Mutex<SystemState>>,
+    interval: Duration,
+    precision: Precision,
+) -> Result<(), io::Error> {
+    let mut stdout = std::io::stdout();
+    let clear = stdout.is_terminal() && std::env::var("TERM").is_ok_and(|term| term != "dumb");
+    let mut last_printed = None;
+    loop {
+        let (sample, block) = {
+            let state = lock_state(&system_state);
+            (
+                state.sample_count,
+                render(&state.snapshot, &state.interface_filter, &precision),
+            )
+        };
+        if last_printed != Some(sample) {
+            last_printed = Some(sample);
+            if clear {
+                write!(stdout, "\x1b[2J\x1b[H")?;
+            }
+            writeln!(stdout, "{block}")?;
+            stdout.flush()?;
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn render(snapshot: &Snapshot, interface_filter: &str, precision: &Precision) -> String {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+
+    let mut out = String::new();
+    let _ = writeln!(out, "--- {} UTC ---", format_timestamp(snapshot.timestamp));
+    let _ = writeln!(
+        out,
+        "CPU     {:>6} ({} cores)",
+        precision.percent(snapshot.cpu.usage.into()),
+        snapshot.cpu.cores.len()
+    );
+
+    let memory = &snapshot.memory;
+    let _ = writeln!(
+        out,
+        "Memory  {:>6} ({:.*}/{})",
+        precision.percent(percent(memory.used, memory.total)),
+        precision.memory,
+        memory.used as f64 / GIB,
+        precision.memory(memory.total as f64 / GIB, "GiB")
+    );
+    if memory.swap_total > 0 {
+        let _ = writeln!(
+            out,
+            "{:<7} {:>6} ({:.*}/{})",
+            memory.swap_label(),
+            precision.percent(percent(memory.swap_used, memory.swap_total)),
+            precision.memory,
+            memory.swap_used as f64 / MIB,
+            precision.memory(memory.swap_total as f64 / MIB, "MiB")
+        );
+    }
+
+    for disk in &snapshot.disks {
+        let used = disk.used();
+        let _ = writeln!(
+            out,
+            "Disk    {:>6} {} ({:.*}/{})",
+            precision.percent(percent(used, disk.total)),
+            disk.mount_point,
+            precision.memory,
+            used as f64 / 1_000_000_000.0,
+            precision.memory(disk.total as f64 / 1_000_000_000.0, "GB")
+        );
+    }
+    for interface in snapshot
+        .networks
+        .iter()
+        .filter(|interface| interface.matches(interface_filter))
+    {
+        let _ = writeln!(
+            out,
+            "Net     {} down {}, up {}",
+            interface.name,
+            precision.rate(interface.rx_rate as f64 / MIB, "MB/s"),
+            precision.rate(interface.tx_rate as f64 / MIB, "MB/s")
+        );
+    }
+
+    let mut processes: Vec<_> = snapshot.processes.iter().collect();
+    processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+    for process in processes.into_iter().take(TOP_PROCESSES) {
+        let _ = writeln!(
+            out,
+            "Process {:>6} {} ({}), {} MB",
+            precision.percent(process.cpu_usage.into()),
+            process.name,
+            process.pid,
+            process.memory / 1024 / 1024
+        );
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+fn percent(used: u64, total: u64) -> f64 {
+    if total > 0 {
+        used as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{CpuSnapshot, MemorySnapshot, ProcessSnapshot, ProcessState};
+
+    fn process(pid: u32, name: &str, cpu_usage: f32) -> ProcessSnapshot {
+        ProcessSnapshot {
+            pid,
+            name: name.to_string(),
+            cpu_usage,
+            memory: 512 * 1024 * 1024,
+            status: ProcessState::Running,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renders_one_line_per_metric_and_busiest_processes_first() {
+        let snapshot = Snapshot {
+            cpu: CpuSnapshot {
+                usage: 12.5,
+                cores: vec![10.0, 15.0],
+            },
+            memory: MemorySnapshot {
+                used: 2 * 1024 * 1024 * 1024,
+                total: 8 * 1024 * 1024 * 1024,
+                swap_used: 0,
+                swap_total: 0,
+                swap_is_commit: false,
+            },
+            processes: vec![process(1, "init", 0.1), process(42, "cargo", 90.0)],
+            ..Snapshot::default()
+        };
+        let text = render(&snapshot, "", &Precision::default());
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines[0], "--- 1970-01-01 00:00:00 UTC ---");
+        assert_eq!(lines[1], "CPU      12.5% (2 cores)");
+        assert_eq!(lines[2], "Memory   25.0% (2.00/8.00 GiB)");
+        assert_eq!(lines[3], "Process  90.0% cargo (42), 512 MB");
+        assert_eq!(lines.len(), 5);
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::util::{Snapshot, format_timestamp};
+
+/// When a recording is moved aside for a fresh file, and how many of the
+/// moved files are kept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rotation {
+    /// Rotate once the file reaches this many bytes.
+    pub max_size: Option<u64>,
+    /// Rotate when a new UTC day starts.
+    pub daily: bool,
+    /// Rotated files kept next to the current one; older ones are deleted.
+    pub keep: usize,
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Self {
+            max_size: None,
+            daily: false,
+            keep: 7,
+        }
+    }
+}
+
+/// Appends every sample to a file as one JSON snapshot per line, the format
+/// `--replay` reads back.
+///
+/// With rotation the file at `path` is always the current one: a full file
+/// is renamed to `<stem>-<UTC time>.<ext>` and recording continues in a new
+/// one, so each rotated file can be replayed on its own.
+pub struct Recorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    rotation: Rotation,
+    /// Bytes in the current file, including what it held when opened.
+    size: u64,
+    /// UTC day the current file was opened on.
+    day: u64,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, rotation: Rotation) -> Result<Self, io::Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            size: file.metadata()?.len(),
+            writer: BufWriter::new(file),
+            rotation,
+            day: now() / 86_400,
+        })
+    }
+
+    /// Flushes after each line so a crash loses at most the sample being
+    /// written.
+    pub fn append(&mut self, snapshot: &Snapshot) -> Result<(), io::Error> {
+        let line = serde_json::to_vec(snapshot)?;
+        self.rotate_if_due(now())?;
+        self.writer.write_all(&line)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn rotate_if_due(&mut self, now: u64) -> Result<(), io::Error> {
+        let full = self.rotation.max_size.is_some_and(|max| self.size >= max);
+        let new_day = self.rotation.daily && now / 86_400 != self.day;
+        if self.size == 0 || !(full || new_day) {
+            self.day = now / 86_400;
+            return Ok(());
+        }
+        self.writer.flush()?;
+        fs::rename(&self.path, self.rotated_path(now)?)?;
+        *self = Self::create(&self.path, self.rotation)?;
+        self.day = now / 86_400;
+        self.prune()
+    }
+
+    /// `<stem>-<UTC time>.<ext>` next to the current file, with a counter
+    /// added if several rotations fall within one second. The counter goes
+    /// on from the highest one still there, so it keeps counting up after
+    /// pruning has deleted the lower ones.
+    fn rotated_path(&self, now: u64) -> Result<PathBuf, io::Error> {
+        let (stem, extension) = self.name_parts();
+        let time = format_timestamp(now).replace(' ', "T").replace(':', "");
+        let first = self
+            .rotated_files()?
+            .iter()
+            .filter(|(rotated, _, _)| *rotated == time)
+            .map(|(_, counter, _)| counter + 1)
+            .max()
+            .unwrap_or(0);
+        Ok((first..)
+            .map(|n| {
+                let suffix = if n == 0 {
+                    String::new()
+                } else {
+                    format!("-{n}")
+                };
+                self.path
+                    .with_file_name(format!("{stem}-{time}{suffix}{extension}"))
+            })
+            .find(|path| !path.exists())
+            .expect("some counter is free"))
+    }
+
+    /// Deletes the oldest rotated files beyond `Rotation::keep`. Anything
+    /// else in the directory is left alone.
+    fn prune(&self) -> Result<(), io::Error> {
+        let rotated = self.rotated_files()?;
+        let excess = rotated.len().saturating_sub(self.rotation.keep);
+        for (_, _, name) in &rotated[..excess] {
+            fs::remove_file(self.dir().join(name))?;
+        }
+        Ok(())
+    }
+
+    /// UTC time, counter and name of each rotated file next to the current
+    /// one, oldest first. Only names `rotated_path` could have produced
+    /// count.
+    fn rotated_files(&self) -> Result<Vec<(String, u64, String)>, io::Error> {
+        let (stem, extension) = self.name_parts();
+        let mut rotated: Vec<_> = fs::read_dir(self.dir())?
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter_map(|name| {
+                let (time, counter) = rotation_order(&name, &stem, &extension)?;
+                Some((time.to_string(), counter, name))
+            })
+            .collect();
+        rotated.sort();
+        Ok(rotated)
+    }
+
+    fn dir(&self) -> &Path {
+        match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        }
+    }
+
+    /// File stem and extension (with its dot, or empty) of the current file.
+    fn name_parts(&self) -> (String, String) {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let extension = self
+            .path
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+        (stem, extension)
+    }
+}
+
+/// UTC time and counter of a file named `<stem>-YYYY-MM-DDTHHMMSS[-N]<ext>`
+/// by `Recorder::rotated_path`, which order rotations oldest first; `None`
+/// for any other name.
+fn rotation_order<'a>(name: &'a str, stem: &str, extension: &str) -> Option<(&'a str, u64)> {
+    let rest = name
+        .strip_prefix(stem)?
+        .strip_prefix('-')?
+        .strip_suffix(extension)?;
+    let time = rest.get(..17)?;
+    let timestamp = time.bytes().enumerate().all(|(i, byte)| match i {
+        4 | 7 => byte == b'-',
+        10 => byte == b'T',
+        _ => byte.is_ascii_digit(),
+    });
+    if !timestamp {
+        return None;
+    }
+    let counter = match &rest[17..] {
+        "" => 0,
+        suffix => {
+            let digits = suffix.strip_prefix('-')?;
+            if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+                return None;
+            }
+            digits.parse().ok()?
+        }
+    };
+    Some((time, counter))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_files_are_rotated_and_old_ones_pruned() {
+        let dir = std::env::temp_dir().join(format!("dashoxide-record-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("samples.jsonl");
+        let rotation = Rotation {
+            max_size: Some(1),
+            daily: false,
+            keep: 2,
+        };
+        let mut recorder = Recorder::create(&path, rotation).unwrap();
+        for _ in 0..5 {
+            recorder.append(&Snapshot::default()).unwrap();
+        }
+
+        let mut names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names.len(), 3, "{names:?}");
+        assert!(names.contains(&"samples.jsonl".to_string()));
+        assert!(
+            names
+                .iter()
+                .all(|name| name.starts_with("samples") && name.ends_with(".jsonl"))
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn pruning_keeps_the_newest_and_skips_other_files() {
+        let dir = std::env::temp_dir().join(format!("dashoxide-prune-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("samples.jsonl");
+        let unrelated = dir.join("samples-backup.jsonl");
+        fs::write(&unrelated, "keep me\n").unwrap();
+        let rotation = Rotation {
+            max_size: Some(1),
+            daily: false,
+            keep: 3,
+        };
+        // Fourteen samples in well under a second: thirteen rotations, most
+        // of them sharing a timestamp and told apart by counters past 10.
+        let mut recorder = Recorder::create(&path, rotation).unwrap();
+        for timestamp in 0..14 {
+            let snapshot = Snapshot {
+                timestamp,
+                ..Snapshot::default()
+            };
+            recorder.append(&snapshot).unwrap();
+        }
+
+        let mut kept: Vec<u64> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|file| *file != path && *file != unrelated)
+            .map(|file| {
+                let line = fs::read_to_string(file).unwrap();
+                serde_json::from_str::<Snapshot>(line.trim())
+                    .unwrap()
+                    .timestamp
+            })
+            .collect();
+        kept.sort();
+        assert_eq!(kept, [10, 11, 12]);
+        assert_eq!(fs::read_to_string(&unrelated).unwrap(), "keep me\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rotation_order_reads_only_rotated_names() {
+        let order = |name| rotation_order(name, "samples", ".jsonl");
+        assert_eq!(
+            order("samples-2024-05-01T120000.jsonl"),
+            Some(("2024-05-01T120000", 0))
+        );
+        assert_eq!(
+            order("samples-2024-05-01T120000-10.jsonl"),
+            Some(("2024-05-01T120000", 10))
+        );
+        assert!(
+            order("samples-2024-05-01T120000-2.jsonl")
+                < order("samples-2024-05-01T120000-10.jsonl")
+        );
+        assert!(
+            order("samples-2024-05-01T120000.jsonl") < order("samples-2024-05-01T120000-1.jsonl")
+        );
+        assert_eq!(order("samples-backup.jsonl"), None);
+        assert_eq!(order("samples-2024-05-01T120000-x.jsonl"), None);
+        assert_eq!(order("samples-2024-05-01T120000.jsonl.bak"), None);
+    }
+}
@@ -0,0 +1,181 @@
+//! The quick switcher: a fuzzy search over every process, disk and
+//! interface that jumps to the tab showing the chosen one.
+
+use std::cmp::Reverse;
+
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+
+use crate::util::Snapshot;
+
+/// Where picking an entry takes the dashboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    Process(u32),
+    /// A disk, by mount point.
+    Disk(String),
+    /// A network interface, by name.
+    Interface(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub target: Target,
+    /// Text matched against and shown, e.g. "firefox (4242)".
+    pub label: String,
+}
+
+impl Entry {
+    /// Short tag for the kind of item, shown before the label.
+    pub fn kind(&self) -> &'static str {
+        match self.target {
+            Target::Process(_) => "proc",
+            Target::Disk(_) => "disk",
+            Target::Interface(_) => "net",
+        }
+    }
+}
+
+/// An open switcher. The index is taken from the snapshot it was opened
+/// on, so results don't shift under the cursor while typing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Switcher {
+    entries: Vec<Entry>,
+    pub query: String,
+    /// Index into `results()`.
+    pub selected: usize,
+    /// List results alphabetically instead of best match first.
+    pub by_name: bool,
+}
+
+impl Switcher {
+    /// Interfaces not matching `interface_filter` are left out, as they
+    /// are from the Network tab.
+    pub fn new(snapshot: &Snapshot, interface_filter: &str) -> Self {
+        let processes = snapshot.processes.iter().map(|process| Entry {
+            target: Target::Process(process.pid),
+            label: format!("{} ({})", process.name, process.pid),
+        });
+        let disks = snapshot.disks.iter().map(|disk| Entry {
+            target: Target::Disk(disk.mount_point.clone()),
+            label: disk.mount_point.clone(),
+        });
+        let interfaces = snapshot
+            .networks
+            .iter()
+            .filter(|interface| interface.matches(interface_filter))
+            .map(|interface| Entry {
+                target: Target::Interface(interface.name.clone()),
+                label: interface.name.clone(),
+            });
+        Self {
+            entries: processes.chain(disks).chain(interfaces).collect(),
+            query: String::new(),
+            selected: 0,
+            by_name: false,
+        }
+    }
+
+    /// Entries matching the query, best match first unless sorted by name.
+    /// An empty query matches everything.
+    pub fn results(&self) -> Vec<&Entry> {
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let mut results: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let score = matcher.fuzzy_match(&entry.label, &self.query)?;
+                Some((entry, score))
+            })
+            .collect();
+        if self.by_name {
+            results.sort_by(|(a, _), (b, _)| a.label.cmp(&b.label));
+        } else {
+            results.sort_by_key(|&(_, score)| Reverse(score));
+        }
+        results.into_iter().map(|(entry, _)| entry).collect()
+    }
+
+    /// Moves the selection one result up or down, stopping at either end.
+    pub fn move_selection(&mut self, down: bool) {
+        let last = self.results().len().saturating_sub(1);
+        self.selected = if down {
+            (self.selected + 1).min(last)
+        } else {
+            self.selected.saturating_sub(1)
+        };
+    }
+
+    pub fn selected_target(&self) -> Option<Target> {
+        let results = self.results();
+        let entry = results.get(self.selected.min(results.len().checked_sub(1)?))?;
+        Some(entry.target.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{DiskSnapshot, InterfaceSnapshot, ProcessSnapshot, ProcessState};
+
+    fn snapshot() -> Snapshot {
+        let process = |pid, name: &str| ProcessSnapshot {
+            pid,
+            name: name.to_string(),
+            status: ProcessState::Running,
+            ..Default::default()
+        };
+        Snapshot {
+            processes: vec![process(1, "systemd"), process(4242, "firefox")],
+            disks: vec![DiskSnapshot {
+                mount_point: "/data".to_string(),
+                file_system: "ext4".to_string(),
+                ..Default::default()
+            }],
+            networks: vec![InterfaceSnapshot {
+                name: "eth0".to_string(),
+                rx_rate: 0,
+                tx_rate: 0,
+                total_received: 0,
+                total_transmitted: 0,
+                up: None,
+                mac_address: None,
+                mtu: None,
+                addresses: Vec::new(),
+            }],
+            ..Snapshot::default()
+        }
+    }
+
+    #[test]
+    fn fuzzy_query_finds_items_of_every_kind() {
+        let mut switcher = Switcher::new(&snapshot(), "");
+        assert_eq!(switcher.results().len(), 4);
+
+        switcher.query = "ffx".to_string();
+        assert_eq!(switcher.selected_target(), Some(Target::Process(4242)));
+        switcher.query = "DTA".to_string();
+        assert_eq!(
+            switcher.selected_target(),
+            Some(Target::Disk("/data".to_string()))
+        );
+        switcher.query = "zzz".to_string();
+        assert_eq!(switcher.selected_target(), None);
+    }
+
+    #[test]
+    fn selection_moves_within_the_results() {
+        let mut switcher = Switcher::new(&snapshot(), "");
+        switcher.by_name = true;
+        switcher.move_selection(false);
+        assert_eq!(switcher.selected, 0);
+        for _ in 0..10 {
+            switcher.move_selection(true);
+        }
+        assert_eq!(switcher.selected, 3);
+        assert_eq!(
+            switcher.selected_target(),
+            Some(Target::Process(1)),
+            "sorted by name, systemd comes last"
+        );
+    }
+}
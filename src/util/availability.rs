@@ -0,0 +1,33 @@
+/// Whether a metric's underlying data source actually produced anything.
+///
+/// Lets the renderers tell a genuine zero (an idle NIC) apart from a source
+/// that simply isn't reported on this platform or inside this container.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataAvailability {
+    Available,
+    /// The source exists but not enough samples have been taken yet.
+    Collecting,
+    Unavailable,
+    /// The source works but lists nothing, e.g. no disks in a minimal
+    /// container. Holds the message saying what's missing.
+    Empty(&'static str),
+}
+
+impl DataAvailability {
+    pub fn message(self) -> &'static str {
+        match self {
+            DataAvailability::Available => "",
+            DataAvailability::Collecting => "Collecting data...",
+            DataAvailability::Unavailable => "Not supported on this platform",
+            DataAvailability::Empty(message) => message,
+        }
+    }
+
+    /// Nothing to show at all, as opposed to not yet.
+    pub fn is_missing(self) -> bool {
+        matches!(
+            self,
+            DataAvailability::Unavailable | DataAvailability::Empty(_)
+        )
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use super::SystemState;
+
+/// What the initial refresh found on this machine, shown once at startup so
+/// an empty panel can be told apart from absent hardware.
+pub struct DetectionSummary {
+    cpus: usize,
+    total_memory: u64,
+    disks: usize,
+    interfaces: usize,
+    has_swap: bool,
+    components: usize,
+    has_battery: Option<bool>,
+}
+
+impl DetectionSummary {
+    pub fn from_state(state: &SystemState) -> Self {
+        let snapshot = &state.snapshot;
+        Self {
+            cpus: snapshot.cpu.cores.len(),
+            total_memory: snapshot.memory.total,
+            disks: snapshot.disks.len(),
+            interfaces: snapshot.networks.len(),
+            has_swap: snapshot.memory.swap_total > 0,
+            components: snapshot.sensors.len(),
+            has_battery: snapshot.battery,
+        }
+    }
+
+    pub fn details(&self) -> String {
+        let battery = match self.has_battery {
+            Some(true) => "present",
+            Some(false) => "absent",
+            None => "unknown",
+        };
+        format!(
+            "CPUs:               {}\n\
+             Total memory:       {:.2} GiB\n\
+             Disks:              {}\n\
+             Network interfaces: {}\n\
+             Swap:               {}\n\
+             Sensors:            {}\n\
+             Battery:            {}",
+            self.cpus,
+            self.total_memory as f64 / (1_024 * 1_024 * 1_024) as f64,
+            self.disks,
+            self.interfaces,
+            if self.has_swap { "present" } else { "absent" },
+            self.components,
+            battery,
+        )
+    }
+}
+
+impl fmt::Display for DetectionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dashoxide: {} CPUs, {:.1} GiB RAM, {} disks, {} interfaces, swap {}, {} sensors",
+            self.cpus,
+            self.total_memory as f64 / (1_024 * 1_024 * 1_024) as f64,
+            self.disks,
+            self.interfaces,
+            if self.has_swap { "yes" } else { "no" },
+            self.components,
+        )
+    }
+}
@@ -0,0 +1,67 @@
+/// Reduces `data` to roughly `target_points` using min/max bucketing.
+///
+/// Each bucket keeps its lowest and highest sample in their original order,
+/// so short spikes survive even when most of the series is dropped.
+pub fn downsample(data: &[(f64, f64)], target_points: usize) -> Vec<(f64, f64)> {
+    if target_points < 2 || data.len() <= target_points {
+        return data.to_vec();
+    }
+
+    let bucket_size = data.len().div_ceil(target_points / 2);
+    let mut points = Vec::with_capacity(target_points);
+    for bucket in data.chunks(bucket_size) {
+        let mut min = 0;
+        let mut max = 0;
+        for (i, &(_, y)) in bucket.iter().enumerate() {
+            if y < bucket[min].1 {
+                min = i;
+            }
+            if y > bucket[max].1 {
+                max = i;
+            }
+        }
+
+        points.push(bucket[min.min(max)]);
+        if min != max {
+            points.push(bucket[min.max(max)]);
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(len: usize) -> Vec<(f64, f64)> {
+        (0..len).map(|x| (x as f64, 10.0)).collect()
+    }
+
+    #[test]
+    fn a_single_sample_spike_survives() {
+        let mut data = flat(10_000);
+        data[6_789].1 = 95.0;
+        let points = downsample(&data, 200);
+        assert!(points.contains(&(6_789.0, 95.0)));
+    }
+
+    #[test]
+    fn output_stays_within_the_target() {
+        let data: Vec<_> = (0..10_007)
+            .map(|x| (x as f64, (x as f64 / 7.0).sin()))
+            .collect();
+        for target in [2, 3, 100, 333, 5_000] {
+            let points = downsample(&data, target);
+            assert!(points.len() <= target, "{} > {target}", points.len());
+            assert!(points.len() >= target / 2 - 1);
+            assert!(points.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        }
+    }
+
+    #[test]
+    fn short_input_comes_back_unchanged() {
+        let data = flat(50);
+        assert_eq!(downsample(&data, 200), data);
+        assert_eq!(downsample(&data, 50), data);
+    }
+}
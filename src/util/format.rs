@@ -0,0 +1,328 @@
+use std::{ffi::OsStr, str::FromStr, time::Duration};
+
+/// What the CPU, memory, swap and disk gauges print over their bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GaugeLabel {
+    /// "50.0%"
+    Percent,
+    /// "4.0/8.0 GiB"
+    Absolute,
+    /// "4.0/8.0 GiB (50.0%)"
+    Both,
+}
+
+impl FromStr for GaugeLabel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "percent" => Ok(GaugeLabel::Percent),
+            "absolute" => Ok(GaugeLabel::Absolute),
+            "both" => Ok(GaugeLabel::Both),
+            _ => Err(format!(
+                "unknown gauge label '{s}', expected one of: percent, absolute, both"
+            )),
+        }
+    }
+}
+
+/// Most decimals `--precision` accepts for any metric.
+pub const MAX_PRECISION: usize = 6;
+
+/// Decimals shown for each kind of value, so the same kind of number reads
+/// the same on every tab. Gauges follow `percent` unless
+/// `--gauge-precision` overrides it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Precision {
+    /// CPU, memory and disk usage and alert thresholds, in percent.
+    pub percent: usize,
+    /// Amounts of memory, disk space and data transferred.
+    pub memory: usize,
+    /// Network and disk I/O rates.
+    pub rate: usize,
+}
+
+impl Default for Precision {
+    fn default() -> Self {
+        Self {
+            percent: 1,
+            memory: 2,
+            rate: 1,
+        }
+    }
+}
+
+impl Precision {
+    /// "42.0%"
+    pub fn percent(&self, value: f64) -> String {
+        format!("{value:.*}%", self.percent)
+    }
+
+    /// "4.00 GB"
+    pub fn memory(&self, value: f64, unit: &str) -> String {
+        format!("{value:.*} {unit}", self.memory)
+    }
+
+    /// "5.0 MB/s"
+    pub fn rate(&self, value: f64, unit: &str) -> String {
+        format!("{value:.*} {unit}", self.rate)
+    }
+}
+
+impl FromStr for Precision {
+    type Err = String;
+
+    /// Parses comma-separated `metric=decimals` pairs, e.g.
+    /// "percent=2,rate=0"; metrics left out keep their default.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut precision = Self::default();
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (metric, decimals) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected metric=decimals, got '{pair}'"))?;
+            let decimals: usize = decimals
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid number of decimals in '{pair}'"))?;
+            let decimals = decimals.min(MAX_PRECISION);
+            match metric.trim() {
+                "percent" => precision.percent = decimals,
+                "memory" => precision.memory = decimals,
+                "rate" => precision.rate = decimals,
+                other => {
+                    return Err(format!(
+                        "unknown metric '{other}', expected one of: percent, memory, rate"
+                    ));
+                }
+            }
+        }
+        Ok(precision)
+    }
+}
+
+/// Gauge label for `used` out of `total` (both already in `unit`), with
+/// `precision` decimals on every number.
+pub fn gauge_label(
+    style: GaugeLabel,
+    precision: usize,
+    used: f64,
+    total: f64,
+    unit: &str,
+) -> String {
+    let percent = if total > 0.0 {
+        used / total * 100.0
+    } else {
+        0.0
+    };
+    match style {
+        GaugeLabel::Percent => format!("{percent:.precision$}%"),
+        GaugeLabel::Absolute => format!("{used:.precision$}/{total:.precision$} {unit}"),
+        GaugeLabel::Both => {
+            format!("{used:.precision$}/{total:.precision$} {unit} ({percent:.precision$}%)")
+        }
+    }
+}
+
+/// Evenly spaced x-axis labels for a chart spanning `samples` samples taken
+/// every `interval`, oldest first and ending in "now".
+pub fn time_axis_labels(samples: usize, interval: Duration, ticks: usize) -> Vec<String> {
+    let span = interval.as_secs_f64() * samples as f64;
+    let ticks = ticks.max(2);
+    (0..ticks)
+        .map(|i| {
+            let ago = span * (ticks - 1 - i) as f64 / (ticks - 1) as f64;
+            format_ago(ago)
+        })
+        .collect()
+}
+
+/// Formats an age in seconds as a compact "12s ago"-style label.
+pub fn format_ago(seconds: f64) -> String {
+    if seconds < 0.05 {
+        "now".to_string()
+    } else if seconds < 10.0 && seconds.fract() > 0.05 {
+        format!("{seconds:.1}s ago")
+    } else if seconds < 120.0 {
+        format!("{seconds:.0}s ago")
+    } else if seconds < 7200.0 {
+        format!("{:.0}m ago", seconds / 60.0)
+    } else {
+        format!("{:.1}h ago", seconds / 3600.0)
+    }
+}
+
+/// Formats a process age in its largest whole unit, e.g. "45s ago" or
+/// "3d ago".
+pub fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => format!("{seconds}s ago"),
+        60..3_600 => format!("{}m ago", seconds / 60),
+        3_600..86_400 => format!("{}h ago", seconds / 3_600),
+        _ => format!("{}d ago", seconds / 86_400),
+    }
+}
+
+/// Converts an OS string (process name, mount point, ...) for display,
+/// comparison and export.
+///
+/// Invalid UTF-8 becomes U+FFFD and control characters become `?`, so a
+/// hostile or mangled name can neither break JSON nor inject terminal escapes.
+pub fn os_str_display(value: &OsStr) -> String {
+    value
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_control() { '?' } else { c })
+        .collect()
+}
+
+/// Formats seconds since the Unix epoch as a UTC `YYYY-MM-DD HH:MM:SS`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // Civil-from-days, proleptic Gregorian calendar.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Time of day at `timestamp` in the local time zone, followed by the
+/// zone's abbreviation: "14:03:12 CEST". UTC where the zone can't be read.
+pub fn format_local_time(timestamp: u64) -> String {
+    let (offset, zone) = local_zone(timestamp).unwrap_or((0, "UTC".to_string()));
+    let local = timestamp.saturating_add_signed(offset);
+    format!("{} {zone}", &format_timestamp(local)[11..])
+}
+
+/// Offset from UTC in seconds and abbreviation of the local time zone at
+/// `timestamp`, as libc works them out from `TZ` or the system setting.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn local_zone(timestamp: u64) -> Option<(i64, String)> {
+    let time = libc::time_t::try_from(timestamp).ok()?;
+    // SAFETY: an all-zero tm is valid, and localtime_r only writes to it.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() || tm.tm_zone.is_null() {
+        return None;
+    }
+    // SAFETY: tm_zone points at a NUL-terminated string libc keeps alive.
+    let zone = unsafe { std::ffi::CStr::from_ptr(tm.tm_zone) };
+    // tm_gmtoff is a C long, only 32 bits on some targets.
+    #[allow(clippy::useless_conversion)]
+    let offset = i64::from(tm.tm_gmtoff);
+    Some((offset, zone.to_string_lossy().into_owned()))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn local_zone(_timestamp: u64) -> Option<(i64, String)> {
+    None
+}
+
+/// A byte count in KB, MB or GB, whichever reads best.
+pub fn format_total_bytes(bytes: u64, precision: &Precision) -> String {
+    const MB: f64 = 1_000_000.0;
+    const GB: f64 = 1_000.0 * MB;
+    if bytes == 0 {
+        return "0 B".to_string();
+    }
+    let b = bytes as f64;
+    if b < MB {
+        precision.memory(b / 1000.0, "KB")
+    } else if b < GB {
+        precision.memory(b / MB, "MB")
+    } else {
+        precision.memory(b / GB, "GB")
+    }
+}
+
+/// Abbreviates a count to at most four characters plus a suffix, e.g.
+/// "1.2k" or "35M"; counts below a thousand are left as they are.
+pub fn abbreviate_count(count: u64) -> String {
+    const UNITS: [(u64, &str); 3] = [(1_000, "k"), (1_000_000, "M"), (1_000_000_000, "G")];
+    if count < 1_000 {
+        return count.to_string();
+    }
+    // The unit and decimals go by the value as rounded for printing, so
+    // 9,990 reads "10k" rather than "10.0k" and 999,950 "1.0M" rather than
+    // "1000k".
+    let (size, suffix) = UNITS
+        .into_iter()
+        .find(|&(size, _)| (count as f64 / size as f64).round() < 1_000.0)
+        .unwrap_or(UNITS[UNITS.len() - 1]);
+    let value = count as f64 / size as f64;
+    if (value * 10.0).round() < 100.0 {
+        format!("{value:.1}{suffix}")
+    } else {
+        format!("{value:.0}{suffix}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_are_rounded_before_picking_the_unit() {
+        assert_eq!(abbreviate_count(999), "999");
+        assert_eq!(abbreviate_count(1_000), "1.0k");
+        assert_eq!(abbreviate_count(1_234), "1.2k");
+        assert_eq!(abbreviate_count(9_990), "10k");
+        assert_eq!(abbreviate_count(35_000_000), "35M");
+        assert_eq!(abbreviate_count(999_950), "1.0M");
+        assert_eq!(abbreviate_count(999_950_000_000), "1000G");
+    }
+
+    #[test]
+    fn precision_overrides_only_the_named_metrics() {
+        let precision: Precision = "percent=2, rate=0".parse().unwrap();
+        assert_eq!(precision.percent(42.0), "42.00%");
+        assert_eq!(precision.memory(4.0, "GB"), "4.00 GB");
+        assert_eq!(precision.rate(5.04, "MB/s"), "5 MB/s");
+        assert_eq!(
+            "memory=9".parse::<Precision>().unwrap().memory,
+            MAX_PRECISION
+        );
+        assert!("disk=1".parse::<Precision>().is_err());
+        assert!("percent".parse::<Precision>().is_err());
+    }
+
+    #[test]
+    fn ages_use_their_largest_unit() {
+        assert_eq!(format_age(59), "59s ago");
+        assert_eq!(format_age(7_199), "1h ago");
+        assert_eq!(format_age(3 * 86_400 + 5), "3d ago");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn mangled_names_are_safe_to_show_and_export() {
+        use std::os::unix::ffi::OsStrExt;
+
+        use crate::util::snapshot::{ProcessSnapshot, ProcessState};
+
+        let name = os_str_display(OsStr::from_bytes(b"bad\xffname\x1b"));
+        assert_eq!(name, "bad\u{fffd}name?");
+
+        let process = ProcessSnapshot {
+            pid: 7,
+            name,
+            status: ProcessState::Running,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&process).unwrap();
+        let exported: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(exported["name"], process.name);
+    }
+}
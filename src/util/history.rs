@@ -1,70 +1,1160 @@
-use sysinfo::{Disks, Networks, System};
+use std::{
+    collections::HashMap,
+    io,
+    sync::{Arc, Mutex, MutexGuard, PoisonError, TryLockError},
+    time::{Duration, Instant},
+};
+
+use super::{
+    DataAvailability, InterfaceSnapshot, ProcessHistory, ProcessSnapshot, Snapshot,
+    session::SessionStats,
+};
+use crate::collector::{Collector, RefreshDemand, ReplayCommand, ReplayStatus};
+
+/// Number of samples kept in each history buffer.
+pub const HISTORY_LEN: usize = 60;
+
+/// Samples a newly appeared process counts as new for.
+const NEW_PROCESS_SAMPLES: u64 = 2;
+
+/// Locks the shared state even if the collection thread panicked while
+/// holding it. A panic mid-update at worst leaves the histories a sample
+/// apart, so the last data is still worth showing; `Mutex::is_poisoned`
+/// tells callers that no more will come.
+pub fn lock_state(state: &Mutex<SystemState>) -> MutexGuard<'_, SystemState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn io_totals(snapshot: &Snapshot) -> HashMap<u32, (u64, u64)> {
+    snapshot
+        .processes
+        .iter()
+        .map(|process| {
+            (
+                process.pid,
+                (process.disk_total_read, process.disk_total_written),
+            )
+        })
+        .collect()
+}
+
+/// How near the top and bottom of the 32-bit range two counter readings
+/// must be for a decrease between them to count as a wrap: an eighth of it,
+/// 512 MiB, far more than a 32-bit counter's link carries between samples.
+const WRAP_MARGIN: u64 = u32::MAX as u64 / 8;
+
+/// Bytes counted between two readings of a cumulative interface counter.
+///
+/// Counters only go down when they wrap or restart. A counter that was just
+/// below `u32::MAX` and is now just above zero is taken to have wrapped, as
+/// 32-bit kernels' and some drivers' counters do; anything else going
+/// backwards is a reset (an interface brought down and up, a driver reload)
+/// and gives `None`, since the traffic in between is unknown.
+fn counter_delta(previous: u64, current: u64) -> Option<u64> {
+    if current >= previous {
+        return Some(current - previous);
+    }
+    let max = u64::from(u32::MAX);
+    (previous <= max && previous > max - WRAP_MARGIN && current < WRAP_MARGIN)
+        .then(|| max - previous + current + 1)
+}
+
+/// The chart histories as they were at one moment, drawn dimmed behind the
+/// live charts to compare before and after a change.
+#[derive(Clone)]
+pub struct Ghost {
+    pub cpu_history: Vec<f32>,
+    pub memory_history: Vec<(u64, u64)>,
+    pub network_history: Vec<(u64, u64)>,
+    device_io_history: HashMap<String, Vec<(u64, u64)>>,
+}
+
+impl Ghost {
+    /// Like `SystemState::device_io`, as it was.
+    pub fn device_io(&self, device: Option<&str>) -> Vec<(u64, u64)> {
+        device_io(&self.device_io_history, device)
+    }
+}
+
+fn device_io(
+    histories: &HashMap<String, Vec<(u64, u64)>>,
+    device: Option<&str>,
+) -> Vec<(u64, u64)> {
+    if let Some(device) = device {
+        return histories.get(device).cloned().unwrap_or_default();
+    }
+    let len = histories.values().map(Vec::len).max();
+    let mut total = vec![(0, 0); len.unwrap_or(0)];
+    for history in histories.values() {
+        let offset = total.len() - history.len();
+        for (sum, &(read, write)) in total[offset..].iter_mut().zip(history) {
+            sum.0 += read;
+            sum.1 += write;
+        }
+    }
+    total
+}
+
+/// Mean of the last `window` rate pairs, or zeros with no samples yet.
+fn mean_rate(history: &[(u64, u64)], window: usize) -> (u64, u64) {
+    let recent = &history[history.len().saturating_sub(window)..];
+    let Some(count) = u64::try_from(recent.len()).ok().filter(|&count| count > 0) else {
+        return (0, 0);
+    };
+    let (first, second) = recent
+        .iter()
+        .fold((0, 0), |(first, second), &(a, b)| (first + a, second + b));
+    (first / count, second / count)
+}
+
+/// The collector, behind a lock of its own so a slow collection (an ssh
+/// round trip, say) doesn't hold the state lock the UI draws under.
+pub type SharedCollector = Arc<Mutex<Box<dyn Collector>>>;
+
+fn lock_collector(collector: &Mutex<Box<dyn Collector>>) -> MutexGuard<'_, Box<dyn Collector>> {
+    collector.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The collector for a command from the UI thread, or a `WouldBlock` error
+/// while it's busy taking a sample: waiting would freeze input and drawing
+/// for as long as the sample takes.
+fn try_lock_collector(
+    collector: &Mutex<Box<dyn Collector>>,
+) -> Result<MutexGuard<'_, Box<dyn Collector>>, io::Error> {
+    match collector.try_lock() {
+        Ok(collector) => Ok(collector),
+        Err(TryLockError::Poisoned(e)) => Ok(e.into_inner()),
+        Err(TryLockError::WouldBlock) => {
+            Err(io::Error::new(io::ErrorKind::WouldBlock, "collector busy"))
+        }
+    }
+}
+
+/// Takes one sample from `collector`, holding only its lock.
+pub fn collect(collector: &Mutex<Box<dyn Collector>>) -> Result<Snapshot, io::Error> {
+    lock_collector(collector).collect()
+}
 
 pub struct SystemState {
-    pub system: System,
-    pub disks: Disks,
-    pub networks: Networks,
+    collector: SharedCollector,
+    /// Asked of the collector once; they don't change.
+    own_pid: Option<u32>,
+    provides_rates: bool,
+    /// The collector's playback position as of the last sample or command,
+    /// so drawing doesn't wait on the collector.
+    replay_status: Option<ReplayStatus>,
+    /// Demand that came in while the collector was busy, handed to it
+    /// before the next sample.
+    pending_demand: Option<RefreshDemand>,
+    /// The most recent successful sample.
+    pub snapshot: Snapshot,
     pub cpu_history: Vec<f32>,
+    /// CPU temperature alongside each `cpu_history` entry, `None` for
+    /// samples without a CPU sensor reading.
+    pub cpu_temperature_history: Vec<Option<f32>>,
+    /// Usage history of each core, indexed like `CpuSnapshot::cores`.
+    pub core_history: Vec<Vec<f32>>,
     pub memory_history: Vec<(u64, u64)>,
-    pub disk_history: Vec<(u64, u64)>,
+    /// Used and total swap bytes, one entry per sample.
+    pub swap_history: Vec<(u64, u64)>,
+    /// Used and total bytes over all disks, one entry per sample.
+    pub disk_space_history: Vec<(u64, u64)>,
+    /// Read/write rates in bytes per second of each disk device, keyed by
+    /// device name, one entry per sample since the device was first seen.
+    pub device_io_history: HashMap<String, Vec<(u64, u64)>>,
+    /// Aggregate receive/transmit rates in bytes per second, one entry per
+    /// sample.
     pub network_history: Vec<(u64, u64)>,
+    /// Comma-separated globs naming the interfaces counted into
+    /// `network_history` and the session totals; see `counts_interface`.
+    pub interface_filter: String,
+    /// Count loopback and container interfaces into the network totals too.
+    /// Without a filter they're left out so local traffic doesn't pass for
+    /// network traffic; with one, the filter alone decides.
+    pub network_total_all: bool,
+    /// Leave this dashoxide process out of the session's top process, as
+    /// the dashboard leaves it out of its lists.
+    pub hide_self: bool,
+    /// Bytes received/transmitted since launch (or the last reset), summed
+    /// from per-tick deltas so it never goes backwards when an interface's
+    /// own counters reset.
+    pub session_totals: (u64, u64),
+    /// Context switches and interrupts per second over the last sample,
+    /// from the difference of the snapshots' counters. `None` until two
+    /// samples with counters have come in.
+    pub kernel_rates: Option<(u64, u64)>,
+    /// Number of completed `update` calls, so readers can tell new samples
+    /// apart from redraws of the same one.
+    pub sample_count: u64,
+    pub session: SessionStats,
+    /// Why the most recent collection failed, cleared on the next success.
+    pub last_error: Option<String>,
+    /// How long the last collection took. The state lock is only held to
+    /// apply its result, not while collecting.
+    pub update_duration: Duration,
+    /// Samples aren't taken while set, leaving the data as it was.
+    pub paused: bool,
+    /// When the last successful sample was applied; see `last_sample_age`.
+    pub last_sample_at: Instant,
+    /// When the collector last refreshed processes, which it may skip for
+    /// several samples; their I/O rates are over the time since.
+    process_io_at: Instant,
+    /// Likewise for disks.
+    disk_io_at: Instant,
+    /// `sample_count` at which each current PID first appeared; 0 for
+    /// processes that were already running at launch.
+    process_first_seen: HashMap<u32, u64>,
+    /// Histories of the processes the dashboard follows, in the order set
+    /// by `track_processes`.
+    pub tracked_processes: Vec<ProcessHistory>,
+    /// Lifetime read/written bytes of each process at the last sample, for
+    /// deriving its I/O rates. Rebuilt every sample, so exited PIDs drop
+    /// out.
+    process_io_totals: HashMap<u32, (u64, u64)>,
 }
 
 impl SystemState {
-    pub fn new() -> Self {
-        let mut system = System::new_all();
-        system.refresh_all();
+    pub fn new(mut collector: Box<dyn Collector>) -> Result<Self, io::Error> {
+        let snapshot = collector.collect()?;
+        let process_first_seen = snapshot.processes.iter().map(|p| (p.pid, 0)).collect();
+        let process_io_totals = io_totals(&snapshot);
 
-        let disks = Disks::new_with_refreshed_list();
+        Ok(Self {
+            own_pid: collector.own_pid(),
+            provides_rates: collector.provides_rates(),
+            replay_status: collector.replay_status(),
+            collector: Arc::new(Mutex::new(collector)),
+            pending_demand: None,
+            snapshot,
+            cpu_history: Vec::with_capacity(HISTORY_LEN),
+            cpu_temperature_history: Vec::with_capacity(HISTORY_LEN),
+            core_history: Vec::new(),
+            memory_history: Vec::with_capacity(HISTORY_LEN),
+            swap_history: Vec::with_capacity(HISTORY_LEN),
+            disk_space_history: Vec::with_capacity(HISTORY_LEN),
+            device_io_history: HashMap::new(),
+            network_history: Vec::with_capacity(HISTORY_LEN),
+            interface_filter: String::new(),
+            network_total_all: false,
+            hide_self: false,
+            session_totals: (0, 0),
+            kernel_rates: None,
+            sample_count: 0,
+            session: SessionStats::default(),
+            last_error: None,
+            update_duration: Duration::ZERO,
+            paused: false,
+            last_sample_at: Instant::now(),
+            process_io_at: Instant::now(),
+            disk_io_at: Instant::now(),
+            process_first_seen,
+            tracked_processes: Vec::new(),
+            process_io_totals,
+        })
+    }
 
-        let networks: Networks = Networks::new_with_refreshed_list();
+    /// Takes a sample and applies it, all under the caller's lock. The
+    /// collection thread uses `begin_sample` and `finish_sample` instead,
+    /// so it can let go of the lock in between.
+    pub fn update(&mut self) {
+        let Some(collector) = self.begin_sample() else {
+            return;
+        };
+        let started = Instant::now();
+        let result = collect(&collector);
+        self.finish_sample(result, started.elapsed());
+    }
 
-        Self {
-            system,
-            disks,
-            networks,
-            cpu_history: Vec::with_capacity(60),
-            memory_history: Vec::with_capacity(60),
-            disk_history: Vec::with_capacity(60),
-            network_history: Vec::with_capacity(60),
+    /// The collector to take the next sample from, with any pending demand
+    /// handed over, or `None` while sampling is paused. A paused replay
+    /// would only repeat its current frame into the histories.
+    pub fn begin_sample(&mut self) -> Option<SharedCollector> {
+        if self.paused || self.replay_status.is_some_and(|status| status.paused) {
+            return None;
+        }
+        if let Some(demand) = self.pending_demand.take() {
+            lock_collector(&self.collector).set_demand(demand);
         }
+        Some(Arc::clone(&self.collector))
     }
 
-    pub fn update(&mut self) {
-        self.system.refresh_all();
-        self.networks.refresh(true);
+    /// Applies a sample collected from `begin_sample`'s collector, unless
+    /// sampling was paused in the meantime.
+    pub fn finish_sample(&mut self, result: Result<Snapshot, io::Error>, duration: Duration) {
+        if !self.paused {
+            self.apply_result(result, duration);
+        }
+    }
 
-        let cpu_usage = self.system.global_cpu_usage();
+    fn apply_result(&mut self, result: Result<Snapshot, io::Error>, duration: Duration) {
+        self.update_duration = duration;
+        self.replay_status = lock_collector(&self.collector).replay_status();
+        match result {
+            Ok(snapshot) => {
+                self.apply(snapshot);
+                self.last_error = None;
+            }
+            Err(e) => self.last_error = Some(e.to_string()),
+        }
+    }
+
+    fn apply(&mut self, mut snapshot: Snapshot) {
+        let elapsed = self
+            .last_sample_at
+            .elapsed()
+            .as_secs_f64()
+            .max(f64::EPSILON);
+        self.last_sample_at = Instant::now();
+
+        let cpu_usage = snapshot.cpu.usage;
         self.cpu_history.push(cpu_usage);
-        if self.cpu_history.len() > 60 {
+        if self.cpu_history.len() > HISTORY_LEN {
             self.cpu_history.remove(0);
         }
+        self.cpu_temperature_history
+            .push(snapshot.cpu_temperature());
+        if self.cpu_temperature_history.len() > HISTORY_LEN {
+            self.cpu_temperature_history.remove(0);
+        }
 
-        let memory_used = self.system.used_memory();
-        let memory_total = self.system.total_memory();
+        self.core_history
+            .resize_with(snapshot.cpu.cores.len(), || Vec::with_capacity(HISTORY_LEN));
+        for (history, &usage) in self.core_history.iter_mut().zip(&snapshot.cpu.cores) {
+            history.push(usage);
+            if history.len() > HISTORY_LEN {
+                history.remove(0);
+            }
+        }
+
+        let memory_used = snapshot.memory.used;
+        let memory_total = snapshot.memory.total;
         self.memory_history.push((memory_used, memory_total));
-        if self.memory_history.len() > 60 {
+        if self.memory_history.len() > HISTORY_LEN {
             self.memory_history.remove(0);
         }
+        self.swap_history
+            .push((snapshot.memory.swap_used, snapshot.memory.swap_total));
+        if self.swap_history.len() > HISTORY_LEN {
+            self.swap_history.remove(0);
+        }
+        self.disk_space_history.push(snapshot.disk_space(false));
+        if self.disk_space_history.len() > HISTORY_LEN {
+            self.disk_space_history.remove(0);
+        }
 
-        for (pid, process) in self.system.processes() {
-            let disk_usage = process.disk_usage();
-            let disk_stats: (u64, u64) = (disk_usage.read_bytes, disk_usage.written_bytes);
+        // Like interfaces, a process seen for the first time starts at zero,
+        // and so does one whose totals went down, which means its PID was
+        // reused. Processes the collector didn't refresh keep their rates.
+        if snapshot.processes_stale && !self.provides_rates {
+            let previous: HashMap<u32, (u64, u64)> = self
+                .snapshot
+                .processes
+                .iter()
+                .map(|process| {
+                    (
+                        process.pid,
+                        (process.disk_read_rate, process.disk_write_rate),
+                    )
+                })
+                .collect();
+            for process in &mut snapshot.processes {
+                (process.disk_read_rate, process.disk_write_rate) =
+                    previous.get(&process.pid).copied().unwrap_or_default();
+            }
+        } else if !self.provides_rates {
+            let elapsed = self.process_io_at.elapsed().as_secs_f64().max(f64::EPSILON);
+            self.process_io_at = Instant::now();
+            for process in &mut snapshot.processes {
+                let (prev_read, prev_written) = self
+                    .process_io_totals
+                    .get(&process.pid)
+                    .copied()
+                    .unwrap_or((process.disk_total_read, process.disk_total_written));
+                let read = process.disk_total_read.saturating_sub(prev_read);
+                let written = process.disk_total_written.saturating_sub(prev_written);
+                process.disk_read_rate = (read as f64 / elapsed) as u64;
+                process.disk_write_rate = (written as f64 / elapsed) as u64;
+            }
+        }
+        self.process_io_totals = io_totals(&snapshot);
 
-            self.disk_history.push(disk_stats);
-            if self.disk_history.len() > 60 {
-                self.disk_history.remove(0);
+        // Devices are matched like interfaces below. Every mount of a device
+        // reports the same totals, so each device counts once. Like
+        // processes, disks the collector didn't refresh keep their rates.
+        let previous: HashMap<&str, (u64, u64, u64, u64)> = self
+            .snapshot
+            .disks
+            .iter()
+            .map(|disk| {
+                (
+                    disk.device.as_str(),
+                    (
+                        disk.total_read,
+                        disk.total_written,
+                        disk.read_rate,
+                        disk.write_rate,
+                    ),
+                )
+            })
+            .collect();
+        let disk_elapsed = self.disk_io_at.elapsed().as_secs_f64().max(f64::EPSILON);
+        if !snapshot.disks_stale {
+            self.disk_io_at = Instant::now();
+        }
+        let mut device_rates = HashMap::new();
+        for disk in &mut snapshot.disks {
+            if disk.device.is_empty() {
+                continue;
+            }
+            let previous = previous.get(disk.device.as_str()).copied();
+            if snapshot.disks_stale && !self.provides_rates {
+                (disk.read_rate, disk.write_rate) = previous
+                    .map(|(_, _, read_rate, write_rate)| (read_rate, write_rate))
+                    .unwrap_or_default();
+            } else if !self.provides_rates {
+                let (prev_read, prev_written, _, _) =
+                    previous.unwrap_or((disk.total_read, disk.total_written, 0, 0));
+                let read = disk.total_read.saturating_sub(prev_read);
+                let written = disk.total_written.saturating_sub(prev_written);
+                disk.read_rate = (read as f64 / disk_elapsed) as u64;
+                disk.write_rate = (written as f64 / disk_elapsed) as u64;
             }
+            device_rates.insert(disk.device.clone(), (disk.read_rate, disk.write_rate));
         }
+        self.device_io_history
+            .retain(|device, _| device_rates.contains_key(device));
+        for (device, rates) in device_rates {
+            let history = self.device_io_history.entry(device).or_default();
+            history.push(rates);
+            if history.len() > HISTORY_LEN {
+                history.remove(0);
+            }
+        }
+
+        // Interfaces are matched by name against the previous snapshot only:
+        // one that just appeared starts at a rate of zero instead of spiking
+        // by its whole counter, and one that went away is simply gone. A
+        // counter that reset is a gap the same way: zero for this sample,
+        // counted from the new value on.
+        let previous: HashMap<&str, (u64, u64)> = self
+            .snapshot
+            .networks
+            .iter()
+            .map(|i| (i.name.as_str(), (i.total_received, i.total_transmitted)))
+            .collect();
         let mut rx_bytes = 0;
         let mut tx_bytes = 0;
-        for (_, data) in self.networks.list() {
-            rx_bytes += data.received();
-            tx_bytes += data.transmitted();
+        let mut rx_rate = 0;
+        let mut tx_rate = 0;
+        for interface in &mut snapshot.networks {
+            let (prev_rx, prev_tx) = previous
+                .get(interface.name.as_str())
+                .copied()
+                .unwrap_or((interface.total_received, interface.total_transmitted));
+            let rx = counter_delta(prev_rx, interface.total_received).unwrap_or(0);
+            let tx = counter_delta(prev_tx, interface.total_transmitted).unwrap_or(0);
+            if !self.provides_rates {
+                interface.rx_rate = (rx as f64 / elapsed) as u64;
+                interface.tx_rate = (tx as f64 / elapsed) as u64;
+            }
+
+            if !self.counts_interface(interface) {
+                continue;
+            }
+            rx_bytes += rx;
+            tx_bytes += tx;
+            rx_rate += interface.rx_rate;
+            tx_rate += interface.tx_rate;
         }
-        self.network_history.push((rx_bytes, tx_bytes));
-        if self.network_history.len() > 60 {
+        self.session_totals.0 = self.session_totals.0.saturating_add(rx_bytes);
+        self.session_totals.1 = self.session_totals.1.saturating_add(tx_bytes);
+        self.network_history.push((rx_rate, tx_rate));
+        if self.network_history.len() > HISTORY_LEN {
             self.network_history.remove(0);
         }
+
+        // A counter that went backwards (a reboot behind a remote or replayed
+        // source) gives no rate for this sample rather than a huge one.
+        self.kernel_rates = self
+            .snapshot
+            .kernel_counters
+            .zip(snapshot.kernel_counters)
+            .and_then(|(previous, current)| {
+                let switches = current
+                    .context_switches
+                    .checked_sub(previous.context_switches)?;
+                let interrupts = current.interrupts.checked_sub(previous.interrupts)?;
+                Some((
+                    (switches as f64 / elapsed) as u64,
+                    (interrupts as f64 / elapsed) as u64,
+                ))
+            });
+
+        let hidden = self.own_pid.filter(|_| self.hide_self);
+        self.session.observe(&snapshot, rx_bytes, tx_bytes, hidden);
+        self.snapshot = snapshot;
+        self.sample_count += 1;
+
+        // Forgetting exited PIDs means a reused PID counts as new again.
+        let mut first_seen = HashMap::with_capacity(self.snapshot.processes.len());
+        for process in &self.snapshot.processes {
+            let seen = self
+                .process_first_seen
+                .get(&process.pid)
+                .copied()
+                .unwrap_or(self.sample_count);
+            first_seen.insert(process.pid, seen);
+        }
+        self.process_first_seen = first_seen;
+
+        for history in &mut self.tracked_processes {
+            history.observe(&self.snapshot);
+        }
+    }
+
+    /// Time since the last successful sample, which keeps growing while
+    /// collection fails or stalls.
+    pub fn last_sample_age(&self) -> Duration {
+        self.last_sample_at.elapsed()
+    }
+
+    /// Whether `pid` appeared within the last couple of samples, which makes
+    /// fork storms and short-lived processes stand out.
+    pub fn is_new_process(&self, pid: u32) -> bool {
+        self.process_first_seen
+            .get(&pid)
+            .is_some_and(|&seen| seen > 0 && self.sample_count - seen < NEW_PROCESS_SAMPLES)
+    }
+
+    /// Follows exactly `pids` from now on. Histories of PIDs already
+    /// followed are kept; new ones start from the latest snapshot.
+    pub fn track_processes(&mut self, pids: &[u32]) {
+        let mut previous = std::mem::take(&mut self.tracked_processes);
+        for &pid in pids {
+            let history = match previous.iter().position(|history| history.pid == pid) {
+                Some(index) => previous.swap_remove(index),
+                None => {
+                    let mut history = ProcessHistory::new(pid);
+                    history.observe(&self.snapshot);
+                    history
+                }
+            };
+            self.tracked_processes.push(history);
+        }
+    }
+
+    /// PID of this dashoxide process, when the collector watches the machine
+    /// it runs on.
+    pub fn own_pid(&self) -> Option<u32> {
+        self.own_pid
+    }
+
+    /// This dashoxide process in the latest snapshot, when the snapshot is
+    /// of the machine it runs on. As stale as the process list, which is
+    /// only refreshed every tick while a view shows processes.
+    pub fn own_process(&self) -> Option<&ProcessSnapshot> {
+        let pid = self.own_pid()?;
+        self.snapshot
+            .processes
+            .iter()
+            .find(|process| process.pid == pid)
+    }
+
+    pub fn process_history(&self, pid: u32) -> Option<&ProcessHistory> {
+        self.tracked_processes
+            .iter()
+            .find(|history| history.pid == pid)
+    }
+
+    /// Hands `demand` to the collector now if it's idle, or before the
+    /// next sample if it's busy collecting.
+    pub fn set_demand(&mut self, demand: RefreshDemand) {
+        match self.collector.try_lock() {
+            Ok(mut collector) => {
+                collector.set_demand(demand);
+                self.pending_demand = None;
+            }
+            Err(_) => self.pending_demand = Some(demand),
+        }
+    }
+
+    pub fn replay_status(&self) -> Option<ReplayStatus> {
+        self.replay_status
+    }
+
+    /// Applies a playback command and shows its effect right away rather
+    /// than on the next tick. Like the other commands below, fails with
+    /// `WouldBlock` instead of waiting while the collector is busy.
+    pub fn control_replay(&mut self, command: ReplayCommand) -> Result<(), io::Error> {
+        let mut collector = try_lock_collector(&self.collector)?;
+        collector.control_replay(command);
+        self.replay_status = collector.replay_status();
+        if let ReplayCommand::Step(_) = command {
+            let started = Instant::now();
+            let result = collector.collect();
+            drop(collector);
+            self.apply_result(result, started.elapsed());
+        }
+        Ok(())
+    }
+
+    /// Kills every PID in one go, returning those that failed and why.
+    pub fn kill(&mut self, pids: &[u32]) -> Result<Vec<(u32, io::Error)>, io::Error> {
+        let mut collector = try_lock_collector(&self.collector)?;
+        Ok(pids
+            .iter()
+            .filter_map(|&pid| collector.kill(pid).err().map(|e| (pid, e)))
+            .collect())
+    }
+
+    /// Sets the nice value of each PID like `kill`, and shows the new ones
+    /// in the latest snapshot straight away.
+    pub fn renice(&mut self, changes: &[(u32, i32)]) -> Result<Vec<(u32, io::Error)>, io::Error> {
+        let mut collector = try_lock_collector(&self.collector)?;
+        let mut failures = Vec::new();
+        for &(pid, nice) in changes {
+            match collector.renice(pid, nice) {
+                Ok(()) => {
+                    if let Some(process) = self.snapshot.processes.iter_mut().find(|p| p.pid == pid)
+                    {
+                        process.nice = Some(nice);
+                    }
+                }
+                Err(e) => failures.push((pid, e)),
+            }
+        }
+        Ok(failures)
+    }
+
+    pub fn environment(&mut self, pid: u32) -> Result<Vec<String>, io::Error> {
+        try_lock_collector(&self.collector)?.environment(pid)
+    }
+
+    /// Total network rates averaged over the last `window` samples, a
+    /// steadier figure than the newest sample alone; a window of 1 is just
+    /// that sample. The history itself keeps every sample for the charts.
+    pub fn network_rate(&self, window: usize) -> (u64, u64) {
+        mean_rate(&self.network_history, window)
+    }
+
+    /// Read/write rates of one disk device, or of every device with `None`,
+    /// averaged over the last `window` samples like `network_rate`.
+    pub fn device_rate(&self, device: Option<&str>, window: usize) -> (u64, u64) {
+        mean_rate(&self.device_io(device), window)
+    }
+
+    /// Whether the interface's traffic is summed into `network_history` and
+    /// the session totals.
+    pub fn counts_interface(&self, interface: &InterfaceSnapshot) -> bool {
+        if self.interface_filter.is_empty() {
+            self.network_total_all || !interface.is_virtual()
+        } else {
+            interface.matches(&self.interface_filter)
+        }
+    }
+
+    /// Read/write rates of one disk device, or summed over every device
+    /// with `None`, oldest first. Devices seen for fewer samples count from
+    /// when they appeared.
+    pub fn device_io(&self, device: Option<&str>) -> Vec<(u64, u64)> {
+        device_io(&self.device_io_history, device)
+    }
+
+    /// Copies the histories the charts draw.
+    pub fn ghost(&self) -> Ghost {
+        Ghost {
+            cpu_history: self.cpu_history.clone(),
+            memory_history: self.memory_history.clone(),
+            network_history: self.network_history.clone(),
+            device_io_history: self.device_io_history.clone(),
+        }
+    }
+
+    pub fn reset_session_totals(&mut self) {
+        self.session_totals = (0, 0);
+    }
+
+    /// Drops every history buffer and cumulative counter so observation
+    /// starts over from the next sample. The latest snapshot is kept, so the
+    /// gauges and tables stay populated while the charts refill.
+    pub fn reset_history(&mut self) {
+        self.cpu_history.clear();
+        self.cpu_temperature_history.clear();
+        self.core_history.clear();
+        self.memory_history.clear();
+        self.swap_history.clear();
+        self.disk_space_history.clear();
+        self.device_io_history.clear();
+        self.network_history.clear();
+        for history in &mut self.tracked_processes {
+            history.samples.clear();
+        }
+        self.reset_session_totals();
+        self.session = SessionStats::default();
+    }
+
+    pub fn cpu_availability(&self) -> DataAvailability {
+        if self.snapshot.cpu.cores.is_empty() {
+            DataAvailability::Unavailable
+        } else if self.cpu_history.is_empty() {
+            DataAvailability::Collecting
+        } else {
+            DataAvailability::Available
+        }
+    }
+
+    pub fn memory_availability(&self) -> DataAvailability {
+        if self.snapshot.memory.total == 0 {
+            DataAvailability::Unavailable
+        } else {
+            DataAvailability::Available
+        }
+    }
+
+    /// A total of zero means either no swap is configured or the platform
+    /// doesn't report it; both are shown the same way.
+    pub fn swap_availability(&self) -> DataAvailability {
+        if self.snapshot.memory.swap_total == 0 {
+            DataAvailability::Unavailable
+        } else {
+            DataAvailability::Available
+        }
+    }
+
+    pub fn disk_availability(&self) -> DataAvailability {
+        if self.snapshot.disks.is_empty() {
+            DataAvailability::Empty("No disks detected")
+        } else {
+            DataAvailability::Available
+        }
+    }
+
+    /// Rates need a sample after the initial one, so a fresh state is still
+    /// collecting even when interfaces are present.
+    pub fn network_availability(&self) -> DataAvailability {
+        if self.snapshot.networks.is_empty() {
+            DataAvailability::Empty("No network interfaces detected")
+        } else if self.network_history.is_empty() {
+            DataAvailability::Collecting
+        } else {
+            DataAvailability::Available
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{DiskSnapshot, KernelCountersSnapshot, ProcessSnapshot, ProcessState};
+
+    struct Sequence(Vec<Snapshot>);
+
+    impl Collector for Sequence {
+        fn collect(&mut self) -> Result<Snapshot, io::Error> {
+            Ok(self.0.remove(0))
+        }
+    }
+
+    fn interface(name: &str, total_received: u64) -> InterfaceSnapshot {
+        InterfaceSnapshot {
+            name: name.to_string(),
+            rx_rate: 0,
+            tx_rate: 0,
+            total_received,
+            total_transmitted: 0,
+            up: None,
+            mac_address: None,
+            mtu: None,
+            addresses: Vec::new(),
+        }
+    }
+
+    fn with_interfaces(networks: Vec<InterfaceSnapshot>) -> Snapshot {
+        Snapshot {
+            networks,
+            ..Snapshot::default()
+        }
+    }
+
+    #[test]
+    fn rates_average_over_the_window() {
+        let mut state = SystemState::new(Box::new(Sequence(vec![Snapshot::default()]))).unwrap();
+        state.network_history = vec![(100, 10), (300, 30), (200, 0)];
+        assert_eq!(state.network_rate(1), (200, 0));
+        assert_eq!(state.network_rate(2), (250, 15));
+        assert_eq!(state.network_rate(10), (200, 13));
+        state.network_history.clear();
+        assert_eq!(state.network_rate(3), (0, 0));
+
+        state.device_io_history = HashMap::from([
+            ("sda".to_string(), vec![(100, 0), (300, 40)]),
+            ("sdb".to_string(), vec![(10, 10)]),
+        ]);
+        assert_eq!(state.device_rate(Some("sda"), 2), (200, 20));
+        assert_eq!(state.device_rate(None, 1), (310, 50));
+        assert_eq!(state.device_rate(Some("sdc"), 2), (0, 0));
+    }
+
+    #[test]
+    fn counters_that_wrap_or_reset_do_not_spike() {
+        assert_eq!(counter_delta(1_000, 3_000), Some(2_000));
+        assert_eq!(
+            counter_delta(u64::from(u32::MAX) - 99, 100),
+            Some(200),
+            "32-bit wrap"
+        );
+        assert_eq!(counter_delta(5_000_000_000, 1_000), None, "64-bit reset");
+        assert_eq!(
+            counter_delta(2_000_000_000, 1_000),
+            None,
+            "reset, not a wrap"
+        );
+        assert_eq!(
+            counter_delta(3 << 30, 100 << 20),
+            None,
+            "reset of a 64-bit counter past 2 GiB"
+        );
+
+        let collector = Sequence(vec![
+            with_interfaces(vec![interface("eth0", 9_000_000)]),
+            with_interfaces(vec![interface("eth0", 500)]),
+            with_interfaces(vec![interface("eth0", 1_500)]),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.update();
+        assert_eq!(state.session_totals.0, 0, "the reset is a gap");
+        state.update();
+        assert_eq!(state.session_totals.0, 1_000);
+    }
+
+    #[test]
+    fn interfaces_that_appear_and_disappear_are_tracked() {
+        let collector = Sequence(vec![
+            with_interfaces(vec![interface("eth0", 1_000)]),
+            with_interfaces(vec![interface("eth0", 2_000), interface("tun0", 5_000_000)]),
+            with_interfaces(vec![interface("eth0", 3_000)]),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+
+        state.update();
+        let names: Vec<_> = state.snapshot.networks.iter().map(|i| &i.name).collect();
+        assert_eq!(names, ["eth0", "tun0"]);
+        assert_eq!(state.snapshot.networks[1].rx_rate, 0);
+        assert_eq!(state.session_totals.0, 1_000);
+
+        state.update();
+        let names: Vec<_> = state.snapshot.networks.iter().map(|i| &i.name).collect();
+        assert_eq!(names, ["eth0"]);
+        assert_eq!(state.session_totals.0, 2_000);
+    }
+
+    #[test]
+    fn loopback_is_left_out_of_totals_unless_asked_for() {
+        let collector = Sequence(vec![
+            with_interfaces(vec![interface("eth0", 1_000), interface("lo", 1_000)]),
+            with_interfaces(vec![interface("eth0", 2_000), interface("lo", 9_000)]),
+            with_interfaces(vec![interface("eth0", 3_000), interface("lo", 17_000)]),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.update();
+        assert_eq!(state.session_totals.0, 1_000);
+        assert_eq!(state.snapshot.networks.len(), 2, "lo is still listed");
+
+        state.network_total_all = true;
+        state.update();
+        assert_eq!(state.session_totals.0, 10_000);
+    }
+
+    #[test]
+    fn only_matching_interfaces_are_summed() {
+        let collector = Sequence(vec![
+            with_interfaces(vec![interface("eth0", 1_000), interface("veth9", 1_000)]),
+            with_interfaces(vec![interface("eth0", 2_000), interface("veth9", 9_000)]),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.interface_filter = "eth*,en*".to_string();
+
+        state.update();
+        assert_eq!(state.session_totals.0, 1_000);
+        assert_eq!(
+            state.network_history[0].0,
+            state.snapshot.networks[0].rx_rate
+        );
+        assert!(
+            state.snapshot.networks[1].rx_rate > 0,
+            "others still get their own rates"
+        );
+    }
+
+    #[test]
+    fn kernel_rates_come_from_counter_differences() {
+        let with_counters = |context_switches, interrupts| Snapshot {
+            kernel_counters: Some(KernelCountersSnapshot {
+                context_switches,
+                interrupts,
+            }),
+            ..Snapshot::default()
+        };
+        let collector = Sequence(vec![
+            Snapshot::default(),
+            with_counters(1_000, 500),
+            with_counters(3_000, 500),
+            with_counters(10, 10),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.update();
+        assert_eq!(state.kernel_rates, None, "no previous counters");
+        state.update();
+        let (switches, interrupts) = state.kernel_rates.unwrap();
+        assert!(switches > 0);
+        assert_eq!(interrupts, 0);
+        state.update();
+        assert_eq!(state.kernel_rates, None, "counters went backwards");
+    }
+
+    fn with_pids(pids: &[u32]) -> Snapshot {
+        let processes = pids
+            .iter()
+            .map(|&pid| ProcessSnapshot {
+                pid,
+                name: format!("p{pid}"),
+                status: ProcessState::Running,
+                ..Default::default()
+            })
+            .collect();
+        Snapshot {
+            processes,
+            ..Snapshot::default()
+        }
+    }
+
+    #[test]
+    fn vanished_processes_leave_no_entries_behind() {
+        let collector = Sequence(vec![with_pids(&[1, 7]), with_pids(&[1])]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.update();
+        fn pids<V>(map: &HashMap<u32, V>) -> Vec<u32> {
+            let mut pids: Vec<_> = map.keys().copied().collect();
+            pids.sort_unstable();
+            pids
+        }
+        assert_eq!(pids(&state.process_first_seen), [1]);
+        assert_eq!(pids(&state.process_io_totals), [1]);
+    }
+
+    #[test]
+    fn hidden_self_is_never_the_session_top_process() {
+        let busy = || {
+            let mut snapshot = with_pids(&[1, 7]);
+            snapshot.processes[0].cpu_usage = 5.0;
+            snapshot.processes[1].cpu_usage = 90.0;
+            snapshot
+        };
+        let mut state = SystemState::new(Box::new(Sequence(vec![busy(), busy(), busy()]))).unwrap();
+        state.own_pid = Some(7);
+        state.update();
+        assert!(state.session.to_string().contains("Top process: p7 (7)"));
+
+        state.hide_self = true;
+        state.reset_history();
+        state.update();
+        assert!(state.session.to_string().contains("Top process: p1 (1)"));
+    }
+
+    #[test]
+    fn processes_count_as_new_for_two_samples() {
+        let collector = Sequence(vec![
+            with_pids(&[1]),
+            with_pids(&[1, 7]),
+            with_pids(&[1, 7]),
+            with_pids(&[1, 7]),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        assert!(!state.is_new_process(1));
+
+        state.update();
+        assert!(!state.is_new_process(1));
+        assert!(state.is_new_process(7));
+        state.update();
+        assert!(state.is_new_process(7));
+        state.update();
+        assert!(!state.is_new_process(7));
+    }
+
+    #[test]
+    fn disk_rates_come_from_lifetime_totals_per_process() {
+        let with_reads = |reads: &[(u32, u64)]| {
+            let pids: Vec<_> = reads.iter().map(|&(pid, _)| pid).collect();
+            let mut snapshot = with_pids(&pids);
+            for (process, &(_, read)) in snapshot.processes.iter_mut().zip(reads) {
+                process.disk_total_read = read;
+            }
+            snapshot
+        };
+        let collector = Sequence(vec![
+            with_reads(&[(1, 10_000), (7, 10_000)]),
+            with_reads(&[(1, 11_000), (7, 13_000)]),
+            with_reads(&[(1, 12_000), (9, 50_000)]),
+            with_reads(&[(1, 12_000), (7, 500)]),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.update();
+
+        let rates: Vec<_> = state
+            .snapshot
+            .processes
+            .iter()
+            .map(|process| process.disk_read_rate)
+            .collect();
+        assert!(rates[1] > rates[0] * 2, "{rates:?}");
+
+        state.update();
+        assert_eq!(state.snapshot.processes[1].disk_read_rate, 0, "new PID");
+        assert!(!state.process_io_totals.contains_key(&7), "exited PID");
+        state.update();
+        assert_eq!(state.snapshot.processes[1].disk_read_rate, 0, "reused PID");
+    }
+
+    #[test]
+    fn process_rates_span_the_samples_since_the_last_refresh() {
+        let with_read = |read, stale| {
+            let mut snapshot = with_pids(&[1]);
+            snapshot.processes[0].disk_total_read = read;
+            snapshot.processes_stale = stale;
+            snapshot
+        };
+        let collector = Sequence(vec![
+            with_read(0, false),
+            with_read(1_000, false),
+            with_read(1_000, true),
+            with_read(11_000, false),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.process_io_at = Instant::now() - Duration::from_secs(1);
+        state.update();
+        let rate = state.snapshot.processes[0].disk_read_rate;
+        assert!((900..=1_000).contains(&rate), "{rate}");
+
+        state.update();
+        assert_eq!(state.snapshot.processes[0].disk_read_rate, rate, "kept");
+
+        // Refreshed after five seconds, with samples in between.
+        state.process_io_at = Instant::now() - Duration::from_secs(5);
+        state.update();
+        let rate = state.snapshot.processes[0].disk_read_rate;
+        assert!((1_900..=2_000).contains(&rate), "{rate}");
+    }
+
+    #[test]
+    fn disk_io_is_kept_per_device() {
+        let disk = |mount_point: &str, device: &str, total_read| DiskSnapshot {
+            mount_point: mount_point.to_string(),
+            device: device.to_string(),
+            file_system: "ext4".to_string(),
+            total_read,
+            ..Default::default()
+        };
+        let with_disks = |disks| Snapshot {
+            disks,
+            ..Snapshot::default()
+        };
+        let collector = Sequence(vec![
+            with_disks(vec![disk("/", "nvme0n1p2", 1_000)]),
+            with_disks(vec![
+                disk("/", "nvme0n1p2", 2_000),
+                disk("/home", "nvme0n1p2", 2_000),
+            ]),
+            with_disks(vec![
+                disk("/", "nvme0n1p2", 3_000),
+                disk("/home", "nvme0n1p2", 3_000),
+                disk("/backup", "sdb1", 5_000_000),
+            ]),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.update();
+        state.update();
+
+        let nvme = state.device_io(Some("nvme0n1p2"));
+        assert_eq!(nvme.len(), 2);
+        assert!(nvme.iter().all(|&(read, _)| read > 0));
+        assert_eq!(state.device_io(Some("sdb1")), [(0, 0)], "new device");
+        assert_eq!(state.device_io(None), nvme, "each device counts once");
+    }
+
+    #[test]
+    fn device_rates_span_the_samples_since_the_last_refresh() {
+        let with_read = |total_read, stale| Snapshot {
+            disks: vec![DiskSnapshot {
+                mount_point: "/".to_string(),
+                device: "sda1".to_string(),
+                file_system: "ext4".to_string(),
+                total_read,
+                ..Default::default()
+            }],
+            disks_stale: stale,
+            ..Snapshot::default()
+        };
+        let collector = Sequence(vec![
+            with_read(0, false),
+            with_read(1_000, false),
+            with_read(1_000, true),
+            with_read(11_000, false),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.disk_io_at = Instant::now() - Duration::from_secs(1);
+        state.update();
+        state.update();
+        let [(first, _), (kept, _)] = state.device_io(Some("sda1"))[..] else {
+            panic!("two samples");
+        };
+        assert!((900..=1_000).contains(&first), "{first}");
+        assert_eq!(kept, first);
+
+        state.disk_io_at = Instant::now() - Duration::from_secs(5);
+        state.update();
+        let (read, _) = state.device_io(Some("sda1"))[2];
+        assert!((1_900..=2_000).contains(&read), "{read}");
+    }
+
+    #[test]
+    fn tracked_processes_keep_history_and_freeze_on_exit() {
+        let collector = Sequence(vec![
+            with_pids(&[1, 7]),
+            with_pids(&[1, 7]),
+            with_pids(&[1]),
+            with_pids(&[1, 7]),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.track_processes(&[7, 1]);
+        assert_eq!(state.process_history(7).unwrap().samples.len(), 1);
+
+        state.update();
+        state.track_processes(&[7]);
+        assert!(state.process_history(1).is_none());
+        let history = state.process_history(7).unwrap();
+        assert_eq!((history.name.as_str(), history.samples.len()), ("p7", 2));
+
+        state.update();
+        state.update();
+        let history = state.process_history(7).unwrap();
+        assert!(history.exited, "a reappearing PID doesn't revive it");
+        assert_eq!(history.samples.len(), 2);
+    }
+
+    #[test]
+    fn reset_history_clears_buffers_and_totals() {
+        let collector = Sequence(vec![
+            with_interfaces(vec![interface("eth0", 0)]),
+            with_interfaces(vec![interface("eth0", 500)]),
+        ]);
+        let mut state = SystemState::new(Box::new(collector)).unwrap();
+        state.update();
+        state.reset_history();
+
+        assert!(state.cpu_history.is_empty());
+        assert!(state.core_history.is_empty());
+        assert!(state.network_history.is_empty());
+        assert_eq!(state.session_totals, (0, 0));
+        assert_eq!(state.network_availability(), DataAvailability::Collecting);
     }
 }
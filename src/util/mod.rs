@@ -1,3 +1,25 @@
+mod availability;
+mod detection;
+mod downsample;
+mod format;
 mod history;
+mod process_history;
+mod risk;
+mod session;
+mod snapshot;
 
-pub use self::history::SystemState;
+pub use self::availability::DataAvailability;
+pub use self::detection::DetectionSummary;
+pub use self::downsample::downsample;
+pub use self::format::{
+    GaugeLabel, Precision, abbreviate_count, format_age, format_ago, format_local_time,
+    format_timestamp, format_total_bytes, gauge_label, os_str_display, time_axis_labels,
+};
+pub use self::history::{Ghost, HISTORY_LEN, SystemState, collect, lock_state};
+pub use self::process_history::{ProcessHistory, ProcessSample};
+pub use self::risk::{OomRisk, oom_risk};
+pub use self::snapshot::{
+    CgroupSnapshot, CpuSnapshot, DiskSnapshot, FanSnapshot, InterfaceSnapshot,
+    KernelCountersSnapshot, KernelMemorySnapshot, MemorySnapshot, NumaNodeSnapshot,
+    ProcessSnapshot, ProcessState, SensorSnapshot, Snapshot, ThermalPressure,
+};
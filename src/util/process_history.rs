@@ -0,0 +1,80 @@
+use super::{HISTORY_LEN, Snapshot};
+
+/// One sample of a tracked process.
+#[derive(Clone, Copy)]
+pub struct ProcessSample {
+    /// Usage in percent of a single core.
+    pub cpu_usage: f32,
+    /// Resident memory in bytes.
+    pub memory: u64,
+    /// `None` while open files aren't gathered or can't be read.
+    pub open_files: Option<u32>,
+    /// Bytes per second read from disk.
+    pub disk_read_rate: u64,
+    /// Bytes per second written to disk.
+    pub disk_write_rate: u64,
+}
+
+/// Metrics of a single process over time, kept while the dashboard tracks
+/// its PID. Once the process exits the history is frozen rather than
+/// dropped, so its last moments stay on screen.
+pub struct ProcessHistory {
+    pub pid: u32,
+    /// Name at the last sample, or "?" if the process was never seen.
+    pub name: String,
+    /// Oldest first, at most `HISTORY_LEN` entries.
+    pub samples: Vec<ProcessSample>,
+    /// The PID went away, or now belongs to a different process.
+    pub exited: bool,
+    /// Start time of the process first seen under `pid`, to tell it apart
+    /// from a later process reusing the PID.
+    start_time: Option<u64>,
+}
+
+impl ProcessHistory {
+    pub fn new(pid: u32) -> Self {
+        Self {
+            pid,
+            name: "?".to_string(),
+            samples: Vec::with_capacity(HISTORY_LEN),
+            exited: false,
+            start_time: None,
+        }
+    }
+
+    /// Appends the process's entry in `snapshot`, or marks it exited when
+    /// there is none.
+    pub fn observe(&mut self, snapshot: &Snapshot) {
+        if self.exited {
+            return;
+        }
+        let process = snapshot
+            .processes
+            .iter()
+            .find(|process| process.pid == self.pid)
+            .filter(|process| {
+                self.start_time
+                    .is_none_or(|start| start == process.start_time)
+            });
+        let Some(process) = process else {
+            self.exited = true;
+            return;
+        };
+        self.start_time = Some(process.start_time);
+        self.name.clone_from(&process.name);
+        self.samples.push(ProcessSample {
+            cpu_usage: process.cpu_usage,
+            memory: process.memory,
+            open_files: process.open_files,
+            disk_read_rate: process.disk_read_rate,
+            disk_write_rate: process.disk_write_rate,
+        });
+        if self.samples.len() > HISTORY_LEN {
+            self.samples.remove(0);
+        }
+    }
+
+    pub fn last(&self) -> Option<&ProcessSample> {
+        self.samples.last()
+    }
+}
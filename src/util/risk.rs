@@ -0,0 +1,129 @@
+use super::MemorySnapshot;
+
+/// Samples back the usage trend is measured over.
+const TREND_WINDOW: usize = 10;
+
+/// How likely the machine is to start killing processes for lack of memory,
+/// folded from available memory, swap headroom and the recent trend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OomRisk {
+    Low,
+    Elevated,
+    High,
+}
+
+impl OomRisk {
+    pub fn label(self) -> &'static str {
+        match self {
+            OomRisk::Low => "low",
+            OomRisk::Elevated => "elevated",
+            OomRisk::High => "high",
+        }
+    }
+}
+
+/// Rates the OOM risk for `memory`, given the `(used, total)` history the
+/// trend is read from (oldest first, as in `SystemState::memory_history`).
+///
+/// With a fifth or more of RAM available the risk is always low. Below that,
+/// less available memory, usage that grew by over 1% of RAM across the last
+/// `TREND_WINDOW` samples, and swap headroom under a tenth of RAM each add to
+/// the score.
+pub fn oom_risk(memory: &MemorySnapshot, history: &[(u64, u64)]) -> OomRisk {
+    if memory.total == 0 {
+        return OomRisk::Low;
+    }
+    let total = memory.total as f64;
+    let available = memory.total.saturating_sub(memory.used) as f64 / total;
+    if available >= 0.20 {
+        return OomRisk::Low;
+    }
+
+    let mut score = if available < 0.05 {
+        3
+    } else if available < 0.10 {
+        2
+    } else {
+        1
+    };
+
+    let window = &history[history.len().saturating_sub(TREND_WINDOW)..];
+    if let (Some(&(first, _)), Some(&(last, _))) = (window.first(), window.last())
+        && last.saturating_sub(first) as f64 / total > 0.01
+    {
+        score += 1;
+    }
+
+    let swap_headroom = memory.swap_total.saturating_sub(memory.swap_used) as f64 / total;
+    if swap_headroom < 0.10 {
+        score += 1;
+    }
+
+    match score {
+        4.. => OomRisk::High,
+        2..=3 => OomRisk::Elevated,
+        _ => OomRisk::Low,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1 << 30;
+
+    fn memory(used: u64, swap_total: u64, swap_used: u64) -> MemorySnapshot {
+        MemorySnapshot {
+            used,
+            total: 16 * GIB,
+            swap_used,
+            swap_total,
+            swap_is_commit: false,
+        }
+    }
+
+    fn rising_to(used: u64) -> Vec<(u64, u64)> {
+        (0..TREND_WINDOW as u64)
+            .map(|i| (used - (TREND_WINDOW as u64 - 1 - i) * GIB / 8, 16 * GIB))
+            .collect()
+    }
+
+    #[test]
+    fn plenty_of_memory_is_low_risk_even_without_swap() {
+        let used = 8 * GIB;
+        assert_eq!(
+            oom_risk(&memory(used, 0, 0), &rising_to(used)),
+            OomRisk::Low
+        );
+    }
+
+    #[test]
+    fn nearly_full_and_rising_without_swap_is_high_risk() {
+        let used = 15 * GIB;
+        assert_eq!(
+            oom_risk(&memory(used, 0, 0), &rising_to(used)),
+            OomRisk::High
+        );
+    }
+
+    #[test]
+    fn swap_headroom_lowers_the_risk() {
+        let used = 15 * GIB;
+        let swap = memory(used, 8 * GIB, 0);
+        assert_eq!(oom_risk(&swap, &rising_to(used)), OomRisk::Elevated);
+    }
+
+    #[test]
+    fn steady_usage_lowers_the_risk() {
+        let used = 15 * GIB;
+        let steady = vec![(used, 16 * GIB); TREND_WINDOW];
+        assert_eq!(oom_risk(&memory(used, 0, 0), &steady), OomRisk::Elevated);
+    }
+
+    #[test]
+    fn almost_no_memory_left_is_high_risk_when_swap_is_full() {
+        let used = 16 * GIB - GIB / 2;
+        let full_swap = memory(used, 4 * GIB, 4 * GIB);
+        assert_eq!(oom_risk(&full_swap, &[]), OomRisk::High);
+    }
+}
@@ -0,0 +1,77 @@
+use std::{collections::HashMap, fmt};
+
+use super::Snapshot;
+
+/// Running min/max/accumulator stats over the whole session, printed as a
+/// recap after the TUI exits.
+#[derive(Default)]
+pub struct SessionStats {
+    samples: u64,
+    peak_cpu: f32,
+    peak_memory: u64,
+    total_memory: u64,
+    received: u64,
+    transmitted: u64,
+    /// Ticks each process spent as the top CPU consumer, keyed by
+    /// "name (pid)".
+    top_process_ticks: HashMap<String, u64>,
+}
+
+impl SessionStats {
+    /// Counts one sample. The process `hidden` names, dashoxide's own while
+    /// it's hidden, is never taken for the top process.
+    pub fn observe(
+        &mut self,
+        snapshot: &Snapshot,
+        received: u64,
+        transmitted: u64,
+        hidden: Option<u32>,
+    ) {
+        self.samples += 1;
+        self.peak_cpu = self.peak_cpu.max(snapshot.cpu.usage);
+        self.peak_memory = self.peak_memory.max(snapshot.memory.used);
+        self.total_memory = snapshot.memory.total;
+        self.received = self.received.saturating_add(received);
+        self.transmitted = self.transmitted.saturating_add(transmitted);
+
+        let top = snapshot
+            .processes
+            .iter()
+            .filter(|process| Some(process.pid) != hidden)
+            .max_by(|a, b| a.cpu_usage.total_cmp(&b.cpu_usage));
+        if let Some(process) = top {
+            let key = format!("{} ({})", process.name, process.pid);
+            *self.top_process_ticks.entry(key).or_default() += 1;
+        }
+    }
+}
+
+impl fmt::Display for SessionStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const GIB: f64 = (1_024 * 1_024 * 1_024) as f64;
+        const MB: f64 = 1_000_000.0;
+
+        writeln!(f, "dashoxide session summary ({} samples)", self.samples)?;
+        writeln!(f, "  Peak CPU:    {:.1}%", self.peak_cpu)?;
+        writeln!(
+            f,
+            "  Peak memory: {:.2} / {:.2} GiB",
+            self.peak_memory as f64 / GIB,
+            self.total_memory as f64 / GIB
+        )?;
+        writeln!(
+            f,
+            "  Network:     {:.2} MB received, {:.2} MB transmitted",
+            self.received as f64 / MB,
+            self.transmitted as f64 / MB
+        )?;
+        match self
+            .top_process_ticks
+            .iter()
+            .max_by_key(|(_, ticks)| **ticks)
+        {
+            Some((name, ticks)) => write!(f, "  Top process: {name}, on top for {ticks} samples"),
+            None => write!(f, "  Top process: none observed"),
+        }
+    }
+}
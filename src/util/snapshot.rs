@@ -0,0 +1,674 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+/// File systems that hold no persistent storage of their own, or that
+/// re-expose another one (overlay), or that are read-only images (squashfs
+/// snaps), so counting them towards disk capacity misleads.
+const PSEUDO_FILE_SYSTEMS: [&str; 22] = [
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "overlay",
+    "proc",
+    "pstore",
+    "ramfs",
+    "securityfs",
+    "squashfs",
+    "sysfs",
+    "tmpfs",
+];
+
+/// Kernel interfaces mounted as file systems live under these.
+const PSEUDO_MOUNT_PREFIXES: [&str; 3] = ["/proc", "/sys", "/dev"];
+
+/// Name prefixes of container and VM plumbing: veth pairs, bridges and the
+/// interfaces of common CNI plugins. Their traffic also crosses a real
+/// interface, or never leaves the host.
+const VIRTUAL_INTERFACE_PREFIXES: [&str; 9] = [
+    "veth", "docker", "br-", "virbr", "vnet", "cni", "flannel", "cali", "lxc",
+];
+
+/// A point-in-time copy of everything the dashboard shows, independent of
+/// where it was collected. This is what collectors produce, what the
+/// renderers read, and what `--report` and the HTTP API serialize.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Seconds since the Unix epoch when the snapshot was taken.
+    pub timestamp: u64,
+    pub cpu: CpuSnapshot,
+    pub memory: MemorySnapshot,
+    pub disks: Vec<DiskSnapshot>,
+    pub networks: Vec<InterfaceSnapshot>,
+    pub processes: Vec<ProcessSnapshot>,
+    #[serde(default)]
+    pub sensors: Vec<SensorSnapshot>,
+    /// `None` when the platform gives no way to tell.
+    #[serde(default)]
+    pub battery: Option<bool>,
+    /// Limits of the cgroup the collector runs in, `None` outside a
+    /// container or when nothing is limited.
+    #[serde(default)]
+    pub cgroup: Option<CgroupSnapshot>,
+    /// Empty where the platform has no NUMA or doesn't say.
+    #[serde(default)]
+    pub numa_nodes: Vec<NumaNodeSnapshot>,
+    /// `None` off Linux, where the kernel's breakdown isn't available.
+    #[serde(default)]
+    pub kernel_memory: Option<KernelMemorySnapshot>,
+    /// `None` off Linux; `SystemState` turns them into rates.
+    #[serde(default)]
+    pub kernel_counters: Option<KernelCountersSnapshot>,
+    /// Fan speeds; only read from the SMC on macOS so far.
+    #[serde(default)]
+    pub fans: Vec<FanSnapshot>,
+    /// How hard macOS is throttling to keep cool; `None` elsewhere.
+    #[serde(default)]
+    pub thermal_pressure: Option<ThermalPressure>,
+    /// The processes are the previous sample's: the collector refreshes
+    /// them on a slower cadence while no view shows them.
+    #[serde(default)]
+    pub processes_stale: bool,
+    /// Likewise for disks.
+    #[serde(default)]
+    pub disks_stale: bool,
+}
+
+impl Snapshot {
+    /// Temperature of the CPU package, or of its hottest core when no
+    /// package sensor is listed. `None` when no sensor looks like a CPU's.
+    pub fn cpu_temperature(&self) -> Option<f32> {
+        let hottest = |patterns: &[&str]| {
+            self.sensors
+                .iter()
+                .filter(|sensor| {
+                    let label = sensor.label.to_lowercase();
+                    patterns.iter().any(|pattern| label.contains(pattern))
+                })
+                .filter_map(|sensor| sensor.temperature)
+                .reduce(f32::max)
+        };
+        hottest(&["package", "tctl", "tdie", "cpu"]).or_else(|| hottest(&["core"]))
+    }
+
+    /// Temperature of the hottest sensor labelled as a GPU's.
+    pub fn gpu_temperature(&self) -> Option<f32> {
+        self.sensors
+            .iter()
+            .filter(|sensor| sensor.label.to_lowercase().contains("gpu"))
+            .filter_map(|sensor| sensor.temperature)
+            .reduce(f32::max)
+    }
+
+    /// Used and total bytes summed over the disks. Unless `include_all` is
+    /// set, pseudo file systems are left out and a device mounted more than
+    /// once (bind mounts, btrfs subvolumes) is counted once, so overlay-heavy
+    /// container hosts don't report several times their real capacity.
+    /// Inside a container, where the root is an overlay and no real file
+    /// system is listed, the largest mount stands in: an overlay reports
+    /// the space of the disk under it.
+    pub fn disk_space(&self, include_all: bool) -> (u64, u64) {
+        let mut seen = HashSet::new();
+        let (used, total) = self
+            .disks
+            .iter()
+            .filter(|disk| {
+                include_all
+                    || (!disk.is_pseudo()
+                        && seen.insert(if disk.device.is_empty() {
+                            &disk.mount_point
+                        } else {
+                            &disk.device
+                        }))
+            })
+            .fold((0, 0), |(used, total), disk| {
+                (used + disk.used(), total + disk.total)
+            });
+        if total > 0 {
+            return (used, total);
+        }
+        self.disks
+            .iter()
+            .max_by_key(|disk| disk.total)
+            .map_or((0, 0), |disk| (disk.used(), disk.total))
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct CpuSnapshot {
+    /// Global usage in percent.
+    pub usage: f32,
+    /// Per-core usage in percent, in the order the OS reports cores.
+    pub cores: Vec<f32>,
+}
+
+/// All values are in bytes.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct MemorySnapshot {
+    pub used: u64,
+    pub total: u64,
+    /// Swap in use, or the commit charge; see `swap_is_commit`.
+    pub swap_used: u64,
+    pub swap_total: u64,
+    /// Whether the swap figures are the commit charge and its limit, as
+    /// collected on Windows, which has no swap as such. Set by the collector
+    /// on the machine the data came from, so remote and recorded data keep
+    /// their own meaning.
+    #[serde(default)]
+    pub swap_is_commit: bool,
+}
+
+impl MemorySnapshot {
+    /// What the swap figures are called.
+    pub fn swap_label(&self) -> &'static str {
+        if self.swap_is_commit {
+            "Commit"
+        } else {
+            "Swap"
+        }
+    }
+}
+
+/// Memory values are in bytes; a `None` limit means that resource isn't
+/// limited.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CgroupSnapshot {
+    /// cgroup version, 1 or 2.
+    pub version: u8,
+    pub memory_limit: Option<u64>,
+    pub memory_used: Option<u64>,
+    /// CPU quota in cores, e.g. 1.5 for 150ms of CPU time per 100ms period.
+    pub cpu_limit: Option<f32>,
+}
+
+impl CgroupSnapshot {
+    /// Used and limit bytes, when memory is limited.
+    pub fn memory(&self) -> Option<(u64, u64)> {
+        Some((self.memory_used?, self.memory_limit?))
+    }
+}
+
+/// One NUMA node. Memory values are in bytes.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NumaNodeSnapshot {
+    pub id: u32,
+    pub total: u64,
+    pub free: u64,
+    /// Indices into `CpuSnapshot::cores` of the CPUs on this node.
+    pub cpus: Vec<usize>,
+}
+
+impl NumaNodeSnapshot {
+    /// Average usage of the node's CPUs, `None` if none of them is listed
+    /// in `cores`.
+    pub fn cpu_usage(&self, cores: &[f32]) -> Option<f32> {
+        let usages: Vec<_> = self.cpus.iter().filter_map(|&cpu| cores.get(cpu)).collect();
+        (!usages.is_empty()).then(|| usages.iter().copied().sum::<f32>() / usages.len() as f32)
+    }
+}
+
+/// Memory the kernel holds for itself and pages waiting to be written
+/// out, in bytes.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KernelMemorySnapshot {
+    pub slab_reclaimable: u64,
+    pub slab_unreclaimable: u64,
+    pub page_tables: u64,
+    pub kernel_stack: u64,
+    /// Modified pages not yet written back to disk.
+    pub dirty: u64,
+    /// Pages being written back right now.
+    pub writeback: u64,
+}
+
+impl KernelMemorySnapshot {
+    /// Kernel object caches, reclaimable or not.
+    pub fn slab(&self) -> u64 {
+        self.slab_reclaimable + self.slab_unreclaimable
+    }
+}
+
+/// Scheduler activity counted since boot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KernelCountersSnapshot {
+    pub context_switches: u64,
+    /// Interrupts of every kind, summed.
+    pub interrupts: u64,
+}
+
+/// Space values are in bytes.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct DiskSnapshot {
+    pub mount_point: String,
+    /// Device the file system lives on, e.g. "/dev/sda1", shared by every
+    /// mount of it. Empty when unknown.
+    #[serde(default)]
+    pub device: String,
+    pub file_system: String,
+    pub total: u64,
+    pub available: u64,
+    #[serde(default)]
+    pub removable: bool,
+    #[serde(default)]
+    pub read_only: bool,
+    /// Bytes read from the device since boot; the same for every mount of
+    /// it.
+    #[serde(default)]
+    pub total_read: u64,
+    /// Bytes written to the device since boot.
+    #[serde(default)]
+    pub total_written: u64,
+    /// Bytes per second read from the device, derived by `SystemState` from
+    /// consecutive totals; collectors leave it at zero.
+    #[serde(default)]
+    pub read_rate: u64,
+    /// Bytes per second written to the device, derived like `read_rate`.
+    #[serde(default)]
+    pub write_rate: u64,
+}
+
+impl DiskSnapshot {
+    /// Space in use. Some network and overlay file systems report more
+    /// available than their total, which counts as none used.
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.available)
+    }
+
+    /// Whether this is a virtual or kernel file system rather than real
+    /// storage, judged by its type and where it's mounted.
+    pub fn is_pseudo(&self) -> bool {
+        PSEUDO_FILE_SYSTEMS.contains(&self.file_system.as_str())
+            || PSEUDO_MOUNT_PREFIXES.iter().any(|prefix| {
+                self.mount_point
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InterfaceSnapshot {
+    pub name: String,
+    /// Bytes per second received, derived by `SystemState` from consecutive
+    /// totals; collectors leave it at zero.
+    #[serde(default)]
+    pub rx_rate: u64,
+    /// Bytes per second transmitted, derived like `rx_rate`.
+    #[serde(default)]
+    pub tx_rate: u64,
+    /// Cumulative bytes received as reported by the OS: sysinfo reads the
+    /// interface statistics (`/sys/class/net/*/statistics` on Linux) that
+    /// one long-lived `Networks` refreshes. May wrap or restart from zero.
+    pub total_received: u64,
+    /// Cumulative bytes transmitted, from the same source.
+    pub total_transmitted: u64,
+    /// Whether the link is up; `None` where the platform doesn't say.
+    #[serde(default)]
+    pub up: Option<bool>,
+    /// Hardware address, `None` for interfaces without one such as `lo`.
+    #[serde(default)]
+    pub mac_address: Option<String>,
+    #[serde(default)]
+    pub mtu: Option<u64>,
+    /// Addresses with their prefix length, e.g. "192.168.1.2/24".
+    #[serde(default)]
+    pub addresses: Vec<String>,
+}
+
+impl InterfaceSnapshot {
+    /// `lo` on Linux, `lo0` on macOS and the BSDs.
+    pub fn is_loopback(&self) -> bool {
+        self.name
+            .strip_prefix("lo")
+            .is_some_and(|rest| rest.chars().all(|c| c.is_ascii_digit()))
+    }
+
+    /// Whether this is loopback or container/VM plumbing rather than a link
+    /// to the outside, judged by its name.
+    pub fn is_virtual(&self) -> bool {
+        self.is_loopback()
+            || VIRTUAL_INTERFACE_PREFIXES
+                .iter()
+                .any(|prefix| self.name.starts_with(prefix))
+    }
+
+    /// Whether the name matches any of the comma-separated glob `patterns`,
+    /// e.g. "eth*,en*". `*` stands for any run of characters and `?` for
+    /// one; an empty list matches every interface.
+    pub fn matches(&self, patterns: &str) -> bool {
+        let name: Vec<char> = self.name.chars().collect();
+        let mut patterns = patterns
+            .split(',')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .peekable();
+        patterns.peek().is_none()
+            || patterns.any(|pattern| glob_matches(&pattern.chars().collect::<Vec<_>>(), &name))
+    }
+}
+
+/// Matches `name` against a glob with `*` and `?` wildcards, backtracking
+/// to the last `*` on a mismatch.
+fn glob_matches(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ProcessSnapshot {
+    pub pid: u32,
+    pub name: String,
+    /// Usage in percent of a single core.
+    pub cpu_usage: f32,
+    /// Resident memory in bytes.
+    pub memory: u64,
+    pub status: ProcessState,
+    /// Bytes read from disk since the previous refresh.
+    #[serde(default)]
+    pub disk_read: u64,
+    /// Bytes written to disk since the previous refresh.
+    #[serde(default)]
+    pub disk_written: u64,
+    /// Bytes read from disk over the process's lifetime.
+    #[serde(default)]
+    pub disk_total_read: u64,
+    /// Bytes written to disk over the process's lifetime.
+    #[serde(default)]
+    pub disk_total_written: u64,
+    /// Bytes per second read from disk, derived by `SystemState` from
+    /// consecutive lifetime totals; collectors leave it at zero.
+    #[serde(default)]
+    pub disk_read_rate: u64,
+    /// Bytes per second written to disk, derived like `disk_read_rate`.
+    #[serde(default)]
+    pub disk_write_rate: u64,
+    /// `None` where the platform doesn't list a process's threads.
+    #[serde(default)]
+    pub threads: Option<usize>,
+    /// Name of the owning user, if it could be resolved.
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Seconds since the Unix epoch when the process started.
+    #[serde(default)]
+    pub start_time: u64,
+    /// Open file descriptors; `None` unless requested, or when the process
+    /// belongs to another user and can't be inspected.
+    #[serde(default)]
+    pub open_files: Option<u32>,
+    /// Soft limit on open file descriptors, `None` if unlimited or unknown.
+    #[serde(default)]
+    pub open_files_limit: Option<u32>,
+    /// Bytes swapped out; `None` unless requested, off Linux, or when the
+    /// process can't be inspected.
+    #[serde(default)]
+    pub swap: Option<u64>,
+    /// Scheduling nice value, -20 to 19; `None` off Linux.
+    #[serde(default)]
+    pub nice: Option<i32>,
+}
+
+impl ProcessSnapshot {
+    /// Whether the process uses at least 80% of its file descriptor limit,
+    /// a common sign of a descriptor leak.
+    pub fn near_open_files_limit(&self) -> bool {
+        match (self.open_files, self.open_files_limit) {
+            (Some(open), Some(limit)) => u64::from(open) * 5 >= u64::from(limit) * 4,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FanSnapshot {
+    pub label: String,
+    /// Revolutions per minute; 0 for a fan that's stopped.
+    pub rpm: f32,
+}
+
+/// macOS's thermal pressure level, from none to the system about to sleep
+/// to cool down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThermalPressure {
+    Nominal,
+    Moderate,
+    Heavy,
+    Trapping,
+    Sleeping,
+}
+
+impl ThermalPressure {
+    pub fn label(self) -> &'static str {
+        match self {
+            ThermalPressure::Nominal => "nominal",
+            ThermalPressure::Moderate => "moderate",
+            ThermalPressure::Heavy => "heavy",
+            ThermalPressure::Trapping => "trapping",
+            ThermalPressure::Sleeping => "sleeping",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SensorSnapshot {
+    pub label: String,
+    /// Degrees Celsius, if the sensor currently reports one.
+    pub temperature: Option<f32>,
+}
+
+/// Platform-neutral process state, so snapshots from other machines (or
+/// other sysinfo versions) deserialize to the same set of values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessState {
+    Running,
+    Waking,
+    Sleeping,
+    Parked,
+    Idle,
+    Stopped,
+    Traced,
+    Wakekill,
+    DiskWait,
+    LockWait,
+    Zombie,
+    Dead,
+    #[default]
+    Unknown,
+}
+
+impl ProcessState {
+    /// Short human-readable name, e.g. "disk wait" for `DiskWait`.
+    pub fn label(self) -> &'static str {
+        match self {
+            ProcessState::Running => "running",
+            ProcessState::Waking => "waking",
+            ProcessState::Sleeping => "sleeping",
+            ProcessState::Parked => "parked",
+            ProcessState::Idle => "idle",
+            ProcessState::Stopped => "stopped",
+            ProcessState::Traced => "traced",
+            ProcessState::Wakekill => "wakekill",
+            ProcessState::DiskWait => "disk wait",
+            ProcessState::LockWait => "lock wait",
+            ProcessState::Zombie => "zombie",
+            ProcessState::Dead => "dead",
+            ProcessState::Unknown => "unknown",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_sensors(sensors: &[(&str, Option<f32>)]) -> Snapshot {
+        Snapshot {
+            sensors: sensors
+                .iter()
+                .map(|&(label, temperature)| SensorSnapshot {
+                    label: label.to_string(),
+                    temperature,
+                })
+                .collect(),
+            ..Snapshot::default()
+        }
+    }
+
+    #[test]
+    fn cpu_temperature_prefers_the_package_sensor() {
+        let snapshot = with_sensors(&[
+            ("coretemp Core 0", Some(70.0)),
+            ("coretemp Package id 0", Some(64.0)),
+            ("nvme Composite", Some(80.0)),
+        ]);
+        assert_eq!(snapshot.cpu_temperature(), Some(64.0));
+
+        let snapshot = with_sensors(&[("coretemp Core 0", Some(55.0)), ("Core 1", Some(58.0))]);
+        assert_eq!(snapshot.cpu_temperature(), Some(58.0));
+
+        let snapshot = with_sensors(&[("nvme Composite", Some(80.0)), ("acpitz", Some(40.0))]);
+        assert_eq!(snapshot.cpu_temperature(), None);
+    }
+
+    #[test]
+    fn gpu_temperature_is_kept_apart_from_the_cpu() {
+        let snapshot = with_sensors(&[
+            ("SMC CPU die", Some(55.0)),
+            ("SMC GPU proximity", Some(48.0)),
+            ("amdgpu edge", Some(52.0)),
+        ]);
+        assert_eq!(snapshot.cpu_temperature(), Some(55.0));
+        assert_eq!(snapshot.gpu_temperature(), Some(52.0));
+        assert_eq!(with_sensors(&[]).gpu_temperature(), None);
+    }
+
+    fn disk(mount_point: &str, device: &str, file_system: &str, total: u64) -> DiskSnapshot {
+        DiskSnapshot {
+            mount_point: mount_point.to_string(),
+            device: device.to_string(),
+            file_system: file_system.to_string(),
+            total,
+            available: total / 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn disk_space_counts_each_real_device_once() {
+        let snapshot = Snapshot {
+            disks: vec![
+                disk("/", "/dev/sda1", "ext4", 100),
+                disk("/var/lib/docker", "/dev/sda1", "ext4", 100),
+                disk("/home", "/dev/sdb1", "xfs", 300),
+                disk(
+                    "/var/lib/docker/overlay2/x/merged",
+                    "overlay",
+                    "overlay",
+                    100,
+                ),
+                disk("/run", "tmpfs", "tmpfs", 10),
+                disk("/snap/core/1", "/dev/loop0", "squashfs", 1),
+                disk("/sys/firmware/efi/efivars", "efivarfs", "efivarfs", 1),
+            ],
+            ..Snapshot::default()
+        };
+        assert_eq!(snapshot.disk_space(false), (200, 400));
+        assert_eq!(snapshot.disk_space(true), (307, 612));
+
+        let container = Snapshot {
+            disks: vec![
+                disk("/", "overlay", "overlay", 500),
+                disk("/dev/shm", "shm", "tmpfs", 64),
+            ],
+            ..Snapshot::default()
+        };
+        assert_eq!(container.disk_space(false), (250, 500));
+
+        let mut overfull = disk("/mnt/share", "server:/share", "nfs", 100);
+        overfull.available = overfull.total + 1;
+        assert_eq!(overfull.used(), 0);
+    }
+
+    #[test]
+    fn pseudo_mounts_are_matched_by_path_component() {
+        assert!(disk("/dev/shm", "", "ext4", 1).is_pseudo());
+        assert!(!disk("/devel", "/dev/sdc1", "ext4", 1).is_pseudo());
+    }
+
+    #[test]
+    fn interfaces_are_matched_by_glob() {
+        let interface = |name: &str| InterfaceSnapshot {
+            name: name.to_string(),
+            rx_rate: 0,
+            tx_rate: 0,
+            total_received: 0,
+            total_transmitted: 0,
+            up: None,
+            mac_address: None,
+            mtu: None,
+            addresses: Vec::new(),
+        };
+        assert!(interface("veth1a2b").matches(""));
+        assert!(interface("eth0").matches("eth*"));
+        assert!(interface("enp3s0").matches("eth*, en*"));
+        assert!(interface("wlan0").matches("wl?n*"));
+        assert!(interface("br-1").matches("*-?"));
+        assert!(!interface("veth1a2b").matches("eth*,en*"));
+        assert!(!interface("eth0").matches("eth"));
+    }
+
+    #[test]
+    fn loopback_and_container_interfaces_are_virtual() {
+        let interface = |name: &str| InterfaceSnapshot {
+            name: name.to_string(),
+            rx_rate: 0,
+            tx_rate: 0,
+            total_received: 0,
+            total_transmitted: 0,
+            up: None,
+            mac_address: None,
+            mtu: None,
+            addresses: Vec::new(),
+        };
+        for name in ["lo", "lo0", "veth1a2b", "docker0", "br-3f2a", "virbr0"] {
+            assert!(interface(name).is_virtual(), "{name}");
+        }
+        assert!(interface("lo0").is_loopback());
+        for name in ["eth0", "enp3s0", "wlan0", "lowpan0", "tun0"] {
+            assert!(!interface(name).is_virtual(), "{name}");
+        }
+    }
+}
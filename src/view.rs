@@ -0,0 +1,87 @@
+//! The dashboard's tabs, named here rather than in the dashboard so the
+//! command line can pick one without depending on the UI.
+
+use std::str::FromStr;
+
+/// A tab of the dashboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DashboardView {
+    Overview,
+    Cpu,
+    Memory,
+    Disk,
+    Network,
+    Processes,
+    /// One line per metric with a sparkline, for a small corner window.
+    Strip,
+    /// Alerts fired this session, newest first.
+    Alerts,
+    /// Histories of two chosen processes side by side.
+    Compare,
+}
+
+impl DashboardView {
+    pub const ALL: [DashboardView; 9] = [
+        DashboardView::Overview,
+        DashboardView::Cpu,
+        DashboardView::Memory,
+        DashboardView::Disk,
+        DashboardView::Network,
+        DashboardView::Processes,
+        DashboardView::Strip,
+        DashboardView::Alerts,
+        DashboardView::Compare,
+    ];
+
+    /// Place on the tab bar.
+    pub fn index(self) -> usize {
+        DashboardView::ALL
+            .iter()
+            .position(|view| *view == self)
+            .unwrap_or_default()
+    }
+
+    /// Name used on the command line.
+    pub fn key(self) -> &'static str {
+        match self {
+            DashboardView::Overview => "overview",
+            DashboardView::Cpu => "cpu",
+            DashboardView::Memory => "memory",
+            DashboardView::Disk => "disk",
+            DashboardView::Network => "network",
+            DashboardView::Processes => "processes",
+            DashboardView::Strip => "strip",
+            DashboardView::Alerts => "alerts",
+            DashboardView::Compare => "compare",
+        }
+    }
+
+    /// Name on the tab bar.
+    pub fn title(self) -> &'static str {
+        match self {
+            DashboardView::Overview => "Overview",
+            DashboardView::Cpu => "CPU",
+            DashboardView::Memory => "Memory",
+            DashboardView::Disk => "Disk",
+            DashboardView::Network => "Network",
+            DashboardView::Processes => "Processes",
+            DashboardView::Strip => "Strip",
+            DashboardView::Alerts => "Alerts",
+            DashboardView::Compare => "Compare",
+        }
+    }
+}
+
+impl FromStr for DashboardView {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DashboardView::ALL
+            .into_iter()
+            .find(|view| view.key() == s.trim())
+            .ok_or_else(|| {
+                let known: Vec<_> = DashboardView::ALL.iter().map(|v| v.key()).collect();
+                format!("unknown tab '{s}', expected one of: {}", known.join(", "))
+            })
+    }
+}